@@ -0,0 +1,40 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use twenty_first::math::other::random_elements;
+use twenty_first::prelude::*;
+
+criterion_main!(benches);
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = poly_xgcd<6>,
+              poly_xgcd<7>,
+              poly_xgcd<8>,
+              poly_xgcd<10>,
+              poly_xgcd<12>,
+              poly_xgcd<14>,
+);
+
+fn poly_xgcd<const LOG2_SIZE: usize>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!(
+        "Extended GCD of Polynomials of Degree 2^{LOG2_SIZE}"
+    ));
+
+    let new_poly = || Polynomial::<BFieldElement>::new(random_elements((1 << LOG2_SIZE) + 1));
+    let poly_0 = new_poly();
+    let poly_1 = new_poly();
+
+    let id = BenchmarkId::new("Euclidean", LOG2_SIZE);
+    group.bench_function(id, |b| {
+        b.iter(|| Polynomial::xgcd(poly_0.clone(), poly_1.clone()))
+    });
+
+    let id = BenchmarkId::new("Half-GCD", LOG2_SIZE);
+    group.bench_function(id, |b| {
+        b.iter(|| Polynomial::fast_xgcd(poly_0.clone(), poly_1.clone()))
+    });
+
+    group.finish();
+}
@@ -4,10 +4,21 @@ use thiserror::Error;
 
 pub use crate::math::bfield_codec::BFieldCodecError;
 pub use crate::math::bfield_codec::PolynomialBFieldCodecError;
+pub use crate::math::ecgfp5::EcGFp5Error;
+pub use crate::math::fri::FriValidationError;
+pub use crate::math::hash_signatures::xmss::XmssError;
+pub use crate::math::lattice::kem::KemDecodingError;
+pub use crate::math::lattice::kem::SealedMessageError;
+pub use crate::math::matrix::MatrixError;
+pub use crate::math::reed_solomon::ReedSolomonError;
+pub use crate::math::secret_sharing::SecretSharingError;
 use crate::prelude::tip5::Digest;
 use crate::prelude::x_field_element::EXTENSION_DEGREE;
 use crate::prelude::BFieldElement;
 pub use crate::util_types::merkle_tree::MerkleTreeError;
+pub use crate::util_types::mmr::archival_mmr::ArchivalMmrError;
+pub use crate::util_types::proof_stream::ProofStreamError;
+pub use crate::util_types::sparse_merkle_tree::SparseMerkleTreeError;
 
 #[derive(Debug, Clone, Eq, PartialEq, Error)]
 #[non_exhaustive]
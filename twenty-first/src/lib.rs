@@ -48,6 +48,8 @@ pub(crate) mod tests {
         implements_usual_auto_traits::<BFieldElement>();
         implements_usual_auto_traits::<Polynomial<BFieldElement>>();
         implements_usual_auto_traits::<Polynomial<XFieldElement>>();
+        implements_usual_auto_traits::<MPolynomial<BFieldElement>>();
+        implements_usual_auto_traits::<MPolynomial<XFieldElement>>();
         implements_usual_auto_traits::<Digest>();
         implements_usual_auto_traits::<Tip5>();
         implements_usual_auto_traits::<XFieldElement>();
@@ -59,14 +61,54 @@ pub(crate) mod tests {
 
     #[test]
     fn public_types_implement_the_usual_auto_traits() {
+        implements_usual_auto_traits::<math::fri::Fri>();
+        implements_usual_auto_traits::<math::fri::FriProof>();
+        implements_usual_auto_traits::<util_types::proof_stream::ProofItem>();
+        implements_usual_auto_traits::<util_types::proof_stream::ProofStream<Tip5>>();
+        implements_usual_auto_traits::<util_types::sparse_merkle_tree::SparseMerkleTree>();
+        implements_usual_auto_traits::<util_types::sparse_merkle_tree::SparseMerkleTreeProof>();
+        implements_usual_auto_traits::<math::hash_signatures::wots::SecretKey>();
+        implements_usual_auto_traits::<math::hash_signatures::wots::PublicKey>();
+        implements_usual_auto_traits::<math::hash_signatures::wots::Signature>();
+        implements_usual_auto_traits::<math::hash_signatures::xmss::SecretKey>();
+        implements_usual_auto_traits::<math::hash_signatures::xmss::PublicKey>();
+        implements_usual_auto_traits::<math::hash_signatures::xmss::Signature>();
+        implements_usual_auto_traits::<math::low_degree_extension::LowDegreeExtension>();
+        implements_usual_auto_traits::<math::matrix::Matrix<BFieldElement>>();
+        implements_usual_auto_traits::<math::matrix::Matrix<XFieldElement>>();
+        implements_usual_auto_traits::<math::matrix::sparse::SparseMatrix<BFieldElement>>();
+        implements_usual_auto_traits::<math::matrix::sparse::SparseMatrix<XFieldElement>>();
+        implements_usual_auto_traits::<math::ntt::NttPlan>();
+        implements_usual_auto_traits::<math::reed_solomon::ReedSolomon>();
+        implements_usual_auto_traits::<math::secret_sharing::SecretSharing>();
+        implements_usual_auto_traits::<math::secret_sharing::Share<BFieldElement>>();
+        implements_usual_auto_traits::<math::secret_sharing::VerifiableShare>();
+        implements_usual_auto_traits::<math::secret_sharing::FeldmanCommitment>();
         implements_usual_auto_traits::<math::lattice::CyclotomicRingElement>();
         implements_usual_auto_traits::<math::lattice::ModuleElement<42>>();
         implements_usual_auto_traits::<math::lattice::kem::SecretKey>();
         implements_usual_auto_traits::<math::lattice::kem::PublicKey>();
         implements_usual_auto_traits::<math::lattice::kem::Ciphertext>();
+        implements_usual_auto_traits::<math::lattice::kem::SealedMessage>();
+        implements_usual_auto_traits::<math::lattice::kem::SharedKey>();
+        implements_usual_auto_traits::<math::lattice::kem::Rank2Degree256>();
+        implements_usual_auto_traits::<
+            math::lattice::kem::PublicKey<math::lattice::kem::Rank2Degree128>,
+        >();
+        implements_usual_auto_traits::<math::lattice::signature::SecretKey>();
+        implements_usual_auto_traits::<math::lattice::signature::PublicKey>();
+        implements_usual_auto_traits::<math::lattice::signature::Signature>();
+        implements_usual_auto_traits::<math::quintic_field_element::QuinticFieldElement>();
+        implements_usual_auto_traits::<math::ecgfp5::Point>();
+        implements_usual_auto_traits::<math::ecgfp5::Scalar>();
+        implements_usual_auto_traits::<math::ecgfp5::schnorr::SecretKey>();
+        implements_usual_auto_traits::<math::ecgfp5::schnorr::PublicKey>();
+        implements_usual_auto_traits::<math::ecgfp5::schnorr::Signature>();
         implements_usual_auto_traits::<mock::mmr::MockMmr>();
         implements_usual_auto_traits::<util_types::sponge::Domain>();
         implements_usual_auto_traits::<util_types::mmr::mmr_accumulator::MmrAccumulator>();
+        implements_usual_auto_traits::<util_types::mmr::archival_mmr::ArchivalMmr<Vec<Digest>>>();
+        implements_usual_auto_traits::<util_types::mmr::mmr_storage::FileStorage>();
         implements_usual_auto_traits::<math::zerofier_tree::Branch<BFieldElement>>();
         implements_usual_auto_traits::<math::zerofier_tree::Leaf<BFieldElement>>();
         implements_usual_auto_traits::<math::zerofier_tree::ZerofierTree<BFieldElement>>();
@@ -80,6 +122,17 @@ pub(crate) mod tests {
         implements_usual_auto_traits::<error::BFieldCodecError>();
         implements_usual_auto_traits::<error::PolynomialBFieldCodecError>();
         implements_usual_auto_traits::<error::MerkleTreeError>();
+        implements_usual_auto_traits::<error::FriValidationError>();
+        implements_usual_auto_traits::<error::ProofStreamError>();
+        implements_usual_auto_traits::<error::ReedSolomonError>();
+        implements_usual_auto_traits::<error::EcGFp5Error>();
+        implements_usual_auto_traits::<error::SecretSharingError>();
+        implements_usual_auto_traits::<error::XmssError>();
+        implements_usual_auto_traits::<error::KemDecodingError>();
+        implements_usual_auto_traits::<error::SealedMessageError>();
+        implements_usual_auto_traits::<error::MatrixError>();
+        implements_usual_auto_traits::<error::SparseMerkleTreeError>();
+        implements_usual_auto_traits::<error::ArchivalMmrError>();
         implements_usual_auto_traits::<error::ParseBFieldElementError>();
         implements_usual_auto_traits::<error::TryFromDigestError>();
         implements_usual_auto_traits::<error::TryFromHexDigestError>();
@@ -1,11 +1,21 @@
 pub mod b_field_element;
 pub mod bfield_codec;
 pub mod digest;
+pub mod ecgfp5;
+pub mod fri;
+pub mod hash_signatures;
 pub mod lattice;
+pub mod linear_recurrence;
+pub mod low_degree_extension;
+pub mod matrix;
 pub mod mds;
+pub mod mpolynomial;
 pub mod ntt;
 pub mod other;
 pub mod polynomial;
+pub mod quintic_field_element;
+pub mod reed_solomon;
+pub mod secret_sharing;
 pub mod tip5;
 pub mod traits;
 pub mod x_field_element;
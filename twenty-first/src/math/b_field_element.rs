@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
 use std::hash::Hash;
@@ -28,6 +29,7 @@ use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
 use serde::Serializer;
+use zeroize::DefaultIsZeroes;
 
 use super::traits::Inverse;
 use super::traits::PrimitiveRootOfUnity;
@@ -37,6 +39,12 @@ use crate::math::traits::CyclicGroupGenerator;
 use crate::math::traits::FiniteField;
 use crate::math::traits::ModPowU32;
 use crate::math::traits::ModPowU64;
+use crate::math::traits::SquareRoot;
+
+/// The prime factorization of `p - 1 = 2^32 · 3 · 5 · 17 · 257 · 65537`, the order of the
+/// multiplicative group of the field, as pairs of prime and multiplicity.
+const MULTIPLICATIVE_GROUP_ORDER_FACTORIZATION: [(u64, u32); 6] =
+    [(2, 32), (3, 1), (5, 1), (17, 1), (257, 1), (65537, 1)];
 
 const PRIMITIVE_ROOTS: phf::Map<u64, u64> = phf_map! {
     0u64 => 1,
@@ -172,6 +180,10 @@ impl GetSize for BFieldElement {
     }
 }
 
+// The default element, zero, is represented by all zeroes, so wiping an element from memory
+// leaves a valid element.
+impl DefaultIsZeroes for BFieldElement {}
+
 impl<'a> Arbitrary<'a> for BFieldElement {
     fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
         u.arbitrary().map(BFieldElement::new)
@@ -279,6 +291,123 @@ impl BFieldElement {
         *self -= Self::one();
     }
 
+    /// The smallest positive `k` such that `self^k = 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn multiplicative_order(&self) -> u64 {
+        assert!(
+            !self.is_zero(),
+            "Cannot compute the multiplicative order of zero."
+        );
+
+        let mut order = Self::P - 1;
+        for (prime, multiplicity) in MULTIPLICATIVE_GROUP_ORDER_FACTORIZATION {
+            for _ in 0..multiplicity {
+                if !self.mod_pow(order / prime).is_one() {
+                    break;
+                }
+                order /= prime;
+            }
+        }
+
+        order
+    }
+
+    /// The smallest non-negative `k` such that `base^k = self`, or `None` if no such `k`
+    /// exists.
+    ///
+    /// Uses the Pohlig–Hellman algorithm, solving the discrete logarithm in every subgroup of
+    /// prime order with baby-step giant-step. Since the largest prime factor of `p - 1` is
+    /// 65537, this is cheap.
+    pub fn discrete_log(&self, base: Self) -> Option<u64> {
+        if self.is_one() {
+            return Some(0);
+        }
+        if base.is_zero() || self.is_zero() {
+            return (base.is_zero() && self.is_zero()).then_some(1);
+        }
+
+        // The multiplicative group is cyclic, hence `self` is a power of `base` if and only if
+        // the order of `self` divides the order of `base`.
+        let order = base.multiplicative_order();
+        if !self.mod_pow(order).is_one() {
+            return None;
+        }
+
+        let mut log = 0;
+        let mut modulus = 1;
+        for (prime, _) in MULTIPLICATIVE_GROUP_ORDER_FACTORIZATION {
+            let mut exponent = 0;
+            while order.is_multiple_of(prime.pow(exponent + 1)) {
+                exponent += 1;
+            }
+            if exponent == 0 {
+                continue;
+            }
+
+            let prime_power = prime.pow(exponent);
+            let cofactor = order / prime_power;
+            let log_mod_prime_power = Self::discrete_log_of_prime_power_order(
+                self.mod_pow(cofactor),
+                base.mod_pow(cofactor),
+                prime,
+                exponent,
+            );
+            log = chinese_remainder(log, modulus, log_mod_prime_power, prime_power);
+            modulus *= prime_power;
+        }
+
+        Some(log)
+    }
+
+    /// The discrete logarithm of `element` to the `base` of order `prime^exponent`, computed
+    /// one base-`prime` digit at a time. The `element` must be a power of the `base`.
+    fn discrete_log_of_prime_power_order(
+        element: Self,
+        base: Self,
+        prime: u64,
+        exponent: u32,
+    ) -> u64 {
+        let base_of_prime_order = base.mod_pow(prime.pow(exponent - 1));
+        let base_inverse = base.inverse();
+
+        let mut log = 0;
+        for k in 0..exponent {
+            let residual = element * base_inverse.mod_pow(log);
+            let residual = residual.mod_pow(prime.pow(exponent - 1 - k));
+            let digit = Self::baby_step_giant_step(residual, base_of_prime_order, prime).unwrap();
+            log += digit * prime.pow(k);
+        }
+
+        log
+    }
+
+    /// The smallest non-negative `k < order` such that `base^k = element`, if any, where
+    /// `order` is the multiplicative order of `base`.
+    fn baby_step_giant_step(element: Self, base: Self, order: u64) -> Option<u64> {
+        let num_steps = order.isqrt() + 1;
+
+        let mut baby_steps = HashMap::new();
+        let mut baby_step = Self::ONE;
+        for j in 0..num_steps {
+            baby_steps.entry(baby_step).or_insert(j);
+            baby_step *= base;
+        }
+
+        let giant_step = base.mod_pow(num_steps).inverse();
+        let mut gamma = element;
+        for i in 0..num_steps {
+            if let Some(j) = baby_steps.get(&gamma) {
+                return Some(i * num_steps + j);
+            }
+            gamma *= giant_step;
+        }
+
+        None
+    }
+
     #[inline]
     const fn canonical_representation(&self) -> u64 {
         Self::montyred(self.0 as u128)
@@ -375,6 +504,32 @@ impl BFieldElement {
     }
 }
 
+/// The unique `x < modulus · other_modulus` with `x ≡ residue (mod modulus)` and
+/// `x ≡ other_residue (mod other_modulus)`. The moduli must be coprime, and their product must
+/// fit in a `u64`.
+fn chinese_remainder(residue: u64, modulus: u64, other_residue: u64, other_modulus: u64) -> u64 {
+    // extended Euclidean algorithm for the inverse of `modulus` modulo `other_modulus`
+    let (mut r, mut new_r) = (
+        i128::from(other_modulus),
+        i128::from(modulus % other_modulus),
+    );
+    let (mut t, mut new_t) = (0_i128, 1_i128);
+    while new_r != 0 {
+        let quotient = r / new_r;
+        (r, new_r) = (new_r, r - quotient * new_r);
+        (t, new_t) = (new_t, t - quotient * new_t);
+    }
+    debug_assert_eq!(1, r, "moduli must be coprime");
+    let modulus_inverse = t.rem_euclid(i128::from(other_modulus));
+
+    let difference = (i128::from(other_residue) - i128::from(residue % other_modulus))
+        .rem_euclid(i128::from(other_modulus));
+    let lift = difference * modulus_inverse % i128::from(other_modulus);
+    let solution = i128::from(residue) + i128::from(modulus) * lift;
+
+    u64::try_from(solution).unwrap()
+}
+
 impl fmt::Display for BFieldElement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let canonical_value = Self::canonical_representation(self);
@@ -614,6 +769,62 @@ impl Inverse for BFieldElement {
     }
 }
 
+impl SquareRoot for BFieldElement {
+    /// Tonelli–Shanks, using that `p - 1 = 2^32 · (2^32 - 1)`. Of the two square roots, the
+    /// one with the smaller canonical representation is returned.
+    fn sqrt(&self) -> Option<Self> {
+        const TWO_ADICITY: u32 = 32;
+        const ODD_FACTOR: u64 = (BFieldElement::P - 1) >> TWO_ADICITY;
+
+        if self.is_zero() {
+            return Some(Self::ZERO);
+        }
+
+        // Invariants: `c` has order 2^m, `t` has order dividing 2^(m-1) if `self` is a square,
+        // and `root^2 = self · t`.
+        let mut m = TWO_ADICITY;
+        let mut c = Self::primitive_root_of_unity(1 << TWO_ADICITY).unwrap();
+        let mut t = self.mod_pow(ODD_FACTOR);
+        let mut root = self.mod_pow(ODD_FACTOR.div_ceil(2));
+
+        while !t.is_one() {
+            let mut i = 0;
+            let mut t_pow = t;
+            while !t_pow.is_one() {
+                t_pow = t_pow.square();
+                i += 1;
+                if i == m {
+                    return None;
+                }
+            }
+
+            let b = c.mod_pow(1 << (m - i - 1));
+            m = i;
+            c = b.square();
+            t *= c;
+            root *= b;
+        }
+
+        let other_root = -root;
+        Some(if root.value() < other_root.value() {
+            root
+        } else {
+            other_root
+        })
+    }
+
+    fn legendre_symbol(&self) -> i8 {
+        let symbol = self.mod_pow((Self::P - 1) / 2);
+        if symbol.is_zero() {
+            0
+        } else if symbol.is_one() {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
 impl ModPowU32 for BFieldElement {
     #[inline]
     fn mod_pow_u32(&self, exp: u32) -> Self {
@@ -1531,4 +1742,168 @@ mod b_prime_field_element_test {
     fn const_minus_two_inverse_is_really_minus_two_inverse() {
         assert_eq!(bfe!(-2).inverse(), BFieldElement::MINUS_TWO_INVERSE);
     }
+
+    #[proptest]
+    fn square_root_squares_to_original_element(bfe: BFieldElement) {
+        match bfe.sqrt() {
+            Some(root) => prop_assert_eq!(bfe, root.square()),
+            None => prop_assert_eq!(-1, bfe.legendre_symbol()),
+        }
+    }
+
+    #[proptest]
+    fn square_root_of_square_is_smaller_of_the_two_roots(bfe: BFieldElement) {
+        let root = bfe.square().sqrt().unwrap();
+        prop_assert!(root == bfe || root == -bfe);
+        prop_assert!(root.value() <= (-root).value());
+    }
+
+    #[proptest]
+    fn elements_without_square_root_are_not_squares(bfe: BFieldElement) {
+        prop_assert_eq!(bfe.sqrt().is_some(), bfe.is_square());
+    }
+
+    #[proptest]
+    fn legendre_symbol_is_multiplicative(a: BFieldElement, b: BFieldElement) {
+        prop_assert_eq!(
+            a.legendre_symbol() * b.legendre_symbol(),
+            (a * b).legendre_symbol()
+        );
+    }
+
+    #[test]
+    fn square_root_of_zero_is_zero() {
+        assert_eq!(Some(BFieldElement::ZERO), BFieldElement::ZERO.sqrt());
+        assert_eq!(0, BFieldElement::ZERO.legendre_symbol());
+    }
+
+    #[test]
+    fn primitive_roots_of_unity_of_maximal_two_power_order_are_not_squares() {
+        let root = BFieldElement::primitive_root_of_unity(1 << 32).unwrap();
+        assert!(!root.is_square());
+        assert!(root.sqrt().is_none());
+
+        let square_root_of_root = BFieldElement::primitive_root_of_unity(1 << 31).unwrap();
+        assert!(square_root_of_root.is_square());
+        assert_eq!(
+            square_root_of_root,
+            square_root_of_root.sqrt().unwrap().square()
+        );
+    }
+
+    #[test]
+    fn multiplicative_group_order_factorization_is_correct() {
+        let product = MULTIPLICATIVE_GROUP_ORDER_FACTORIZATION
+            .into_iter()
+            .map(|(prime, multiplicity)| prime.pow(multiplicity))
+            .product::<u64>();
+        assert_eq!(BFieldElement::P - 1, product);
+    }
+
+    #[test]
+    fn generator_has_full_multiplicative_order() {
+        let generator = BFieldElement::generator();
+        assert_eq!(BFieldElement::P - 1, generator.multiplicative_order());
+        assert_eq!(1, BFieldElement::ONE.multiplicative_order());
+        assert_eq!(2, (-BFieldElement::ONE).multiplicative_order());
+    }
+
+    #[test]
+    #[should_panic(expected = "multiplicative order of zero")]
+    fn multiplicative_order_of_zero_panics() {
+        BFieldElement::ZERO.multiplicative_order();
+    }
+
+    #[proptest]
+    fn multiplicative_order_is_smallest_exponent_giving_one(
+        #[filter(!#bfe.is_zero())] bfe: BFieldElement,
+    ) {
+        let order = bfe.multiplicative_order();
+        prop_assert_eq!(0, (BFieldElement::P - 1) % order);
+        prop_assert!(bfe.mod_pow(order).is_one());
+        for (prime, _) in MULTIPLICATIVE_GROUP_ORDER_FACTORIZATION {
+            if order % prime == 0 {
+                prop_assert!(!bfe.mod_pow(order / prime).is_one());
+            }
+        }
+    }
+
+    #[proptest]
+    fn discrete_log_of_power_of_generator_is_exponent(
+        #[strategy(0..BFieldElement::P - 1)] exponent: u64,
+    ) {
+        let generator = BFieldElement::generator();
+        let power = generator.mod_pow(exponent);
+        prop_assert_eq!(Some(exponent), power.discrete_log(generator));
+    }
+
+    #[proptest]
+    fn discrete_log_to_arbitrary_base_is_smallest_exponent(
+        #[filter(!#base.is_zero())] base: BFieldElement,
+        exponent: u64,
+    ) {
+        let power = base.mod_pow(exponent);
+        let log = power.discrete_log(base).unwrap();
+        prop_assert_eq!(power, base.mod_pow(log));
+        prop_assert_eq!(exponent % base.multiplicative_order(), log);
+    }
+
+    #[proptest]
+    fn discrete_log_is_none_if_element_is_not_a_power_of_base(
+        #[strategy(0_u32..=32)] log2_order: u32,
+        #[filter(!#element.is_zero())] element: BFieldElement,
+    ) {
+        let base = BFieldElement::primitive_root_of_unity(1 << log2_order).unwrap();
+        let is_power = element.mod_pow(1 << log2_order).is_one();
+        prop_assert_eq!(is_power, element.discrete_log(base).is_some());
+    }
+
+    #[test]
+    fn discrete_log_handles_zero() {
+        let zero = BFieldElement::ZERO;
+        let one = BFieldElement::ONE;
+        let two = BFieldElement::new(2);
+
+        assert_eq!(Some(0), one.discrete_log(zero));
+        assert_eq!(Some(1), zero.discrete_log(zero));
+        assert_eq!(None, two.discrete_log(zero));
+        assert_eq!(None, zero.discrete_log(two));
+    }
+
+    #[test]
+    fn primitive_roots_of_unity_map_back_to_exponents_of_generator() {
+        let generator = BFieldElement::generator();
+        for log2_order in 0..=32 {
+            let order = 1 << log2_order;
+            let root = BFieldElement::primitive_root_of_unity(order).unwrap();
+            assert_eq!(order, root.multiplicative_order());
+
+            let log = root.discrete_log(generator).unwrap();
+            assert_eq!(root, generator.mod_pow(log));
+            assert_eq!(0, log % ((BFieldElement::P - 1) / order));
+        }
+    }
+
+    #[test]
+    fn cyclic_group_generator_outputs_agree_with_multiplicative_order() {
+        for order in [2, 3, 5, 17, 257, 65537, 2 * 3 * 5 * 17] {
+            let cofactor = (BFieldElement::P - 1) / order;
+            let element = BFieldElement::generator().mod_pow(cofactor);
+            assert_eq!(order, element.multiplicative_order());
+
+            let group_elements = element.get_cyclic_group_elements(None);
+            assert_eq!(order, group_elements.len() as u64);
+            for (exponent, group_element) in group_elements.into_iter().enumerate().take(300) {
+                assert_eq!(Some(exponent as u64), group_element.discrete_log(element));
+            }
+        }
+    }
+
+    #[test]
+    fn chinese_remainder_combines_residues() {
+        let x = 1_234_567_u64;
+        let [m1, m2] = [3 * 5 * 17, 65537];
+        assert_eq!(x % (m1 * m2), chinese_remainder(x % m1, m1, x % m2, m2));
+        assert_eq!(0, chinese_remainder(0, 1, 0, 1 << 32));
+    }
 }
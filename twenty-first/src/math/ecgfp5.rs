@@ -0,0 +1,865 @@
+//! The prime-order group of the elliptic curve [ecGFp5][ecgfp5], defined over the
+//! [quintic extension](QuinticFieldElement) GF(p^5) of the base field.
+//!
+//! The curve `y^2 = x·(x^2 + a·x + b)` with `a = 2` and `b = 263·z` has order `2·n` for a
+//! 319-bit prime `n`. It is a “double-odd” curve: its unique point of order two is
+//! `N = (0, 0)`. The group of order `n` is represented by the coset `G + N` of the subgroup
+//! `G` of points of odd order. Every element of that coset is an affine point, the neutral
+//! element being `N` itself. The group law is `P ⊕ Q = P + Q + N`, where `+` is the usual
+//! addition on the curve.
+//!
+//! A [`Point`] is [encoded](Point::to_w) as the single field element `w = y / x`, which is
+//! zero for the neutral element. [Decoding](Point::from_w) is canonical: every field element
+//! is the encoding of at most one group element, and decoding rejects anything else.
+//!
+//! The [`schnorr`] module builds signatures on top of the group, hashing with [`Tip5`].
+//!
+//! [Scalar multiplication](Point::mul) performs a sequence of field operations that does not
+//! depend on the scalar, and looks up precomputed multiples without branching on the scalar.
+//! Only the conversions between the input point, the result, and the internal representation
+//! branch, and they depend on nothing but those points. Whether the field arithmetic itself runs
+//! in constant time depends on the code the compiler generates for it, which has not been
+//! verified. [Addition](Point::add) of points, decoding, and scalar arithmetic are not constant
+//! time.
+//!
+//! [ecgfp5]: https://eprint.iacr.org/2022/274
+//! [`Tip5`]: crate::math::tip5::Tip5
+
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+
+use arbitrary::Arbitrary;
+use arbitrary::Unstructured;
+use num_bigint::BigUint;
+use num_traits::ConstOne;
+use num_traits::ConstZero;
+use num_traits::Zero;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::bfield_codec::BFieldCodecError;
+use crate::math::digest::Digest;
+use crate::math::quintic_field_element::QuinticFieldElement;
+use crate::math::quintic_field_element::QUINTIC_EXTENSION_DEGREE;
+use crate::math::traits::Inverse;
+use crate::math::traits::SquareRoot;
+
+pub mod schnorr;
+
+/// The coefficient `a` in the curve equation `y^2 = x·(x^2 + a·x + b)`.
+pub const CURVE_A: QuinticFieldElement = QuinticFieldElement::new_const(BFieldElement::new(2));
+
+/// The coefficient `b` in the curve equation `y^2 = x·(x^2 + a·x + b)`.
+pub const CURVE_B: QuinticFieldElement = QuinticFieldElement::new([
+    BFieldElement::ZERO,
+    BFieldElement::new(263),
+    BFieldElement::ZERO,
+    BFieldElement::ZERO,
+    BFieldElement::ZERO,
+]);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Error)]
+#[non_exhaustive]
+pub enum EcGFp5Error {
+    #[error("the field element does not encode any group element")]
+    InvalidPointEncoding,
+
+    #[error("the scalar is not smaller than the group order")]
+    NonCanonicalScalar,
+}
+
+/// An element of the prime-order group of the curve ecGFp5. See the [module-level
+/// documentation](self) for details on the representation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "QuinticFieldElement", into = "QuinticFieldElement")]
+pub struct Point {
+    x: QuinticFieldElement,
+    y: QuinticFieldElement,
+}
+
+/// An integer modulo the [order](Scalar::ORDER) of the group, in particular, a multiplier for
+/// [`Point`]s.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(
+    try_from = "[BFieldElement; Digest::LEN]",
+    into = "[BFieldElement; Digest::LEN]"
+)]
+pub struct Scalar {
+    /// Little-endian; always smaller than the group order.
+    limbs: [u64; Scalar::NUM_LIMBS],
+}
+
+impl Point {
+    /// The neutral element, the curve point `N = (0, 0)`.
+    pub const NEUTRAL: Self = Self {
+        x: QuinticFieldElement::ZERO,
+        y: QuinticFieldElement::ZERO,
+    };
+
+    /// The conventional generator, which is the group element with [encoding](Self::to_w)
+    /// `w = 4`, the smallest integer encoding any group element.
+    pub const GENERATOR: Self = Self {
+        x: QuinticFieldElement::new([
+            BFieldElement::new(12883135586176881569),
+            BFieldElement::new(4356519642755055268),
+            BFieldElement::new(5248930565894896907),
+            BFieldElement::new(2165973894480315022),
+            BFieldElement::new(2448410071095648785),
+        ]),
+        y: QuinticFieldElement::new([
+            BFieldElement::new(14639054205878357634),
+            BFieldElement::new(17426078571020221072),
+            BFieldElement::new(2548978194165003307),
+            BFieldElement::new(8663895577921260088),
+            BFieldElement::new(9793640284382595140),
+        ]),
+    };
+
+    pub fn is_neutral(&self) -> bool {
+        self.x.is_zero()
+    }
+
+    /// The canonical encoding `w = y / x` of the group element, which is zero for the
+    /// [neutral element](Self::NEUTRAL).
+    pub fn to_w(&self) -> QuinticFieldElement {
+        if self.is_neutral() {
+            return QuinticFieldElement::ZERO;
+        }
+        self.y / self.x
+    }
+
+    /// The group element with the given [encoding](Self::to_w), if any.
+    ///
+    /// For a non-zero `w`, the line `y = w·x` intersects the curve in `N` and in at most two
+    /// more points, whose `x`-coordinates are the roots of `x^2 - (w^2 - a)·x + b`. Since `b`
+    /// is not a square, exactly one of them is not a square either, and that one belongs to
+    /// the coset representing the group.
+    pub fn from_w(w: QuinticFieldElement) -> Result<Self, EcGFp5Error> {
+        if w.is_zero() {
+            return Ok(Self::NEUTRAL);
+        }
+
+        let e = w * w - CURVE_A;
+        let delta = e * e - CURVE_B * BFieldElement::new(4);
+        let delta_sqrt = delta.sqrt().ok_or(EcGFp5Error::InvalidPointEncoding)?;
+
+        let half = BFieldElement::new(2).inverse();
+        let mut x = (e + delta_sqrt) * half;
+        if x.is_square() {
+            x = (e - delta_sqrt) * half;
+        }
+        let y = w * x;
+
+        Ok(Self { x, y })
+    }
+
+    pub fn double(self) -> Self {
+        self + self
+    }
+
+    /// The sum of `self` and `other` as points on the curve, or `None` if that sum is the point
+    /// at infinity.
+    fn curve_add(self, other: Self) -> Option<(QuinticFieldElement, QuinticFieldElement)> {
+        let slope = if self.x == other.x {
+            if (self.y + other.y).is_zero() {
+                return None;
+            }
+            let numerator = self.x * self.x * BFieldElement::new(3)
+                + CURVE_A * self.x * BFieldElement::new(2)
+                + CURVE_B;
+            numerator / (self.y * BFieldElement::new(2))
+        } else {
+            (other.y - self.y) / (other.x - self.x)
+        };
+
+        let x = slope * slope - CURVE_A - self.x - other.x;
+        let y = slope * (self.x - x) - self.y;
+        Some((x, y))
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let Some((x, y)) = self.curve_add(other) else {
+            return Self::NEUTRAL;
+        };
+
+        // Adding N = (0, 0) maps (x, y) to (b/x, -b·y/x^2). Here, x is never zero because the
+        // curve sum of two elements of the coset lies in the subgroup of odd order.
+        let x_inverse = x.inverse();
+        let b_over_x = CURVE_B * x_inverse;
+        Self {
+            x: b_over_x,
+            y: -(b_over_x * y * x_inverse),
+        }
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl Mul<Scalar> for Point {
+    type Output = Self;
+
+    /// Fixed-window scalar multiplication with windows of 4 bits.
+    ///
+    /// The computation uses complete addition formulas on a Jacobi quartic, and the multiples
+    /// of `self` are looked up in a table without branching on the scalar. Therefore, the
+    /// sequence of field operations is independent of the scalar.
+    fn mul(self, scalar: Scalar) -> Self {
+        let base = JacobiQuarticPoint::from(self);
+        let mut multiples = [JacobiQuarticPoint::NEUTRAL; 1 << SCALAR_MUL_WINDOW_SIZE];
+        for i in 1..multiples.len() {
+            multiples[i] = multiples[i - 1] + base;
+        }
+
+        let digit_mask = (1 << SCALAR_MUL_WINDOW_SIZE) - 1;
+        let mut acc = JacobiQuarticPoint::NEUTRAL;
+        for limb in scalar.limbs.into_iter().rev() {
+            for window in (0..u64::BITS / SCALAR_MUL_WINDOW_SIZE).rev() {
+                for _ in 0..SCALAR_MUL_WINDOW_SIZE {
+                    acc = acc + acc;
+                }
+                let digit = (limb >> (window * SCALAR_MUL_WINDOW_SIZE)) & digit_mask;
+                acc = acc + JacobiQuarticPoint::select(&multiples, digit);
+            }
+        }
+
+        acc.into()
+    }
+}
+
+/// The number of scalar bits processed per table lookup in [scalar multiplication](Point::mul).
+const SCALAR_MUL_WINDOW_SIZE: u32 = 4;
+
+/// A group element as a point on the Jacobi quartic `e^2 = (a^2 - 4·b)·u^4 - 2·a·u^2 + 1`, in
+/// projective coordinates `u = U/Z` and `e = E/Z^2`.
+///
+/// The map `(x, y) ↦ (u, e) = (x/y, (x/y)^2·(x - b/x))`, sending [`Point::NEUTRAL`] to `(0, 1)`,
+/// is compatible with the group laws. The points `(u, e)` and `(-u, -e)`, which differ by the
+/// point `(0, -1)` of order two, represent the same group element. Since `a^2 - 4·b` is not a square, the
+/// addition formulas on the quartic are complete: they hold for all pairs of points, including
+/// doublings and the neutral element, without any case distinction.
+#[derive(Debug, Copy, Clone)]
+struct JacobiQuarticPoint {
+    e: QuinticFieldElement,
+    u: QuinticFieldElement,
+    z: QuinticFieldElement,
+}
+
+impl JacobiQuarticPoint {
+    const NEUTRAL: Self = Self {
+        e: QuinticFieldElement::ONE,
+        u: QuinticFieldElement::ZERO,
+        z: QuinticFieldElement::ONE,
+    };
+
+    /// The coefficient `d = a^2 - 4·b = 4 - 1052·z` of the quartic.
+    const D: QuinticFieldElement = QuinticFieldElement::new([
+        BFieldElement::new(4),
+        BFieldElement::new(BFieldElement::P - 1052),
+        BFieldElement::ZERO,
+        BFieldElement::ZERO,
+        BFieldElement::ZERO,
+    ]);
+
+    /// The element `points[index]`, selected without branching on `index`.
+    fn select(points: &[Self], index: u64) -> Self {
+        let mut selected = Self::NEUTRAL;
+        for (i, point) in (0..).zip(points) {
+            // all ones if `i == index`, else all zeros
+            let mask = ((i ^ index).wrapping_sub(1) >> 63).wrapping_neg();
+            selected.e = Self::select_coordinate(selected.e, point.e, mask);
+            selected.u = Self::select_coordinate(selected.u, point.u, mask);
+            selected.z = Self::select_coordinate(selected.z, point.z, mask);
+        }
+        selected
+    }
+
+    /// `if_zero` where `mask` is all zeros, `if_ones` where it is all ones.
+    fn select_coordinate(
+        if_zero: QuinticFieldElement,
+        if_ones: QuinticFieldElement,
+        mask: u64,
+    ) -> QuinticFieldElement {
+        let mut coefficients = if_zero.coefficients;
+        for (c, o) in coefficients.iter_mut().zip(if_ones.coefficients) {
+            let raw = (c.raw_u64() & !mask) | (o.raw_u64() & mask);
+            *c = BFieldElement::from_raw_u64(raw);
+        }
+        QuinticFieldElement::new(coefficients)
+    }
+}
+
+impl From<Point> for JacobiQuarticPoint {
+    fn from(point: Point) -> Self {
+        if point.is_neutral() {
+            return Self::NEUTRAL;
+        }
+
+        let u = point.x / point.y;
+        let e = u * u * (point.x - CURVE_B / point.x);
+        let z = QuinticFieldElement::ONE;
+        Self { e, u, z }
+    }
+}
+
+impl From<JacobiQuarticPoint> for Point {
+    /// The `x`-coordinate is a root of `u^2·x^2 + (a·u^2 - 1)·x + b·u^2`, which is the curve
+    /// equation in terms of `x` and `u`. The two roots `x` and `b/x` belong to the points `P` and
+    /// `P + N`, respectively, where `N = (0, 0)`. Exactly one of them is not a square, and that
+    /// one is the group element.
+    fn from(point: JacobiQuarticPoint) -> Self {
+        if point.u.is_zero() {
+            return Self::NEUTRAL;
+        }
+
+        let z_inverse = point.z.inverse();
+        let u = point.u * z_inverse;
+        let e = point.e * z_inverse * z_inverse;
+        let u_squared = u * u;
+        let x = (QuinticFieldElement::ONE - CURVE_A * u_squared + e)
+            / (u_squared * BFieldElement::new(2));
+        if x.is_square() {
+            let x = CURVE_B / x;
+            return Self { x, y: -x / u };
+        }
+
+        Self { x, y: x / u }
+    }
+}
+
+impl Add for JacobiQuarticPoint {
+    type Output = Self;
+
+    /// With `u_i = U_i/Z_i` and `e_i = E_i/Z_i^2`, the sum of affine points is
+    ///
+    /// ```text
+    /// u = (e_1·u_2 + e_2·u_1) / (1 - d·u_1^2·u_2^2)
+    /// e = ((1 + d·u_1^2·u_2^2)·(e_1·e_2 - 2·a·u_1·u_2) + 2·d·u_1·u_2·(u_1^2 + u_2^2))
+    ///     / (1 - d·u_1^2·u_2^2)^2
+    /// ```
+    fn add(self, other: Self) -> Self {
+        let u1u2 = self.u * other.u;
+        let z1z2 = self.z * other.z;
+        let z1z2_squared = z1z2 * z1z2;
+        let d_u1u2_squared = Self::D * u1u2 * u1u2;
+        let u1z2 = self.u * other.z;
+        let u2z1 = other.u * self.z;
+
+        let u = self.e * other.u * other.z + other.e * self.u * self.z;
+        let z = z1z2_squared - d_u1u2_squared;
+        let e = (z1z2_squared + d_u1u2_squared)
+            * (self.e * other.e - CURVE_A * u1u2 * z1z2 * BFieldElement::new(2))
+            + Self::D * u1u2 * z1z2 * (u1z2 * u1z2 + u2z1 * u2z1) * BFieldElement::new(2);
+
+        Self { e, u, z }
+    }
+}
+
+impl Mul<Point> for Scalar {
+    type Output = Point;
+
+    fn mul(self, point: Point) -> Point {
+        point * self
+    }
+}
+
+impl From<Point> for QuinticFieldElement {
+    fn from(point: Point) -> Self {
+        point.to_w()
+    }
+}
+
+impl TryFrom<QuinticFieldElement> for Point {
+    type Error = EcGFp5Error;
+
+    fn try_from(w: QuinticFieldElement) -> Result<Self, Self::Error> {
+        Self::from_w(w)
+    }
+}
+
+impl BFieldCodec for Point {
+    type Error = BFieldCodecError;
+
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let coefficients = *<[BFieldElement; QUINTIC_EXTENSION_DEGREE]>::decode(sequence)?;
+        let w = QuinticFieldElement::new(coefficients);
+        let point =
+            Point::from_w(w).map_err(|e| BFieldCodecError::InnerDecodingFailure(e.into()))?;
+        Ok(Box::new(point))
+    }
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        self.to_w().coefficients.to_vec()
+    }
+
+    fn static_length() -> Option<usize> {
+        Some(QUINTIC_EXTENSION_DEGREE)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Point {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::GENERATOR * u.arbitrary::<Scalar>()?)
+    }
+}
+
+impl Scalar {
+    const NUM_LIMBS: usize = 5;
+
+    /// The order `n` of the group, in little-endian limbs.
+    pub const ORDER: [u64; Self::NUM_LIMBS] = [
+        0xe80f_d996_948b_ffe1,
+        0xe888_5c39_d724_a09c,
+        0x7fff_ffe6_cfb8_0639,
+        0x7fff_fff1_0000_0016,
+        0x7fff_fffd_8000_0007,
+    ];
+
+    pub const ZERO: Self = Self {
+        limbs: [0; Self::NUM_LIMBS],
+    };
+
+    pub const ONE: Self = Self {
+        limbs: [1, 0, 0, 0, 0],
+    };
+
+    pub fn is_zero(&self) -> bool {
+        self == &Self::ZERO
+    }
+
+    /// The multiplicative inverse, computed as `self^(n - 2)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scalar is zero.
+    pub fn inverse(self) -> Self {
+        assert!(!self.is_zero(), "cannot invert zero");
+        let order = Self::order();
+        let exponent = &order - 2_u32;
+        Self::from_biguint_reduced(self.to_biguint().modpow(&exponent, &order))
+    }
+
+    /// Reduce the [`Digest`], interpreted as an integer in base `p`, modulo the group order.
+    ///
+    /// Since `p^5 - 2·n` is smaller than `2^161`, the result is statistically close to
+    /// uniform if the digest is uniform.
+    pub fn from_digest(digest: Digest) -> Self {
+        Self::from_biguint_reduced(digest.into())
+    }
+
+    fn order() -> BigUint {
+        Self::biguint_from_limbs(Self::ORDER)
+    }
+
+    fn biguint_from_limbs(limbs: [u64; Self::NUM_LIMBS]) -> BigUint {
+        let digits = limbs
+            .into_iter()
+            .flat_map(|limb| [limb as u32, (limb >> 32) as u32]);
+        BigUint::new(digits.collect())
+    }
+
+    fn to_biguint(self) -> BigUint {
+        Self::biguint_from_limbs(self.limbs)
+    }
+
+    fn from_biguint_reduced(value: BigUint) -> Self {
+        let reduced = value % Self::order();
+        let mut limbs = [0; Self::NUM_LIMBS];
+        for (limb, digit) in limbs.iter_mut().zip(reduced.to_u64_digits()) {
+            *limb = digit;
+        }
+        Self { limbs }
+    }
+}
+
+impl From<u64> for Scalar {
+    fn from(value: u64) -> Self {
+        Self {
+            limbs: [value, 0, 0, 0, 0],
+        }
+    }
+}
+
+/// The digits of the scalar in base `p`, least significant first.
+impl From<Scalar> for [BFieldElement; Digest::LEN] {
+    fn from(scalar: Scalar) -> Self {
+        let digest = Digest::try_from(scalar.to_biguint()).unwrap();
+        digest.values()
+    }
+}
+
+impl TryFrom<[BFieldElement; Digest::LEN]> for Scalar {
+    type Error = EcGFp5Error;
+
+    fn try_from(digits: [BFieldElement; Digest::LEN]) -> Result<Self, Self::Error> {
+        let value = BigUint::from(Digest::new(digits));
+        if value >= Self::order() {
+            return Err(EcGFp5Error::NonCanonicalScalar);
+        }
+        Ok(Self::from_biguint_reduced(value))
+    }
+}
+
+impl BFieldCodec for Scalar {
+    type Error = BFieldCodecError;
+
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let digits = *<[BFieldElement; Digest::LEN]>::decode(sequence)?;
+        let scalar = Scalar::try_from(digits)
+            .map_err(|e| BFieldCodecError::InnerDecodingFailure(e.into()))?;
+        Ok(Box::new(scalar))
+    }
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        <[BFieldElement; Digest::LEN]>::from(*self).to_vec()
+    }
+
+    fn static_length() -> Option<usize> {
+        Some(Digest::LEN)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Scalar {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::from_digest(u.arbitrary()?))
+    }
+}
+
+impl Add for Scalar {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from_biguint_reduced(self.to_biguint() + other.to_biguint())
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_biguint_reduced(Self::order() - self.to_biguint())
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::from_biguint_reduced(self.to_biguint() * other.to_biguint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::math::other::random_elements;
+
+    impl proptest::arbitrary::Arbitrary for Point {
+        type Parameters = ();
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            arb().boxed()
+        }
+
+        type Strategy = BoxedStrategy<Self>;
+    }
+
+    impl proptest::arbitrary::Arbitrary for Scalar {
+        type Parameters = ();
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            arb().boxed()
+        }
+
+        type Strategy = BoxedStrategy<Self>;
+    }
+
+    fn is_on_curve(point: Point) -> bool {
+        let Point { x, y } = point;
+        y * y == x * (x * x + CURVE_A * x + CURVE_B)
+    }
+
+    #[test]
+    fn curve_parameters_make_a_double_odd_curve() {
+        let discriminant = CURVE_A * CURVE_A - CURVE_B * BFieldElement::new(4);
+        assert!(!CURVE_B.is_square());
+        assert!(!discriminant.is_square());
+    }
+
+    #[test]
+    fn order_is_odd_and_has_319_bits() {
+        let order = Scalar::order();
+        assert_eq!(319, order.bits());
+        assert!(order.bit(0));
+    }
+
+    #[test]
+    fn generator_is_decoding_of_four() {
+        let generator = Point::from_w(QuinticFieldElement::from(4)).unwrap();
+        assert_eq!(Point::GENERATOR, generator);
+        for w in 1..4 {
+            assert!(Point::from_w(QuinticFieldElement::from(w)).is_err());
+        }
+    }
+
+    #[test]
+    fn generator_has_group_order() {
+        let order_minus_one = Scalar::ZERO - Scalar::ONE;
+        let almost_neutral = Point::GENERATOR * order_minus_one;
+        assert_eq!(-Point::GENERATOR, almost_neutral);
+        assert!((almost_neutral + Point::GENERATOR).is_neutral());
+    }
+
+    #[test]
+    fn scalar_multiples_of_generator_agree_with_known_answers() {
+        let known_answers: [(_, [u64; QUINTIC_EXTENSION_DEGREE]); 3] = [
+            (
+                Scalar::from(2),
+                [
+                    9158372289535233080,
+                    10327954189174774606,
+                    15619016834217869504,
+                    16517814385077291378,
+                    10141215455047792195,
+                ],
+            ),
+            (
+                Scalar::from(3),
+                [
+                    6052337009455581569,
+                    14364284273112518944,
+                    6784982068192735943,
+                    3108585027458804187,
+                    12998922769182173772,
+                ],
+            ),
+            (
+                Scalar::from(123456789),
+                [
+                    12109065233027394938,
+                    4699177299220254315,
+                    12232575519279250417,
+                    4131293148783656705,
+                    5883025246099853972,
+                ],
+            ),
+        ];
+
+        for (scalar, encoding) in known_answers {
+            let expected = QuinticFieldElement::from(encoding);
+            assert_eq!(expected, (Point::GENERATOR * scalar).to_w());
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn decoding_encoded_point_gives_same_point(point: Point) {
+        prop_assert!(is_on_curve(point));
+        prop_assert_eq!(point, Point::from_w(point.to_w()).unwrap());
+    }
+
+    #[proptest]
+    fn decoded_points_are_on_curve_and_in_group_coset(w: QuinticFieldElement) {
+        let Ok(point) = Point::from_w(w) else {
+            return Ok(());
+        };
+        prop_assert!(is_on_curve(point));
+        prop_assert!(!point.x.is_square());
+        prop_assert_eq!(w, point.to_w());
+    }
+
+    #[test]
+    fn roughly_half_of_all_field_elements_encode_a_point() {
+        let num_valid = random_elements::<QuinticFieldElement>(200)
+            .into_iter()
+            .filter(|&w| Point::from_w(w).is_ok())
+            .count();
+        assert!((60..140).contains(&num_valid), "{num_valid}");
+    }
+
+    #[proptest(cases = 20)]
+    fn group_law_is_associative_and_commutative(a: Point, b: Point, c: Point) {
+        prop_assert_eq!((a + b) + c, a + (b + c));
+        prop_assert_eq!(a + b, b + a);
+    }
+
+    #[proptest(cases = 20)]
+    fn neutral_element_and_negation_behave(point: Point) {
+        prop_assert_eq!(point, point + Point::NEUTRAL);
+        prop_assert!((point - point).is_neutral());
+        prop_assert_eq!(-point.to_w(), (-point).to_w());
+        prop_assert_eq!(point + point, point.double());
+    }
+
+    #[proptest(cases = 10)]
+    fn scalar_multiplication_is_compatible_with_scalar_arithmetic(
+        point: Point,
+        a: Scalar,
+        b: Scalar,
+    ) {
+        prop_assert_eq!(point * (a + b), point * a + point * b);
+        prop_assert_eq!(point * (a * b), (point * a) * b);
+        prop_assert_eq!(point * (a - b), point * a - point * b);
+    }
+
+    #[proptest(cases = 10)]
+    fn multiplying_by_small_scalars_is_repeated_addition(
+        point: Point,
+        #[strategy(0_u64..10)] scalar: u64,
+    ) {
+        let expected = (0..scalar).fold(Point::NEUTRAL, |acc, _| acc + point);
+        prop_assert_eq!(expected, point * Scalar::from(scalar));
+    }
+
+    #[proptest(cases = 10)]
+    fn scalar_multiplication_agrees_with_double_and_add(point: Point, scalar: Scalar) {
+        let mut expected = Point::NEUTRAL;
+        for limb in scalar.limbs.into_iter().rev() {
+            for bit in (0..u64::BITS).rev() {
+                expected = expected.double();
+                if (limb >> bit) & 1 == 1 {
+                    expected += point;
+                }
+            }
+        }
+        prop_assert_eq!(expected, point * scalar);
+    }
+
+    #[proptest(cases = 20)]
+    fn conversion_to_and_from_jacobi_quartic_is_lossless(point: Point) {
+        prop_assert_eq!(point, JacobiQuarticPoint::from(point).into());
+    }
+
+    #[test]
+    fn jacobi_quartic_coefficient_is_not_a_square() {
+        let d = CURVE_A * CURVE_A - CURVE_B * BFieldElement::new(4);
+        assert_eq!(d, JacobiQuarticPoint::D);
+        assert!(!d.is_square());
+    }
+
+    #[proptest(cases = 20)]
+    fn jacobi_quartic_addition_agrees_with_affine_addition(a: Point, b: Point) {
+        let [jacobi_a, jacobi_b] = [a, b].map(JacobiQuarticPoint::from);
+        let neutral = JacobiQuarticPoint::NEUTRAL;
+        prop_assert_eq!(a + b, (jacobi_a + jacobi_b).into());
+        prop_assert_eq!(a.double(), (jacobi_a + jacobi_a).into());
+        prop_assert_eq!(a, (jacobi_a + neutral).into());
+        prop_assert_eq!(a, (neutral + jacobi_a).into());
+        prop_assert_eq!(Point::NEUTRAL, (jacobi_a + (-a).into()).into());
+        prop_assert_eq!(Point::NEUTRAL, (neutral + neutral).into());
+    }
+
+    #[proptest(cases = 20)]
+    fn constant_time_selection_picks_entry_at_index(
+        #[strategy(proptest::collection::vec(arb::<Point>(), 1..17))] points: Vec<Point>,
+        #[strategy(0..#points.len())] index: usize,
+    ) {
+        let jacobi_points = points
+            .iter()
+            .copied()
+            .map(JacobiQuarticPoint::from)
+            .collect::<Vec<_>>();
+        let selected = JacobiQuarticPoint::select(&jacobi_points, index as u64);
+        prop_assert_eq!(points[index], selected.into());
+    }
+
+    #[proptest(cases = 20)]
+    fn point_bfield_codec_round_trip(point: Point) {
+        let encoding = BFieldCodec::encode(&point);
+        prop_assert_eq!(point, *<Point as BFieldCodec>::decode(&encoding).unwrap());
+    }
+
+    #[proptest]
+    fn invalid_point_encodings_are_rejected(w: QuinticFieldElement) {
+        let is_valid = Point::from_w(w).is_ok();
+        prop_assert_eq!(
+            is_valid,
+            <Point as BFieldCodec>::decode(&w.encode()).is_ok()
+        );
+        prop_assert_eq!(
+            is_valid,
+            serde_json::from_str::<Point>(&serde_json::to_string(&w)?).is_ok()
+        );
+    }
+
+    #[proptest]
+    fn scalar_bfield_codec_and_serde_round_trip(scalar: Scalar) {
+        let encoding = scalar.encode();
+        prop_assert_eq!(scalar, *Scalar::decode(&encoding).unwrap());
+
+        let json = serde_json::to_string(&scalar)?;
+        prop_assert_eq!(scalar, serde_json::from_str::<Scalar>(&json)?);
+    }
+
+    #[test]
+    fn non_canonical_scalars_are_rejected() {
+        let order_digits = Digest::try_from(Scalar::order()).unwrap().values();
+        let Err(err) = Scalar::try_from(order_digits) else {
+            panic!("the group order must not be a valid scalar");
+        };
+        assert_eq!(EcGFp5Error::NonCanonicalScalar, err);
+        assert!(Scalar::decode(&order_digits).is_err());
+    }
+
+    #[proptest]
+    fn scalar_arithmetic_is_consistent(a: Scalar, b: Scalar) {
+        prop_assert_eq!(a, a + b - b);
+        prop_assert_eq!(Scalar::ZERO, a + (-a));
+        prop_assert_eq!(a * b, b * a);
+        prop_assert_eq!(a, a * Scalar::ONE);
+    }
+
+    #[proptest]
+    fn scalar_times_its_inverse_is_one(#[filter(!#scalar.is_zero())] scalar: Scalar) {
+        prop_assert_eq!(Scalar::ONE, scalar * scalar.inverse());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot invert zero")]
+    fn inverting_zero_scalar_panics() {
+        Scalar::ZERO.inverse();
+    }
+}
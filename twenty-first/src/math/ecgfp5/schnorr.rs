@@ -0,0 +1,203 @@
+//! Schnorr signatures over [`Digest`]s in the [ecGFp5](super) group, using [`Tip5`].
+//!
+//! With secret scalar `x` and public key `X = x·G`, a signature for message `m` is a pair
+//! `(R, s)`, where `R = k·G` for a nonce `k`, `s = k + e·x`, and the challenge `e` is the hash
+//! of `R`, `X`, and `m`. The nonce is derived deterministically from a secret seed and the
+//! message, so signing never requires fresh randomness. A signature is valid if
+//! `s·G = R + e·X`.
+
+use arbitrary::Arbitrary;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::Point;
+use super::Scalar;
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::digest::Digest;
+use crate::math::tip5::Tip5;
+
+#[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, Arbitrary)]
+pub struct SecretKey {
+    scalar: Scalar,
+    nonce_seed: Digest,
+}
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BFieldCodec, Arbitrary,
+)]
+pub struct PublicKey {
+    point: Point,
+}
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BFieldCodec, Arbitrary,
+)]
+pub struct Signature {
+    commitment: Point,
+    response: Scalar,
+}
+
+impl SecretKey {
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            point: Point::GENERATOR * self.scalar,
+        }
+    }
+
+    fn nonce(&self, message: Digest) -> Scalar {
+        let preimage = [self.nonce_seed.values(), message.values()].concat();
+        Scalar::from_digest(Tip5::hash_varlen(&preimage))
+    }
+}
+
+impl PublicKey {
+    pub fn point(&self) -> Point {
+        self.point
+    }
+
+    fn challenge(&self, commitment: Point, message: Digest) -> Scalar {
+        let preimage = [
+            commitment.to_w().coefficients,
+            self.point.to_w().coefficients,
+            message.values(),
+        ]
+        .concat();
+        Scalar::from_digest(Tip5::hash_varlen(&preimage))
+    }
+}
+
+/// Generate a key pair from the given randomness.
+pub fn keygen(randomness: [u8; 32]) -> (SecretKey, PublicKey) {
+    let randomness = randomness
+        .chunks_exact(8)
+        .map(|chunk| BFieldElement::new(u64::from_le_bytes(chunk.try_into().unwrap())));
+    let mut preimage = randomness.collect::<Vec<_>>();
+
+    preimage.push(BFieldElement::new(0));
+    let scalar = Scalar::from_digest(Tip5::hash_varlen(&preimage));
+
+    *preimage.last_mut().unwrap() = BFieldElement::new(1);
+    let nonce_seed = Tip5::hash_varlen(&preimage);
+
+    let secret_key = SecretKey { scalar, nonce_seed };
+    let public_key = secret_key.public_key();
+
+    (secret_key, public_key)
+}
+
+pub fn sign(secret_key: &SecretKey, message: Digest) -> Signature {
+    let nonce = secret_key.nonce(message);
+    let commitment = Point::GENERATOR * nonce;
+    let challenge = secret_key.public_key().challenge(commitment, message);
+
+    Signature {
+        commitment,
+        response: nonce + challenge * secret_key.scalar,
+    }
+}
+
+pub fn verify(public_key: &PublicKey, message: Digest, signature: &Signature) -> bool {
+    let challenge = public_key.challenge(signature.commitment, message);
+    let lhs = Point::GENERATOR * signature.response;
+    let rhs = signature.commitment + public_key.point * challenge;
+
+    lhs == rhs
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+
+    #[proptest(cases = 10)]
+    fn signature_for_message_verifies(randomness: [u8; 32], #[strategy(arb())] message: Digest) {
+        let (secret_key, public_key) = keygen(randomness);
+        let signature = sign(&secret_key, message);
+        prop_assert!(verify(&public_key, message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signing_is_deterministic(randomness: [u8; 32], #[strategy(arb())] message: Digest) {
+        let (secret_key, _) = keygen(randomness);
+        prop_assert_eq!(sign(&secret_key, message), sign(&secret_key, message));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_for_other_message_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+        #[strategy(arb())]
+        #[filter(#message != #other_message)]
+        other_message: Digest,
+    ) {
+        let (secret_key, public_key) = keygen(randomness);
+        let signature = sign(&secret_key, message);
+        prop_assert!(!verify(&public_key, other_message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_does_not_verify_under_other_public_key(
+        randomness: [u8; 32],
+        #[filter(#randomness != #other_randomness)] other_randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+    ) {
+        let (secret_key, _) = keygen(randomness);
+        let (_, other_public_key) = keygen(other_randomness);
+        let signature = sign(&secret_key, message);
+        prop_assert!(!verify(&other_public_key, message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn tampered_signature_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+        #[strategy(arb())] tampered_commitment: Point,
+        #[strategy(arb())] tampered_response: Scalar,
+    ) {
+        let (secret_key, public_key) = keygen(randomness);
+        let signature = sign(&secret_key, message);
+
+        prop_assume!(signature.commitment != tampered_commitment);
+        prop_assume!(signature.response != tampered_response);
+
+        let mut with_tampered_commitment = signature;
+        with_tampered_commitment.commitment = tampered_commitment;
+        prop_assert!(!verify(&public_key, message, &with_tampered_commitment));
+
+        let mut with_tampered_response = signature;
+        with_tampered_response.response = tampered_response;
+        prop_assert!(!verify(&public_key, message, &with_tampered_response));
+    }
+
+    #[proptest(cases = 10)]
+    fn keys_and_signatures_can_be_encoded_and_decoded(
+        #[strategy(arb())] secret_key: SecretKey,
+        #[strategy(arb())] message: Digest,
+    ) {
+        let public_key = secret_key.public_key();
+        let signature = sign(&secret_key, message);
+
+        prop_assert_eq!(&secret_key, &*SecretKey::decode(&secret_key.encode())?);
+        prop_assert_eq!(public_key, *PublicKey::decode(&public_key.encode())?);
+        prop_assert_eq!(signature, *Signature::decode(&signature.encode())?);
+
+        let json = serde_json::to_string(&signature)?;
+        prop_assert_eq!(signature, serde_json::from_str::<Signature>(&json)?);
+    }
+
+    #[test]
+    fn signature_agrees_with_known_answer() {
+        let (secret_key, public_key) = keygen([42; 32]);
+        let signature = sign(&secret_key, Digest::default());
+        assert!(verify(&public_key, Digest::default(), &signature));
+
+        let signature_digest = Tip5::hash(&signature);
+        let expected =
+            "806b81ba7b1a0c1d060fa1997a12d7bb963e26b4625f4b1a934be59642ded9be648be77ef796579c";
+        assert_eq!(expected, signature_digest.to_hex());
+    }
+}
@@ -0,0 +1,697 @@
+use itertools::Itertools;
+use num_traits::ConstOne;
+use num_traits::Zero;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::digest::Digest;
+use crate::math::polynomial::Polynomial;
+use crate::math::tip5::Tip5;
+use crate::math::traits::FiniteField;
+use crate::math::traits::PrimitiveRootOfUnity;
+use crate::math::x_field_element::XFieldElement;
+use crate::util_types::merkle_tree::CpuParallel;
+use crate::util_types::merkle_tree::MerkleTree;
+use crate::util_types::merkle_tree::MerkleTreeInclusionProof;
+use crate::util_types::sponge::Sponge;
+
+type Result<T> = std::result::Result<T, FriValidationError>;
+
+/// A low-degree test: the Fast Reed-Solomon Interactive Oracle Proof of Proximity, made
+/// non-interactive using the Fiat-Shamir heuristic with a [`Sponge`], for example [`Tip5`].
+///
+/// The prover commits to a codeword, _i.e._, the evaluations of some polynomial on a coset of
+/// a multiplicative subgroup, and convinces the verifier that the codeword is close to a
+/// polynomial of degree at most [`max_degree`](Self::max_degree).
+///
+/// In every round, the codeword is folded by the folding factor. A folding factor of `k` reduces
+/// the codeword's length by `k`. Internally, a fold by `k` is a sequence of `log₂(k)` folds in
+/// half; those are checked by the verifier using [colinearity checks][colinear]. The `k` values
+/// that fold into a single value of the next round's codeword are committed to as one leaf of
+/// the round's [`MerkleTree`].
+///
+/// # Examples
+///
+/// ```
+/// # use twenty_first::prelude::*;
+/// # use twenty_first::math::fri::Fri;
+/// let fri = Fri::new(bfe!(7), 256, 4, 16, 2);
+/// let polynomial = Polynomial::new(xfe_vec![1, 2, 3, 4, 5]);
+/// let codeword = polynomial.fast_coset_evaluate(bfe!(7), 256);
+///
+/// let (proof, _) = fri.prove(&codeword, &mut Tip5::init());
+/// assert!(fri.verify(&proof, &mut Tip5::init()).is_ok());
+/// ```
+///
+/// [colinear]: Polynomial::are_colinear_3
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fri {
+    offset: BFieldElement,
+    domain_length: usize,
+    expansion_factor: usize,
+    num_colinearity_checks: usize,
+    folding_factor: usize,
+}
+
+/// A non-interactive [FRI](Fri) proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BFieldCodec)]
+pub struct FriProof {
+    /// The Merkle roots of all codewords but the last, in order of the rounds.
+    pub merkle_roots: Vec<Digest>,
+
+    /// The codeword of the last round, sent in the clear.
+    pub last_codeword: Vec<XFieldElement>,
+
+    /// The prover's answers to the verifier's queries, in order of the rounds.
+    pub responses: Vec<FriResponse>,
+}
+
+/// The prover's answer to the verifier's queries for a single round of [FRI](Fri).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BFieldCodec)]
+pub struct FriResponse {
+    /// For every query, all values of the round's codeword that fold into the same value of the
+    /// next round's codeword. Each of these groups is one leaf of the round's Merkle tree.
+    pub revealed_cosets: Vec<Vec<XFieldElement>>,
+
+    /// The [authentication structure](MerkleTree::authentication_structure) for the revealed
+    /// cosets.
+    pub authentication_structure: Vec<Digest>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum FriValidationError {
+    #[error("the proof must contain {expected} rounds but contains {actual}")]
+    IncorrectNumberOfRounds { expected: usize, actual: usize },
+
+    #[error("the last codeword must have length {expected} but has length {actual}")]
+    IncorrectLastCodewordLength { expected: usize, actual: usize },
+
+    #[error("the last codeword must correspond to a polynomial of sufficiently low degree")]
+    LastCodewordTooHighDegree,
+
+    #[error("round {0} reveals an incorrect number of values")]
+    IncorrectNumberOfRevealedValues(usize),
+
+    #[error("the revealed values of round {0} fail Merkle authentication")]
+    BadMerkleAuthenticationStructure(usize),
+
+    #[error("a colinearity check of round {0} failed")]
+    BadColinearityCheck(usize),
+}
+
+impl Fri {
+    /// Set up FRI for codewords of length `domain_length` on the coset of the multiplicative
+    /// subgroup of that order defined by `offset`.
+    ///
+    /// The codeword's rate is the inverse of the `expansion_factor`. The security level is
+    /// determined by the expansion factor and the number of colinearity checks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if
+    /// - any of `domain_length`, `expansion_factor`, or `folding_factor` is not a power of two,
+    /// - the `expansion_factor` or the `folding_factor` is smaller than 2,
+    /// - the `expansion_factor` is larger than the `domain_length`,
+    /// - the `domain_length` is larger than [`u32::MAX`],
+    /// - the `num_colinearity_checks` is zero, or
+    /// - the `offset` is zero.
+    pub fn new(
+        offset: BFieldElement,
+        domain_length: usize,
+        expansion_factor: usize,
+        num_colinearity_checks: usize,
+        folding_factor: usize,
+    ) -> Self {
+        assert!(domain_length.is_power_of_two());
+        assert!(u32::try_from(domain_length).is_ok());
+        assert!(expansion_factor.is_power_of_two());
+        assert!(expansion_factor > 1);
+        assert!(expansion_factor <= domain_length);
+        assert!(folding_factor.is_power_of_two());
+        assert!(folding_factor > 1);
+        assert!(
+            num_colinearity_checks > 0,
+            "at least one colinearity check is required"
+        );
+        assert!(!offset.is_zero(), "the domain offset must be non-zero");
+
+        Self {
+            offset,
+            domain_length,
+            expansion_factor,
+            num_colinearity_checks,
+            folding_factor,
+        }
+    }
+
+    pub fn domain_length(&self) -> usize {
+        self.domain_length
+    }
+
+    pub fn expansion_factor(&self) -> usize {
+        self.expansion_factor
+    }
+
+    pub fn num_colinearity_checks(&self) -> usize {
+        self.num_colinearity_checks
+    }
+
+    pub fn folding_factor(&self) -> usize {
+        self.folding_factor
+    }
+
+    /// The maximal degree of polynomials whose codewords are accepted.
+    pub fn max_degree(&self) -> usize {
+        self.domain_length / self.expansion_factor - 1
+    }
+
+    /// The number of rounds, _i.e._, the number of codewords committed to using a Merkle tree.
+    /// Does not include the last codeword, which is sent in the clear.
+    pub fn num_rounds(&self) -> usize {
+        let mut codeword_length = self.domain_length;
+        let mut num_rounds = 0;
+        while codeword_length / self.folding_factor >= self.expansion_factor {
+            codeword_length /= self.folding_factor;
+            num_rounds += 1;
+        }
+
+        num_rounds
+    }
+
+    fn last_codeword_length(&self) -> usize {
+        let num_rounds = u32::try_from(self.num_rounds()).unwrap();
+        self.domain_length / self.folding_factor.pow(num_rounds)
+    }
+
+    fn round_offset(&self, round: usize) -> BFieldElement {
+        let exponent = self.folding_factor.pow(u32::try_from(round).unwrap());
+        self.offset.mod_pow(u64::try_from(exponent).unwrap())
+    }
+
+    /// Prove that the `codeword` is close to a polynomial of degree at most
+    /// [`max_degree`](Self::max_degree).
+    ///
+    /// The sponge is used as the Fiat-Shamir transcript. It may have absorbed prior protocol
+    /// messages. The verifier's sponge needs to be in the same state.
+    ///
+    /// Returns the proof as well as the indices of the values of `codeword` that are revealed in
+    /// the proof.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the `codeword` does not match the
+    /// [domain length](Self::domain_length).
+    pub fn prove<S: Sponge>(
+        &self,
+        codeword: &[XFieldElement],
+        sponge: &mut S,
+    ) -> (FriProof, Vec<usize>) {
+        assert_eq!(self.domain_length, codeword.len());
+
+        // commit phase
+        let mut codewords = vec![codeword.to_vec()];
+        let mut merkle_trees = vec![];
+        for round in 0..self.num_rounds() {
+            let current_codeword = codewords.last().unwrap();
+            let merkle_tree = self.commit(current_codeword);
+            sponge.pad_and_absorb_all(&merkle_tree.root().encode());
+            let folding_challenge = sponge.sample_scalars(1)[0];
+            let folded_codeword = self.fold(
+                current_codeword,
+                self.round_offset(round),
+                folding_challenge,
+            );
+
+            codewords.push(folded_codeword);
+            merkle_trees.push(merkle_tree);
+        }
+
+        let last_codeword = codewords.pop().unwrap();
+        sponge.pad_and_absorb_all(&last_codeword.encode());
+
+        // query phase
+        let Some(mut coset_indices) = self.sample_first_round_coset_indices(sponge) else {
+            let proof = FriProof {
+                merkle_roots: vec![],
+                responses: vec![],
+                last_codeword,
+            };
+            return (proof, (0..self.domain_length).collect());
+        };
+        let revealed_indices = self.codeword_indices_of_cosets(&coset_indices, self.domain_length);
+
+        let mut responses = vec![];
+        for (round_codeword, merkle_tree) in codewords.iter().zip_eq(&merkle_trees) {
+            let revealed_cosets = coset_indices
+                .iter()
+                .map(|&coset_index| self.coset(round_codeword, coset_index))
+                .collect();
+            let authentication_structure = merkle_tree
+                .authentication_structure(&coset_indices)
+                .expect("coset indices should be valid");
+            responses.push(FriResponse {
+                revealed_cosets,
+                authentication_structure,
+            });
+
+            let next_codeword_length = round_codeword.len() / self.folding_factor;
+            coset_indices = self.next_round_coset_indices(&coset_indices, next_codeword_length);
+        }
+
+        let proof = FriProof {
+            merkle_roots: merkle_trees.iter().map(MerkleTree::root).collect(),
+            last_codeword,
+            responses,
+        };
+
+        (proof, revealed_indices)
+    }
+
+    /// Verify a [FRI proof](FriProof).
+    ///
+    /// The sponge needs to be in the same state as the prover's sponge was when the proof was
+    /// generated.
+    ///
+    /// Returns the revealed values of the first codeword alongside their indices.
+    pub fn verify<S: Sponge>(
+        &self,
+        proof: &FriProof,
+        sponge: &mut S,
+    ) -> Result<Vec<(usize, XFieldElement)>> {
+        self.verify_proof_shape(proof)?;
+
+        let mut folding_challenges = vec![];
+        for root in &proof.merkle_roots {
+            sponge.pad_and_absorb_all(&root.encode());
+            folding_challenges.push(sponge.sample_scalars(1)[0]);
+        }
+        sponge.pad_and_absorb_all(&proof.last_codeword.encode());
+
+        let last_round_offset = self.round_offset(self.num_rounds());
+        let last_polynomial =
+            Polynomial::fast_coset_interpolate(last_round_offset, &proof.last_codeword);
+        let last_max_degree = self.last_codeword_length() / self.expansion_factor - 1;
+        if last_polynomial.degree() > last_max_degree as isize {
+            return Err(FriValidationError::LastCodewordTooHighDegree);
+        }
+
+        let Some(mut coset_indices) = self.sample_first_round_coset_indices(sponge) else {
+            return Ok(proof.last_codeword.iter().copied().enumerate().collect());
+        };
+        let revealed_indices = self.codeword_indices_of_cosets(&coset_indices, self.domain_length);
+        let revealed_values = proof.responses[0].revealed_cosets.concat();
+        let revealed = revealed_indices.into_iter().zip(revealed_values).collect();
+
+        let mut codeword_length = self.domain_length;
+        for (round, response) in proof.responses.iter().enumerate() {
+            let num_cosets = codeword_length / self.folding_factor;
+            let revealed_leafs = response
+                .revealed_cosets
+                .iter()
+                .map(Tip5::hash)
+                .collect_vec();
+            let inclusion_proof = MerkleTreeInclusionProof {
+                tree_height: num_cosets.ilog2() as usize,
+                indexed_leafs: coset_indices.iter().copied().zip(revealed_leafs).collect(),
+                authentication_structure: response.authentication_structure.clone(),
+            };
+            if !inclusion_proof.verify(proof.merkle_roots[round]) {
+                return Err(FriValidationError::BadMerkleAuthenticationStructure(round));
+            }
+
+            let next_codeword_length = num_cosets;
+            let next_coset_indices =
+                self.next_round_coset_indices(&coset_indices, next_codeword_length);
+            let num_next_cosets = next_codeword_length / self.folding_factor;
+            for (i, (&coset_index, coset)) in coset_indices
+                .iter()
+                .zip_eq(&response.revealed_cosets)
+                .enumerate()
+            {
+                let folded_value = match proof.responses.get(round + 1) {
+                    Some(next) => next.revealed_cosets[i][coset_index / num_next_cosets],
+                    None => proof.last_codeword[coset_index],
+                };
+                let coset_is_folded_correctly = self.coset_folds_into_value(
+                    coset,
+                    coset_index,
+                    codeword_length,
+                    self.round_offset(round),
+                    folding_challenges[round],
+                    folded_value,
+                );
+                if !coset_is_folded_correctly {
+                    return Err(FriValidationError::BadColinearityCheck(round));
+                }
+            }
+
+            coset_indices = next_coset_indices;
+            codeword_length = next_codeword_length;
+        }
+
+        Ok(revealed)
+    }
+
+    fn verify_proof_shape(&self, proof: &FriProof) -> Result<()> {
+        let num_rounds = self.num_rounds();
+        for actual in [proof.merkle_roots.len(), proof.responses.len()] {
+            if actual != num_rounds {
+                let expected = num_rounds;
+                return Err(FriValidationError::IncorrectNumberOfRounds { expected, actual });
+            }
+        }
+
+        let expected = self.last_codeword_length();
+        let actual = proof.last_codeword.len();
+        if actual != expected {
+            return Err(FriValidationError::IncorrectLastCodewordLength { expected, actual });
+        }
+
+        for (round, response) in proof.responses.iter().enumerate() {
+            let num_cosets_is_correct =
+                response.revealed_cosets.len() == self.num_colinearity_checks;
+            let coset_lengths_are_correct = response
+                .revealed_cosets
+                .iter()
+                .all(|coset| coset.len() == self.folding_factor);
+            if !num_cosets_is_correct || !coset_lengths_are_correct {
+                return Err(FriValidationError::IncorrectNumberOfRevealedValues(round));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Commit to a codeword, using all values that fold into the same value of the next
+    /// codeword as one leaf.
+    fn commit(&self, codeword: &[XFieldElement]) -> MerkleTree {
+        let num_cosets = codeword.len() / self.folding_factor;
+        let leafs = (0..num_cosets)
+            .map(|coset_index| Tip5::hash(&self.coset(codeword, coset_index)))
+            .collect_vec();
+
+        MerkleTree::new::<CpuParallel>(&leafs).expect("number of cosets should be a power of two")
+    }
+
+    /// All values of the `codeword` that fold into the value with index `coset_index` of the
+    /// next round's codeword.
+    fn coset(&self, codeword: &[XFieldElement], coset_index: usize) -> Vec<XFieldElement> {
+        let num_cosets = codeword.len() / self.folding_factor;
+        (0..self.folding_factor)
+            .map(|i| codeword[coset_index + i * num_cosets])
+            .collect()
+    }
+
+    fn codeword_indices_of_cosets(&self, coset_indices: &[usize], length: usize) -> Vec<usize> {
+        let num_cosets = length / self.folding_factor;
+        coset_indices
+            .iter()
+            .flat_map(|&coset_index| {
+                (0..self.folding_factor).map(move |i| coset_index + i * num_cosets)
+            })
+            .collect()
+    }
+
+    /// Index `i` of the next round's codeword is part of coset `i mod num_next_cosets`.
+    fn next_round_coset_indices(
+        &self,
+        coset_indices: &[usize],
+        next_codeword_length: usize,
+    ) -> Vec<usize> {
+        let num_next_cosets = (next_codeword_length / self.folding_factor).max(1);
+        coset_indices.iter().map(|i| i % num_next_cosets).collect()
+    }
+
+    /// `None` if there are no rounds and consequently, nothing to sample.
+    fn sample_first_round_coset_indices<S: Sponge>(&self, sponge: &mut S) -> Option<Vec<usize>> {
+        if self.num_rounds() == 0 {
+            return None;
+        }
+
+        let num_cosets = u32::try_from(self.domain_length / self.folding_factor).unwrap();
+        let indices = sponge.sample_indices(num_cosets, self.num_colinearity_checks);
+        Some(indices.into_iter().map(|i| i as usize).collect())
+    }
+
+    /// Fold the codeword by the folding factor.
+    fn fold(
+        &self,
+        codeword: &[XFieldElement],
+        mut offset: BFieldElement,
+        mut folding_challenge: XFieldElement,
+    ) -> Vec<XFieldElement> {
+        let mut codeword = codeword.to_vec();
+        for _ in 0..self.folding_factor.ilog2() {
+            codeword = Self::fold_in_half(&codeword, offset, folding_challenge);
+            offset = offset.square();
+            folding_challenge = folding_challenge.square();
+        }
+
+        codeword
+    }
+
+    /// Write the polynomial `f(X)` corresponding to the codeword as `f_e(X²) + X·f_o(X²)`. The
+    /// folded codeword corresponds to `f_e(X) + α·f_o(X)`. Its value at `x²` is the
+    /// [colinear y](Polynomial::get_colinear_y) to the points `(x, f(x))` and `(-x, f(-x))` at
+    /// `α`.
+    fn fold_in_half(
+        codeword: &[XFieldElement],
+        offset: BFieldElement,
+        folding_challenge: XFieldElement,
+    ) -> Vec<XFieldElement> {
+        let half_length = codeword.len() / 2;
+        let generator = BFieldElement::primitive_root_of_unity(codeword.len() as u64).unwrap();
+        let domain = (0..half_length)
+            .scan(offset, |x, _| {
+                let current = *x;
+                *x *= generator;
+                Some(current)
+            })
+            .collect();
+        let domain_inverses = BFieldElement::batch_inversion(domain);
+
+        let one = XFieldElement::ONE;
+        let one_half = -BFieldElement::MINUS_TWO_INVERSE;
+        (0..half_length)
+            .map(|i| {
+                let alpha_over_x = folding_challenge * domain_inverses[i];
+                let left = (one + alpha_over_x) * codeword[i];
+                let right = (one - alpha_over_x) * codeword[half_length + i];
+                (left + right) * one_half
+            })
+            .collect()
+    }
+
+    /// Check that a revealed coset of some round folds into the given value of the next round.
+    fn coset_folds_into_value(
+        &self,
+        coset: &[XFieldElement],
+        coset_index: usize,
+        codeword_length: usize,
+        mut offset: BFieldElement,
+        mut folding_challenge: XFieldElement,
+        folded_value: XFieldElement,
+    ) -> bool {
+        let mut coset = coset.to_vec();
+        let mut codeword_length = codeword_length;
+        let num_cosets = codeword_length / self.folding_factor;
+        while coset.len() > 2 {
+            let half_length = coset.len() / 2;
+            let generator = BFieldElement::primitive_root_of_unity(codeword_length as u64).unwrap();
+            coset = (0..half_length)
+                .map(|i| {
+                    let x = offset * generator.mod_pow((coset_index + i * num_cosets) as u64);
+                    let point = (x.lift(), coset[i]);
+                    let mirrored_point = (-x.lift(), coset[half_length + i]);
+                    Polynomial::get_colinear_y(point, mirrored_point, folding_challenge)
+                })
+                .collect();
+            offset = offset.square();
+            folding_challenge = folding_challenge.square();
+            codeword_length /= 2;
+        }
+
+        let generator = BFieldElement::primitive_root_of_unity(codeword_length as u64).unwrap();
+        let x = offset * generator.mod_pow(coset_index as u64);
+        Polynomial::are_colinear_3(
+            (x.lift(), coset[0]),
+            (-x.lift(), coset[1]),
+            (folding_challenge, folded_value),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::math::other::random_elements;
+    use crate::prelude::*;
+
+    /// Parameters for a FRI instance with a rather small domain.
+    #[derive(Debug, Clone, test_strategy::Arbitrary)]
+    struct FriParameters {
+        #[strategy(arb())]
+        #[filter(!#offset.is_zero())]
+        offset: BFieldElement,
+
+        #[strategy(1_u32..=3)]
+        log2_expansion_factor: u32,
+
+        #[strategy(1_u32..=3)]
+        log2_folding_factor: u32,
+
+        #[strategy(#log2_expansion_factor..=10)]
+        log2_domain_length: u32,
+
+        #[strategy(1_usize..=20)]
+        num_colinearity_checks: usize,
+    }
+
+    impl FriParameters {
+        fn fri(&self) -> Fri {
+            Fri::new(
+                self.offset,
+                1 << self.log2_domain_length,
+                1 << self.log2_expansion_factor,
+                self.num_colinearity_checks,
+                1 << self.log2_folding_factor,
+            )
+        }
+    }
+
+    fn low_degree_codeword(fri: &Fri) -> Vec<XFieldElement> {
+        let coefficients = random_elements(fri.max_degree() + 1);
+        Polynomial::new(coefficients).fast_coset_evaluate(fri.offset, fri.domain_length)
+    }
+
+    #[test]
+    fn number_of_rounds_is_as_expected() {
+        assert_eq!(6, Fri::new(bfe!(7), 256, 4, 10, 2).num_rounds());
+        assert_eq!(3, Fri::new(bfe!(7), 256, 4, 10, 4).num_rounds());
+        assert_eq!(2, Fri::new(bfe!(7), 256, 4, 10, 8).num_rounds());
+        assert_eq!(0, Fri::new(bfe!(7), 4, 4, 10, 2).num_rounds());
+    }
+
+    #[test]
+    #[should_panic]
+    fn folding_factor_must_be_a_power_of_two() {
+        Fri::new(bfe!(7), 256, 4, 10, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn expansion_factor_must_not_exceed_domain_length() {
+        Fri::new(bfe!(7), 256, 512, 10, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one colinearity check is required")]
+    fn number_of_colinearity_checks_must_not_be_zero() {
+        Fri::new(bfe!(7), 256, 4, 0, 2);
+    }
+
+    #[proptest(cases = 30)]
+    fn honest_proof_of_low_degree_codeword_is_accepted(parameters: FriParameters) {
+        let fri = parameters.fri();
+        let codeword = low_degree_codeword(&fri);
+
+        let mut prover_sponge = Tip5::init();
+        let (proof, revealed_indices) = fri.prove(&codeword, &mut prover_sponge);
+
+        let mut verifier_sponge = Tip5::init();
+        let revealed = fri.verify(&proof, &mut verifier_sponge)?;
+        prop_assert_eq!(prover_sponge, verifier_sponge);
+
+        let (verified_indices, revealed_values): (Vec<_>, Vec<_>) = revealed.into_iter().unzip();
+        prop_assert_eq!(&revealed_indices, &verified_indices);
+        for (index, value) in revealed_indices.into_iter().zip(revealed_values) {
+            prop_assert_eq!(codeword[index], value);
+        }
+    }
+
+    #[proptest(cases = 30)]
+    fn proof_of_random_codeword_is_rejected(
+        #[filter(#parameters.fri().num_rounds() > 0)] parameters: FriParameters,
+    ) {
+        let fri = Fri {
+            num_colinearity_checks: 40,
+            ..parameters.fri()
+        };
+        let codeword = random_elements(fri.domain_length);
+        let (proof, _) = fri.prove(&codeword, &mut Tip5::init());
+        prop_assert!(fri.verify(&proof, &mut Tip5::init()).is_err());
+    }
+
+    #[proptest(cases = 20)]
+    fn proof_is_rejected_if_sponge_states_differ(
+        #[filter(#parameters.fri().num_rounds() > 0)] parameters: FriParameters,
+        #[strategy(arb())] element: BFieldElement,
+    ) {
+        let fri = parameters.fri();
+        let (proof, _) = fri.prove(&low_degree_codeword(&fri), &mut Tip5::init());
+
+        let mut verifier_sponge = Tip5::init();
+        verifier_sponge.pad_and_absorb_all(&[element]);
+        prop_assert!(fri.verify(&proof, &mut verifier_sponge).is_err());
+    }
+
+    #[proptest(cases = 20)]
+    fn proof_with_corrupted_revealed_value_is_rejected(
+        #[filter(#parameters.fri().num_rounds() > 0)] parameters: FriParameters,
+        #[strategy(arb())] round_seed: usize,
+        #[strategy(arb())] query_seed: usize,
+        #[strategy(arb())] position_seed: usize,
+        #[strategy(arb())]
+        #[filter(!#summand.is_zero())]
+        summand: XFieldElement,
+    ) {
+        let fri = parameters.fri();
+        let (mut proof, _) = fri.prove(&low_degree_codeword(&fri), &mut Tip5::init());
+
+        let response = &mut proof.responses[round_seed % fri.num_rounds()];
+        let coset = &mut response.revealed_cosets[query_seed % fri.num_colinearity_checks];
+        coset[position_seed % fri.folding_factor] += summand;
+
+        let verdict = fri.verify(&proof, &mut Tip5::init());
+        prop_assert!(verdict.is_err());
+    }
+
+    #[test]
+    fn proof_with_too_few_rounds_is_rejected() {
+        let fri = Fri::new(bfe!(7), 64, 2, 5, 2);
+        let (mut proof, _) = fri.prove(&low_degree_codeword(&fri), &mut Tip5::init());
+        proof.merkle_roots.pop();
+
+        let err = fri.verify(&proof, &mut Tip5::init()).unwrap_err();
+        let expected = fri.num_rounds();
+        let actual = expected - 1;
+        let expected_err = FriValidationError::IncorrectNumberOfRounds { expected, actual };
+        assert_eq!(expected_err, err);
+    }
+
+    #[test]
+    fn proof_with_high_degree_last_codeword_is_rejected() {
+        let fri = Fri::new(bfe!(7), 64, 4, 5, 2);
+        let (mut proof, _) = fri.prove(&low_degree_codeword(&fri), &mut Tip5::init());
+        proof.last_codeword[0] += XFieldElement::ONE;
+
+        let err = fri.verify(&proof, &mut Tip5::init()).unwrap_err();
+        assert_eq!(FriValidationError::LastCodewordTooHighDegree, err);
+    }
+
+    #[proptest(cases = 10)]
+    fn fri_proof_can_be_encoded_and_decoded(parameters: FriParameters) {
+        let fri = parameters.fri();
+        let (proof, _) = fri.prove(&low_degree_codeword(&fri), &mut Tip5::init());
+        let decoded = *FriProof::decode(&proof.encode())?;
+        prop_assert_eq!(proof, decoded);
+    }
+}
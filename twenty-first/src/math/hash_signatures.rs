@@ -0,0 +1,35 @@
+//! Post-quantum signatures built solely from [`Tip5`] and [`MerkleTree`]s.
+//!
+//! - [`wots`] is a Winternitz one-time signature scheme: every key pair must be used to sign
+//!   at most one message.
+//! - [`xmss`] is a stateful many-time signature scheme in the style of [XMSS][xmss], whose
+//!   public key is the root of a Merkle tree over many one-time public keys.
+//!
+//! All signatures and public keys are [`BFieldCodec`](crate::math::bfield_codec::BFieldCodec)
+//! encodable, and verification requires nothing but [`Tip5`] hashing, making it suitable for
+//! recursive verification.
+//!
+//! [xmss]: https://www.rfc-editor.org/rfc/rfc8391
+//! [`MerkleTree`]: crate::util_types::merkle_tree::MerkleTree
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::digest::Digest;
+use crate::math::tip5::Tip5;
+
+pub mod wots;
+pub mod xmss;
+
+/// Derive the secret seed and the public seed from the key generation randomness.
+fn derive_seeds(randomness: [u8; 32]) -> (Digest, Digest) {
+    let randomness = randomness
+        .chunks_exact(8)
+        .map(|chunk| BFieldElement::new(u64::from_le_bytes(chunk.try_into().unwrap())));
+    let mut preimage = randomness.collect::<Vec<_>>();
+
+    preimage.push(BFieldElement::new(0));
+    let secret_seed = Tip5::hash_varlen(&preimage);
+    *preimage.last_mut().unwrap() = BFieldElement::new(1);
+    let public_seed = Tip5::hash_varlen(&preimage);
+
+    (secret_seed, public_seed)
+}
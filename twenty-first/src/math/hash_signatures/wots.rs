@@ -0,0 +1,318 @@
+//! Winternitz one-time signatures (WOTS+) over [`Digest`]s, using [`Tip5`].
+//!
+//! The message [`Digest`] is split into base-[`W`] digits, and a checksum over these digits
+//! is appended. For every digit, there is one hash chain of length `W - 1` starting at a
+//! secret value. The public key commits to the chains' ends; the signature reveals, for every
+//! digit `d`, the chain's `d`th element. Without the checksum, a signature for a message would
+//! also be a signature for every message with larger digits.
+//!
+//! Each step of each chain uses a distinct tweak, derived from the public seed and the
+//! position of the step, which prevents multi-target attacks.
+
+use arbitrary::Arbitrary;
+use itertools::Itertools;
+
+use super::derive_seeds;
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::digest::Digest;
+use crate::math::tip5::Tip5;
+
+/// The base-2 logarithm of the Winternitz parameter [`W`].
+pub const LOG2_W: usize = 4;
+
+/// The Winternitz parameter, _i.e._, the base of the digits of the signed message. Every
+/// hash chain has `W - 1` steps.
+pub const W: usize = 1 << LOG2_W;
+
+/// The number of hash chains covering the message digits.
+pub const NUM_MESSAGE_CHAINS: usize = Digest::LEN * u64::BITS as usize / LOG2_W;
+
+/// The number of hash chains covering the checksum digits. The maximal checksum,
+/// `NUM_MESSAGE_CHAINS · (W - 1)`, has this many base-[`W`] digits.
+pub const NUM_CHECKSUM_CHAINS: usize = 3;
+
+/// The total number of hash chains, which is also the number of [`Digest`]s in a
+/// [`Signature`].
+pub const NUM_CHAINS: usize = NUM_MESSAGE_CHAINS + NUM_CHECKSUM_CHAINS;
+
+const _: () = assert!(NUM_MESSAGE_CHAINS * (W - 1) < W.pow(NUM_CHECKSUM_CHAINS as u32));
+
+/// Must be used to sign at most one message. See [`sign`].
+///
+/// The secret key is deliberately not [`Clone`], so that [`sign`] can consume it. Note that
+/// [decoding](BFieldCodec::decode) an [encoding](BFieldCodec::encode) of the secret key still
+/// produces a copy.
+#[derive(Debug, PartialEq, Eq, BFieldCodec, Arbitrary)]
+pub struct SecretKey {
+    secret_seed: Digest,
+    public_seed: Digest,
+    key_index: u64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, BFieldCodec, Arbitrary)]
+pub struct PublicKey {
+    public_seed: Digest,
+    key_index: u64,
+    chain_ends_digest: Digest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, BFieldCodec, Arbitrary)]
+pub struct Signature {
+    chain_elements: [Digest; NUM_CHAINS],
+}
+
+impl SecretKey {
+    /// The `key_index` distinguishes the one-time keys derived from the same seeds, for
+    /// example, in an [XMSS](super::xmss) tree.
+    pub(super) fn new(secret_seed: Digest, public_seed: Digest, key_index: u64) -> Self {
+        Self {
+            secret_seed,
+            public_seed,
+            key_index,
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        let chain_ends = (0..NUM_CHAINS)
+            .map(|chain| self.chain(chain).walk(self.chain_start(chain), 0, W - 1))
+            .collect_vec();
+
+        public_key_from_chain_ends(self.public_seed, self.key_index, &chain_ends)
+    }
+
+    fn chain_start(&self, chain: usize) -> Digest {
+        let address = [self.key_index, chain as u64, 0, 0, 0].map(BFieldElement::new);
+        Tip5::hash_pair(self.secret_seed, Digest::new(address))
+    }
+
+    fn chain(&self, chain: usize) -> Chain {
+        Chain::new(self.public_seed, self.key_index, chain)
+    }
+}
+
+impl PublicKey {
+    pub fn key_index(&self) -> u64 {
+        self.key_index
+    }
+
+    pub(super) fn chain_ends_digest(&self) -> Digest {
+        self.chain_ends_digest
+    }
+}
+
+/// One hash chain of one one-time key.
+struct Chain {
+    public_seed: Digest,
+    key_index: u64,
+    chain: usize,
+}
+
+impl Chain {
+    fn new(public_seed: Digest, key_index: u64, chain: usize) -> Self {
+        Self {
+            public_seed,
+            key_index,
+            chain,
+        }
+    }
+
+    /// Starting at the chain's `start_step`th element, take `num_steps` steps.
+    fn walk(&self, mut element: Digest, start_step: usize, num_steps: usize) -> Digest {
+        for step in start_step..start_step + num_steps {
+            element = Tip5::hash_pair(self.tweak(step), element);
+        }
+        element
+    }
+
+    fn tweak(&self, step: usize) -> Digest {
+        let [s0, s1, s2, _, _] = self.public_seed.values();
+        let position = (self.chain * W + step) as u64;
+        let [key_index, position] = [self.key_index, position].map(BFieldElement::new);
+        Digest::new([s0, s1, s2, key_index, position])
+    }
+}
+
+fn public_key_from_chain_ends(
+    public_seed: Digest,
+    key_index: u64,
+    chain_ends: &[Digest],
+) -> PublicKey {
+    let mut preimage = public_seed.values().to_vec();
+    preimage.push(BFieldElement::new(key_index));
+    preimage.extend(chain_ends.iter().flat_map(|digest| digest.values()));
+
+    PublicKey {
+        public_seed,
+        key_index,
+        chain_ends_digest: Tip5::hash_varlen(&preimage),
+    }
+}
+
+/// Generate a one-time key pair from the given randomness.
+pub fn keygen(randomness: [u8; 32]) -> (SecretKey, PublicKey) {
+    let (secret_seed, public_seed) = derive_seeds(randomness);
+    let secret_key = SecretKey::new(secret_seed, public_seed, 0);
+    let public_key = secret_key.public_key();
+
+    (secret_key, public_key)
+}
+
+/// Sign the message, consuming the secret key: signing two different messages with the same
+/// secret key allows forging signatures.
+pub fn sign(secret_key: SecretKey, message: Digest) -> Signature {
+    let chain_elements = digits(message)
+        .into_iter()
+        .enumerate()
+        .map(|(chain, digit)| {
+            let start = secret_key.chain_start(chain);
+            secret_key.chain(chain).walk(start, 0, digit)
+        })
+        .collect_vec();
+
+    Signature {
+        chain_elements: chain_elements.try_into().unwrap(),
+    }
+}
+
+pub fn verify(public_key: &PublicKey, message: Digest, signature: &Signature) -> bool {
+    let public_key_from_signature = public_key_from_signature(
+        public_key.public_seed,
+        public_key.key_index,
+        message,
+        signature,
+    );
+
+    public_key_from_signature == *public_key
+}
+
+/// The public key under which the signature is valid for the message, if any.
+pub(super) fn public_key_from_signature(
+    public_seed: Digest,
+    key_index: u64,
+    message: Digest,
+    signature: &Signature,
+) -> PublicKey {
+    let chain_ends = digits(message)
+        .into_iter()
+        .zip_eq(signature.chain_elements)
+        .enumerate()
+        .map(|(chain, (digit, element))| {
+            Chain::new(public_seed, key_index, chain).walk(element, digit, W - 1 - digit)
+        })
+        .collect_vec();
+
+    public_key_from_chain_ends(public_seed, key_index, &chain_ends)
+}
+
+/// The base-[`W`] digits of the message followed by those of the checksum, least
+/// significant digit first.
+fn digits(message: Digest) -> [usize; NUM_CHAINS] {
+    let to_digits = |mut value: u64, num_digits: usize| {
+        (0..num_digits).map(move |_| {
+            let digit = (value % W as u64) as usize;
+            value /= W as u64;
+            digit
+        })
+    };
+
+    let num_digits_per_element = NUM_MESSAGE_CHAINS / Digest::LEN;
+    let mut digits = message
+        .values()
+        .into_iter()
+        .flat_map(|element| to_digits(element.value(), num_digits_per_element))
+        .collect_vec();
+
+    let checksum = digits.iter().map(|&digit| (W - 1 - digit) as u64).sum();
+    digits.extend(to_digits(checksum, NUM_CHECKSUM_CHAINS));
+
+    digits.try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+
+    #[proptest(cases = 20)]
+    fn signature_for_message_verifies(randomness: [u8; 32], #[strategy(arb())] message: Digest) {
+        let (secret_key, public_key) = keygen(randomness);
+        let signature = sign(secret_key, message);
+        prop_assert!(verify(&public_key, message, &signature));
+    }
+
+    #[proptest(cases = 20)]
+    fn signature_for_other_message_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+        #[strategy(arb())]
+        #[filter(#message != #other_message)]
+        other_message: Digest,
+    ) {
+        let (secret_key, public_key) = keygen(randomness);
+        let signature = sign(secret_key, message);
+        prop_assert!(!verify(&public_key, other_message, &signature));
+    }
+
+    #[proptest(cases = 20)]
+    fn signature_does_not_verify_under_other_public_key(
+        randomness: [u8; 32],
+        #[filter(#randomness != #other_randomness)] other_randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+    ) {
+        let (secret_key, _) = keygen(randomness);
+        let (_, other_public_key) = keygen(other_randomness);
+        let signature = sign(secret_key, message);
+        prop_assert!(!verify(&other_public_key, message, &signature));
+    }
+
+    #[proptest(cases = 20)]
+    fn tampered_signature_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+        #[strategy(0..NUM_CHAINS)] tampered_chain: usize,
+        #[strategy(arb())] tampered_element: Digest,
+    ) {
+        let (secret_key, public_key) = keygen(randomness);
+        let mut signature = sign(secret_key, message);
+        prop_assume!(signature.chain_elements[tampered_chain] != tampered_element);
+
+        signature.chain_elements[tampered_chain] = tampered_element;
+        prop_assert!(!verify(&public_key, message, &signature));
+    }
+
+    #[proptest]
+    fn checksum_decreases_if_any_message_digit_increases(
+        #[strategy(arb())] message: Digest,
+        #[strategy(0..Digest::LEN)] element_index: usize,
+        #[strategy(0..NUM_MESSAGE_CHAINS / Digest::LEN)] digit_index: usize,
+    ) {
+        let digit_weight = 1 << (LOG2_W * digit_index);
+        let element = message.values()[element_index].value();
+        let digit = (element / digit_weight) % W as u64;
+        let Some(new_element) = element.checked_add(digit_weight) else {
+            return Ok(());
+        };
+        prop_assume!(digit < W as u64 - 1 && new_element < BFieldElement::P);
+
+        let mut other_message = message;
+        other_message.0[element_index] = BFieldElement::new(new_element);
+
+        let checksum = |digits: [usize; NUM_CHAINS]| {
+            digits[NUM_MESSAGE_CHAINS..]
+                .iter()
+                .rev()
+                .fold(0, |acc, &checksum_digit| acc * W + checksum_digit)
+        };
+        prop_assert!(checksum(digits(other_message)) < checksum(digits(message)));
+    }
+
+    #[proptest]
+    fn signature_can_be_encoded_and_decoded(#[strategy(arb())] signature: Signature) {
+        let encoding = signature.encode();
+        prop_assert_eq!(signature, *Signature::decode(&encoding)?);
+    }
+}
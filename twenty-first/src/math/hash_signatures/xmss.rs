@@ -0,0 +1,429 @@
+//! A stateful many-time signature scheme in the style of [XMSS][xmss], using [`Tip5`](crate::math::tip5::Tip5).
+//!
+//! A key pair of height `h` consists of `2^h` [one-time key pairs](super::wots). The public
+//! key is the root of a [`MerkleTree`] whose leafs are the one-time public keys. Every
+//! signature uses the next unused one-time key and includes the authentication path of the
+//! corresponding one-time public key. Since reusing a one-time key allows forging signatures,
+//! the secret key is stateful: it keeps track of the next unused one-time key.
+//!
+//! The secret key's state must be persisted, for example using its [encoding](BFieldCodec),
+//! _before_ each signature is released. Otherwise, a crash after releasing a signature
+//! restores an outdated state, and the next signature reuses a one-time key.
+//!
+//! [xmss]: https://www.rfc-editor.org/rfc/rfc8391
+
+use arbitrary::Arbitrary;
+use rayon::prelude::*;
+use thiserror::Error;
+
+use super::derive_seeds;
+use super::wots;
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::bfield_codec::BFieldCodecError;
+use crate::math::digest::Digest;
+use crate::util_types::merkle_tree::CpuParallel;
+use crate::util_types::merkle_tree::MerkleTree;
+use crate::util_types::merkle_tree::MerkleTreeInclusionProof;
+
+/// The maximum height of the Merkle tree of one-time public keys. A key pair of height `h`
+/// can sign `2^h` messages.
+///
+/// Both [key generation](keygen) and [decoding](SecretKey::decode) of a secret key compute
+/// all `2^h` one-time public keys, and the secret key holds the entire Merkle tree in memory.
+pub const MAX_HEIGHT: u32 = 20;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Error)]
+#[non_exhaustive]
+pub enum XmssError {
+    #[error("all {0} one-time keys have been used")]
+    KeyExhausted(u64),
+
+    #[error("height {0} exceeds the maximum height {MAX_HEIGHT}")]
+    HeightTooLarge(u32),
+
+    #[error("next key index {next_key_index} exceeds the number of keys {num_keys}")]
+    KeyIndexOutOfRange { next_key_index: u64, num_keys: u64 },
+}
+
+/// The secret key, including the index of the next unused one-time key.
+///
+/// The [encoding](BFieldCodec::encode) holds the seeds, the index of the next unused one-time
+/// key, and the height. [Decoding](BFieldCodec::decode) recomputes the Merkle tree of one-time
+/// public keys, which takes time linear in the number of keys.
+///
+/// Cloning the secret key, or decoding an outdated encoding, and signing with both copies
+/// reuses one-time keys, which allows forging signatures.
+#[derive(Debug, Clone)]
+pub struct SecretKey {
+    secret_seed: Digest,
+    public_seed: Digest,
+    next_key_index: u64,
+    tree: MerkleTree,
+}
+
+/// The part of the [`SecretKey`] that gets [encoded](BFieldCodec::encode).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, BFieldCodec)]
+struct SecretKeyState {
+    secret_seed: Digest,
+    public_seed: Digest,
+    next_key_index: u64,
+    height: u32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, BFieldCodec, Arbitrary)]
+pub struct PublicKey {
+    root: Digest,
+    public_seed: Digest,
+    height: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, BFieldCodec, Arbitrary)]
+pub struct Signature {
+    key_index: u64,
+    one_time_signature: wots::Signature,
+    authentication_path: Vec<Digest>,
+}
+
+impl SecretKey {
+    /// The total number of messages the key pair can sign.
+    pub fn num_keys(&self) -> u64 {
+        self.tree.num_leafs() as u64
+    }
+
+    /// The number of messages the secret key can still sign.
+    pub fn num_remaining_signatures(&self) -> u64 {
+        self.num_keys() - self.next_key_index
+    }
+
+    fn one_time_secret_key(&self, key_index: u64) -> wots::SecretKey {
+        wots::SecretKey::new(self.secret_seed, self.public_seed, key_index)
+    }
+
+    fn height(&self) -> u32 {
+        self.tree.height() as u32
+    }
+}
+
+impl TryFrom<SecretKeyState> for SecretKey {
+    type Error = XmssError;
+
+    fn try_from(state: SecretKeyState) -> Result<Self, Self::Error> {
+        let SecretKeyState {
+            secret_seed,
+            public_seed,
+            next_key_index,
+            height,
+        } = state;
+        if height > MAX_HEIGHT {
+            return Err(XmssError::HeightTooLarge(height));
+        }
+        let num_keys = 1 << height;
+        if next_key_index > num_keys {
+            return Err(XmssError::KeyIndexOutOfRange {
+                next_key_index,
+                num_keys,
+            });
+        }
+
+        Ok(Self {
+            secret_seed,
+            public_seed,
+            next_key_index,
+            tree: one_time_public_key_tree(secret_seed, public_seed, height),
+        })
+    }
+}
+
+impl BFieldCodec for SecretKey {
+    type Error = BFieldCodecError;
+
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let inner_failure = |e: Box<_>| BFieldCodecError::InnerDecodingFailure(e);
+        let state = *SecretKeyState::decode(sequence).map_err(|e| inner_failure(e.into()))?;
+        let secret_key = Self::try_from(state).map_err(|e| inner_failure(e.into()))?;
+        Ok(Box::new(secret_key))
+    }
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        let state = SecretKeyState {
+            secret_seed: self.secret_seed,
+            public_seed: self.public_seed,
+            next_key_index: self.next_key_index,
+            height: self.height(),
+        };
+        state.encode()
+    }
+
+    fn static_length() -> Option<usize> {
+        SecretKeyState::static_length()
+    }
+}
+
+impl PublicKey {
+    pub fn root(&self) -> Digest {
+        self.root
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+impl Signature {
+    /// The index of the one-time key used for the signature.
+    pub fn key_index(&self) -> u64 {
+        self.key_index
+    }
+}
+
+/// Generate a key pair that can sign `2^height` messages. Key generation computes all
+/// one-time public keys and thus takes time linear in `2^height`.
+///
+/// # Panics
+///
+/// Panics if the height exceeds [`MAX_HEIGHT`].
+pub fn keygen(randomness: [u8; 32], height: u32) -> (SecretKey, PublicKey) {
+    assert!(height <= MAX_HEIGHT, "height must not exceed {MAX_HEIGHT}");
+
+    let (secret_seed, public_seed) = derive_seeds(randomness);
+    let tree = one_time_public_key_tree(secret_seed, public_seed, height);
+
+    let public_key = PublicKey {
+        root: tree.root(),
+        public_seed,
+        height,
+    };
+    let secret_key = SecretKey {
+        secret_seed,
+        public_seed,
+        next_key_index: 0,
+        tree,
+    };
+
+    (secret_key, public_key)
+}
+
+fn one_time_public_key_tree(secret_seed: Digest, public_seed: Digest, height: u32) -> MerkleTree {
+    let leafs = (0..1_u64 << height)
+        .into_par_iter()
+        .map(|key_index| {
+            let secret_key = wots::SecretKey::new(secret_seed, public_seed, key_index);
+            secret_key.public_key().chain_ends_digest()
+        })
+        .collect::<Vec<_>>();
+
+    MerkleTree::new::<CpuParallel>(&leafs).unwrap()
+}
+
+/// Sign the message using the next unused one-time key.
+///
+/// The secret key's updated state must be persisted before the signature is released. See
+/// the [module-level documentation](self).
+///
+/// # Errors
+///
+/// Errors if all one-time keys have been used.
+pub fn sign(secret_key: &mut SecretKey, message: Digest) -> Result<Signature, XmssError> {
+    let key_index = secret_key.next_key_index;
+    if key_index >= secret_key.num_keys() {
+        return Err(XmssError::KeyExhausted(secret_key.num_keys()));
+    }
+    secret_key.next_key_index += 1;
+
+    let one_time_secret_key = secret_key.one_time_secret_key(key_index);
+    let one_time_signature = wots::sign(one_time_secret_key, message);
+    let authentication_path = secret_key
+        .tree
+        .authentication_structure(&[key_index as usize])
+        .unwrap();
+
+    Ok(Signature {
+        key_index,
+        one_time_signature,
+        authentication_path,
+    })
+}
+
+pub fn verify(public_key: &PublicKey, message: Digest, signature: &Signature) -> bool {
+    if signature.authentication_path.len() != public_key.height as usize {
+        return false;
+    }
+    let Ok(leaf_index) = usize::try_from(signature.key_index) else {
+        return false;
+    };
+    if leaf_index >> public_key.height != 0 {
+        return false;
+    }
+
+    let one_time_public_key = wots::public_key_from_signature(
+        public_key.public_seed,
+        signature.key_index,
+        message,
+        &signature.one_time_signature,
+    );
+
+    let inclusion_proof = MerkleTreeInclusionProof {
+        tree_height: public_key.height as usize,
+        indexed_leafs: vec![(leaf_index, one_time_public_key.chain_ends_digest())],
+        authentication_structure: signature.authentication_path.clone(),
+    };
+    inclusion_proof.verify(public_key.root)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+
+    #[proptest(cases = 5)]
+    fn all_signatures_verify(
+        randomness: [u8; 32],
+        #[strategy(0_u32..4)] height: u32,
+        #[strategy(arb())] message: Digest,
+    ) {
+        let (mut secret_key, public_key) = keygen(randomness, height);
+        for key_index in 0..1 << height {
+            let signature = sign(&mut secret_key, message)?;
+            prop_assert_eq!(key_index, signature.key_index());
+            prop_assert!(verify(&public_key, message, &signature));
+        }
+        prop_assert_eq!(0, secret_key.num_remaining_signatures());
+    }
+
+    #[proptest(cases = 5)]
+    fn signing_with_exhausted_key_fails(
+        randomness: [u8; 32],
+        #[strategy(0_u32..3)] height: u32,
+        #[strategy(arb())] message: Digest,
+    ) {
+        let (mut secret_key, _) = keygen(randomness, height);
+        for _ in 0..1 << height {
+            sign(&mut secret_key, message)?;
+        }
+        let err = sign(&mut secret_key, message).unwrap_err();
+        prop_assert_eq!(XmssError::KeyExhausted(1 << height), err);
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_for_other_message_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+        #[strategy(arb())]
+        #[filter(#message != #other_message)]
+        other_message: Digest,
+    ) {
+        let (mut secret_key, public_key) = keygen(randomness, 2);
+        let signature = sign(&mut secret_key, message)?;
+        prop_assert!(!verify(&public_key, other_message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_does_not_verify_under_other_public_key(
+        randomness: [u8; 32],
+        #[filter(#randomness != #other_randomness)] other_randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+    ) {
+        let (mut secret_key, _) = keygen(randomness, 2);
+        let (_, other_public_key) = keygen(other_randomness, 2);
+        let signature = sign(&mut secret_key, message)?;
+        prop_assert!(!verify(&other_public_key, message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_with_tampered_key_index_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+        #[strategy(1_u64..8)] key_index_offset: u64,
+    ) {
+        let (mut secret_key, public_key) = keygen(randomness, 2);
+        let mut signature = sign(&mut secret_key, message)?;
+        signature.key_index += key_index_offset;
+        prop_assert!(!verify(&public_key, message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_with_tampered_authentication_path_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+        #[strategy(0_usize..3)] path_index: usize,
+        #[strategy(arb())] tampered_digest: Digest,
+    ) {
+        let (mut secret_key, public_key) = keygen(randomness, 3);
+        let mut signature = sign(&mut secret_key, message)?;
+        prop_assume!(signature.authentication_path[path_index] != tampered_digest);
+        signature.authentication_path[path_index] = tampered_digest;
+        prop_assert!(!verify(&public_key, message, &signature));
+
+        signature.authentication_path.pop();
+        prop_assert!(!verify(&public_key, message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_and_public_key_can_be_encoded_and_decoded(
+        randomness: [u8; 32],
+        #[strategy(arb())] message: Digest,
+    ) {
+        let (mut secret_key, public_key) = keygen(randomness, 1);
+        let signature = sign(&mut secret_key, message)?;
+
+        let decoded_public_key = *PublicKey::decode(&public_key.encode())?;
+        let decoded_signature = *Signature::decode(&signature.encode())?;
+        prop_assert!(verify(&decoded_public_key, message, &decoded_signature));
+    }
+
+    #[proptest(cases = 5)]
+    fn decoded_secret_key_continues_with_next_unused_key(
+        randomness: [u8; 32],
+        #[strategy(0_u32..4)] height: u32,
+        #[strategy(0_u64..1 << #height)] num_signatures: u64,
+        #[strategy(arb())] message: Digest,
+    ) {
+        let (mut secret_key, public_key) = keygen(randomness, height);
+        for _ in 0..num_signatures {
+            sign(&mut secret_key, message)?;
+        }
+
+        let mut decoded_secret_key = *SecretKey::decode(&secret_key.encode())?;
+        prop_assert_eq!(secret_key.tree.root(), decoded_secret_key.tree.root());
+        let signature = sign(&mut decoded_secret_key, message)?;
+        prop_assert_eq!(num_signatures, signature.key_index());
+        prop_assert!(verify(&public_key, message, &signature));
+    }
+
+    #[test]
+    fn decoding_invalid_secret_key_state_fails() {
+        let seed = Digest::default();
+        let too_high = SecretKeyState {
+            secret_seed: seed,
+            public_seed: seed,
+            next_key_index: 0,
+            height: MAX_HEIGHT + 1,
+        };
+        assert!(SecretKey::decode(&too_high.encode()).is_err());
+        let too_high_err = SecretKey::try_from(too_high).unwrap_err();
+        assert_eq!(XmssError::HeightTooLarge(MAX_HEIGHT + 1), too_high_err);
+
+        let out_of_range = SecretKeyState {
+            next_key_index: 5,
+            height: 2,
+            ..too_high
+        };
+        assert!(SecretKey::decode(&out_of_range.encode()).is_err());
+        let out_of_range_err = SecretKey::try_from(out_of_range).unwrap_err();
+        let expected = XmssError::KeyIndexOutOfRange {
+            next_key_index: 5,
+            num_keys: 4,
+        };
+        assert_eq!(expected, out_of_range_err);
+    }
+
+    #[test]
+    #[should_panic(expected = "height must not exceed")]
+    fn key_generation_with_too_large_height_panics() {
+        let _ = keygen([0; 32], MAX_HEIGHT + 1);
+    }
+}
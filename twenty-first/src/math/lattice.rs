@@ -2,6 +2,8 @@ use std::ops::Add;
 use std::ops::AddAssign;
 use std::ops::Mul;
 use std::ops::Sub;
+use std::ops::SubAssign;
+use std::sync::OnceLock;
 
 use itertools::Itertools;
 use num_traits::ConstZero;
@@ -11,11 +13,17 @@ use rayon::prelude::ParallelIterator;
 use serde_big_array::BigArray;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
+use zeroize::Zeroize;
 
 use super::b_field_element::BFieldElement;
+use super::traits::Inverse;
+use super::traits::PrimitiveRootOfUnity;
+use crate::math::bfield_codec::BFieldCodec;
+
+pub mod kem;
+pub mod signature;
 
 pub fn coset_intt_noswap_64(array: &mut [BFieldElement; 64]) {
-    const N: usize = 64;
     const N_INV: BFieldElement = BFieldElement::new(18158513693329981441);
     let powers_of_psi_inv_bitreversed = [
         BFieldElement::new(1),
@@ -83,36 +91,11 @@ pub fn coset_intt_noswap_64(array: &mut [BFieldElement; 64]) {
         BFieldElement::new(2199056809472),
         BFieldElement::new(18446741870424883713),
     ];
-    const LOGN: usize = 6;
-
-    let mut t = 1;
-    let mut h = N / 2;
-    for _ in 0..LOGN {
-        let mut k = 0;
-        for i in 0..h {
-            let zeta = powers_of_psi_inv_bitreversed[h + i];
-            for j in k..(k + t) {
-                let u = array[j];
-                let v = array[j + t];
-                array[j] = u + v;
-                array[j + t] = (u - v) * zeta;
-            }
-
-            k += 2 * t;
-        }
 
-        t *= 2;
-        h >>= 1;
-    }
-
-    for a in array.iter_mut() {
-        *a *= N_INV;
-    }
+    coset_intt_noswap_with_twiddles(array, &powers_of_psi_inv_bitreversed, N_INV);
 }
 
 pub fn coset_ntt_noswap_64(array: &mut [BFieldElement; 64]) {
-    const N: usize = 64;
-
     let powers_of_psi_bitreversed = [
         BFieldElement::new(1),
         BFieldElement::new(281474976710656),
@@ -180,9 +163,103 @@ pub fn coset_ntt_noswap_64(array: &mut [BFieldElement; 64]) {
         BFieldElement::new(18446743794540871745),
     ];
 
+    coset_ntt_noswap_with_twiddles(array, &powers_of_psi_bitreversed);
+}
+
+pub fn coset_intt_noswap_128(array: &mut [BFieldElement; 128]) {
+    let twiddles = CosetNttTwiddles::get(128);
+    coset_intt_noswap_with_twiddles(
+        array,
+        &twiddles.powers_of_psi_inv_bitreversed,
+        twiddles.n_inv,
+    );
+}
+
+pub fn coset_ntt_noswap_128(array: &mut [BFieldElement; 128]) {
+    let twiddles = CosetNttTwiddles::get(128);
+    coset_ntt_noswap_with_twiddles(array, &twiddles.powers_of_psi_bitreversed);
+}
+
+pub fn coset_intt_noswap_256(array: &mut [BFieldElement; 256]) {
+    let twiddles = CosetNttTwiddles::get(256);
+    coset_intt_noswap_with_twiddles(
+        array,
+        &twiddles.powers_of_psi_inv_bitreversed,
+        twiddles.n_inv,
+    );
+}
+
+pub fn coset_ntt_noswap_256(array: &mut [BFieldElement; 256]) {
+    let twiddles = CosetNttTwiddles::get(256);
+    coset_ntt_noswap_with_twiddles(array, &twiddles.powers_of_psi_bitreversed);
+}
+
+/// The forward coset-NTT for any supported ring degree.
+fn coset_ntt_noswap<const D: usize>(array: &mut [BFieldElement; D]) {
+    let array = array.as_mut_slice();
+    match D {
+        64 => coset_ntt_noswap_64(array.try_into().unwrap()),
+        128 => coset_ntt_noswap_128(array.try_into().unwrap()),
+        256 => coset_ntt_noswap_256(array.try_into().unwrap()),
+        _ => panic!("unsupported ring degree {D}"),
+    }
+}
+
+/// The inverse coset-NTT for any supported ring degree.
+fn coset_intt_noswap<const D: usize>(array: &mut [BFieldElement; D]) {
+    let array = array.as_mut_slice();
+    match D {
+        64 => coset_intt_noswap_64(array.try_into().unwrap()),
+        128 => coset_intt_noswap_128(array.try_into().unwrap()),
+        256 => coset_intt_noswap_256(array.try_into().unwrap()),
+        _ => panic!("unsupported ring degree {D}"),
+    }
+}
+
+/// The twiddle factors for the coset-NTT of length `N`, where `ψ` is a primitive `2·N`th
+/// root of unity, _i.e._, a root of `X^N + 1`.
+struct CosetNttTwiddles {
+    powers_of_psi_bitreversed: Vec<BFieldElement>,
+    powers_of_psi_inv_bitreversed: Vec<BFieldElement>,
+    n_inv: BFieldElement,
+}
+
+impl CosetNttTwiddles {
+    fn new(n: usize) -> Self {
+        let log_n = n.ilog2();
+        let psi = BFieldElement::primitive_root_of_unity(2 * n as u64).unwrap();
+        let psi_inv = psi.inverse();
+        let bit_reverse = |i: usize| (i.reverse_bits() >> (usize::BITS - log_n)) as u64;
+        let powers_of =
+            |root: BFieldElement| (0..n).map(|i| root.mod_pow(bit_reverse(i))).collect();
+
+        Self {
+            powers_of_psi_bitreversed: powers_of(psi),
+            powers_of_psi_inv_bitreversed: powers_of(psi_inv),
+            n_inv: BFieldElement::new(n as u64).inverse(),
+        }
+    }
+
+    fn get(n: usize) -> &'static Self {
+        static TWIDDLES_128: OnceLock<CosetNttTwiddles> = OnceLock::new();
+        static TWIDDLES_256: OnceLock<CosetNttTwiddles> = OnceLock::new();
+
+        match n {
+            128 => TWIDDLES_128.get_or_init(|| Self::new(128)),
+            256 => TWIDDLES_256.get_or_init(|| Self::new(256)),
+            _ => panic!("no cached twiddles for length {n}"),
+        }
+    }
+}
+
+fn coset_ntt_noswap_with_twiddles(
+    array: &mut [BFieldElement],
+    powers_of_psi_bitreversed: &[BFieldElement],
+) {
+    let n = array.len();
     let mut m: usize = 1;
-    let mut t: usize = N;
-    while m < N {
+    let mut t: usize = n;
+    while m < n {
         t >>= 1;
 
         for i in 0..m {
@@ -200,46 +277,90 @@ pub fn coset_ntt_noswap_64(array: &mut [BFieldElement; 64]) {
     }
 }
 
+fn coset_intt_noswap_with_twiddles(
+    array: &mut [BFieldElement],
+    powers_of_psi_inv_bitreversed: &[BFieldElement],
+    n_inv: BFieldElement,
+) {
+    let n = array.len();
+    let mut t = 1;
+    let mut h = n / 2;
+    while h > 0 {
+        let mut k = 0;
+        for i in 0..h {
+            let zeta = powers_of_psi_inv_bitreversed[h + i];
+            for j in k..(k + t) {
+                let u = array[j];
+                let v = array[j + t];
+                array[j] = u + v;
+                array[j + t] = (u - v) * zeta;
+            }
+
+            k += 2 * t;
+        }
+
+        t *= 2;
+        h >>= 1;
+    }
+
+    for a in array.iter_mut() {
+        *a *= n_inv;
+    }
+}
+
 pub const CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES: usize = 64;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct CyclotomicRingElement {
+/// The largest supported bound of the limb-wise centered binomial noise, see
+/// [`sample_short_bfield_element`].
+pub const MAX_NOISE_ETA: u32 = 8;
+
+/// An element of the ring `Fp[X] / (X^D + 1)`. The supported degrees `D` are 64, 128, and
+/// 256.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Zeroize)]
+pub struct CyclotomicRingElement<const D: usize = CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES> {
     #[serde(with = "BigArray")]
-    coefficients: [BFieldElement; CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES],
+    coefficients: [BFieldElement; D],
 }
 
-impl From<[BFieldElement; CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES]> for CyclotomicRingElement {
-    fn from(value: [BFieldElement; CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES]) -> Self {
+impl<const D: usize> From<[BFieldElement; D]> for CyclotomicRingElement<D> {
+    fn from(value: [BFieldElement; D]) -> Self {
         Self {
             coefficients: value,
         }
     }
 }
 
-impl From<CyclotomicRingElement> for [BFieldElement; CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES] {
-    fn from(value: CyclotomicRingElement) -> Self {
+impl<const D: usize> From<CyclotomicRingElement<D>> for [BFieldElement; D] {
+    fn from(value: CyclotomicRingElement<D>) -> Self {
         value.coefficients
     }
 }
 
-impl CyclotomicRingElement {
-    pub fn sample_short(randomness: &[u8]) -> CyclotomicRingElement {
-        debug_assert!(randomness.len() >= 8 * 64);
-        CyclotomicRingElement {
+impl<const D: usize> CyclotomicRingElement<D> {
+    pub fn sample_short(randomness: &[u8]) -> Self {
+        Self::sample_short_with_eta(randomness, MAX_NOISE_ETA)
+    }
+
+    /// Like [`sample_short`](Self::sample_short), but with a configurable bound `eta` of
+    /// the limb-wise centered binomial noise. See also [`sample_short_bfield_element`].
+    pub fn sample_short_with_eta(randomness: &[u8], eta: u32) -> Self {
+        debug_assert!(randomness.len() >= 8 * D);
+        Self {
             coefficients: randomness
                 .chunks(8)
+                .take(D)
                 .map(|r| TryInto::<[u8; 8]>::try_into(r).unwrap())
-                .map(|r| sample_short_bfield_element(&r))
+                .map(|r| sample_short_bfield_element_with_eta(&r, eta))
                 .collect_vec()
                 .try_into()
                 .unwrap(),
         }
     }
 
-    pub fn sample_uniform(randomness: &[u8]) -> CyclotomicRingElement {
-        debug_assert!(randomness.len() >= 9 * 64);
-        let mut coefficients = [BFieldElement::ZERO; 64];
-        for i in 0..64 {
+    pub fn sample_uniform(randomness: &[u8]) -> Self {
+        debug_assert!(randomness.len() >= 9 * D);
+        let mut coefficients = [BFieldElement::ZERO; D];
+        for i in 0..D {
             let mut acc = 0u128;
             for j in 0..9 {
                 acc = acc * 256 + randomness[i * 9 + j] as u128;
@@ -247,24 +368,31 @@ impl CyclotomicRingElement {
             acc %= BFieldElement::P as u128;
             coefficients[i] = BFieldElement::new(acc as u64);
         }
-        CyclotomicRingElement { coefficients }
+        Self { coefficients }
     }
 
-    pub fn hadamard(a: CyclotomicRingElement, b: CyclotomicRingElement) -> CyclotomicRingElement {
-        let mut c = CyclotomicRingElement::zero();
-        for i in 0..64 {
+    pub fn hadamard(a: Self, b: Self) -> Self {
+        let mut c = Self::zero();
+        for i in 0..D {
             c.coefficients[i] = a.coefficients[i] * b.coefficients[i];
         }
         c
     }
+
+    /// Add the hadamard product of `a` and `b` to `self`, without copying either factor.
+    fn add_hadamard_product(&mut self, a: &Self, b: &Self) {
+        for i in 0..D {
+            self.coefficients[i] += a.coefficients[i] * b.coefficients[i];
+        }
+    }
 }
 
-impl Add for CyclotomicRingElement {
-    type Output = CyclotomicRingElement;
+impl<const D: usize> Add for CyclotomicRingElement<D> {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        CyclotomicRingElement {
-            coefficients: (0..64)
+        Self {
+            coefficients: (0..D)
                 .map(|i| self.coefficients[i] + rhs.coefficients[i])
                 .collect_vec()
                 .try_into()
@@ -273,8 +401,14 @@ impl Add for CyclotomicRingElement {
     }
 }
 
-impl AddAssign for CyclotomicRingElement {
+impl<const D: usize> AddAssign for CyclotomicRingElement<D> {
     fn add_assign(&mut self, rhs: Self) {
+        *self += &rhs;
+    }
+}
+
+impl<const D: usize> AddAssign<&Self> for CyclotomicRingElement<D> {
+    fn add_assign(&mut self, rhs: &Self) {
         self.coefficients
             .iter_mut()
             .zip(rhs.coefficients.iter())
@@ -282,12 +416,21 @@ impl AddAssign for CyclotomicRingElement {
     }
 }
 
-impl Sub for CyclotomicRingElement {
-    type Output = CyclotomicRingElement;
+impl<const D: usize> SubAssign<&Self> for CyclotomicRingElement<D> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        self.coefficients
+            .iter_mut()
+            .zip(rhs.coefficients.iter())
+            .for_each(|(l, r)| *l -= *r);
+    }
+}
+
+impl<const D: usize> Sub for CyclotomicRingElement<D> {
+    type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        CyclotomicRingElement {
-            coefficients: (0..64)
+        Self {
+            coefficients: (0..D)
                 .map(|i| self.coefficients[i] - rhs.coefficients[i])
                 .collect_vec()
                 .try_into()
@@ -296,156 +439,173 @@ impl Sub for CyclotomicRingElement {
     }
 }
 
-impl Mul for CyclotomicRingElement {
-    type Output = CyclotomicRingElement;
+impl<const D: usize> Mul for CyclotomicRingElement<D> {
+    type Output = Self;
 
     /// Multiply two polynomials in the ring
-    /// `Fp[X] / (X^64 + 1)`
+    /// `Fp[X] / (X^D + 1)`
     /// using `coset-NTT`.
     fn mul(self, rhs: Self) -> Self::Output {
         let mut lhs_coeffs = self.coefficients;
         let mut rhs_coeffs = rhs.coefficients;
-        coset_ntt_noswap_64(&mut lhs_coeffs);
-        coset_ntt_noswap_64(&mut rhs_coeffs);
-        let mut out_coeffs = [BFieldElement::ZERO; 64];
-        for i in 0..64 {
+        coset_ntt_noswap(&mut lhs_coeffs);
+        coset_ntt_noswap(&mut rhs_coeffs);
+        let mut out_coeffs = [BFieldElement::ZERO; D];
+        for i in 0..D {
             out_coeffs[i] = lhs_coeffs[i] * rhs_coeffs[i];
         }
-        coset_intt_noswap_64(&mut out_coeffs);
-        CyclotomicRingElement {
+        coset_intt_noswap(&mut out_coeffs);
+        Self {
             coefficients: out_coeffs,
         }
     }
 }
 
-impl Zero for CyclotomicRingElement {
+impl<const D: usize> Zero for CyclotomicRingElement<D> {
     fn zero() -> Self {
-        CyclotomicRingElement {
-            coefficients: [BFieldElement::ZERO; 64],
+        Self {
+            coefficients: [BFieldElement::ZERO; D],
         }
     }
 
     fn is_zero(&self) -> bool {
-        self.coefficients == [BFieldElement::ZERO; 64]
+        self.coefficients == [BFieldElement::ZERO; D]
     }
 }
 
-pub fn embed_msg(msg: [u8; 32]) -> CyclotomicRingElement {
-    let mut embedding: [BFieldElement; 64] = [BFieldElement::ZERO; 64];
-    for i in 0..msg.len() {
-        let mut integer = 0u64;
-        for j in 0..4 {
-            let bit = (msg[i] >> j) & 1;
-            integer += (bit as u64) << (15 + 16 * j);
-        }
-        embedding[2 * i] = BFieldElement::new(integer);
+// The derive macro cannot infer the types involved in const-generic arrays, so BFieldCodec
+// is implemented manually for CyclotomicRingElement.
+impl<const D: usize> BFieldCodec for CyclotomicRingElement<D> {
+    type Error = <[BFieldElement; D] as BFieldCodec>::Error;
 
-        integer = 0;
-        for j in 0..4 {
-            let bit = (msg[i] >> (4 + j)) & 1;
-            integer += (bit as u64) << (15 + 16 * j);
-        }
-        embedding[2 * i + 1] = BFieldElement::new(integer);
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let coefficients = *<[BFieldElement; D]>::decode(sequence)?;
+        Ok(Box::new(Self { coefficients }))
     }
-    CyclotomicRingElement {
-        coefficients: embedding,
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        self.coefficients.encode()
     }
+
+    fn static_length() -> Option<usize> {
+        <[BFieldElement; D]>::static_length()
+    }
+}
+
+pub fn embed_msg(msg: [u8; 32]) -> CyclotomicRingElement {
+    embed_message(msg)
 }
 
 pub fn extract_msg(embedding: CyclotomicRingElement) -> [u8; 32] {
-    let mut msg = [0u8; 32];
-    for (ctr, pair) in embedding.coefficients.chunks(2).enumerate() {
-        let mut byte = 0u8;
-        let mut value = pair[0].value();
-        for j in 0..4 {
-            let chunk = value & 0xffff;
-            value >>= 16;
+    extract_message(&embedding)
+}
+
+/// Embed the message's 256 bits into the ring element. Every coefficient holds
+/// `256 / D` bits, each of which is placed in the most significant bit of one 16-bit limb.
+fn embed_message<const D: usize>(msg: [u8; 32]) -> CyclotomicRingElement<D> {
+    let mut embedding = CyclotomicRingElement::zero();
+    embed_message_into(&msg, &mut embedding);
+    embedding
+}
 
-            let bit = if chunk < (1 << 14) || (1 << 16) - chunk < (1 << 14) {
-                0
-            } else {
-                1
-            };
-            byte |= bit << j;
+/// Like [`embed_message`], but writes the embedding into an existing ring element. Neither
+/// the message nor its embedding is copied, which matters if they are secret.
+fn embed_message_into<const D: usize>(msg: &[u8; 32], embedding: &mut CyclotomicRingElement<D>) {
+    let bits_per_coefficient = 8 * msg.len() / D;
+    let bit = |index: usize| (msg[index / 8] >> (index % 8)) & 1;
+
+    for (i, coefficient) in embedding.coefficients.iter_mut().enumerate() {
+        let mut integer = 0u64;
+        for j in 0..bits_per_coefficient {
+            integer += (bit(i * bits_per_coefficient + j) as u64) << (15 + 16 * j);
         }
+        *coefficient = BFieldElement::new(integer);
+    }
+}
+
+/// The inverse of [`embed_message`], which tolerates small noise in every 16-bit limb.
+fn extract_message<const D: usize>(embedding: &CyclotomicRingElement<D>) -> [u8; 32] {
+    let mut msg = [0u8; 32];
+    let bits_per_coefficient = 8 * msg.len() / D;
 
-        value = pair[1].value();
-        for j in 0..4 {
+    for (i, coefficient) in embedding.coefficients.iter().enumerate() {
+        let mut value = coefficient.value();
+        for j in 0..bits_per_coefficient {
             let chunk = value & 0xffff;
             value >>= 16;
 
-            let bit = if chunk < (1 << 14) || (1 << 16) - chunk < (1 << 14) {
-                0
-            } else {
-                1
-            };
-            byte |= bit << (4 + j);
+            // The bit is set if and only if `2^14 <= chunk <= 3·2^14`. Because the message
+            // is secret, compute it without branching.
+            let is_not_too_small = 1 - (chunk.wrapping_sub(1 << 14) >> 63);
+            let is_not_too_large = 1 - ((3_u64 << 14).wrapping_sub(chunk) >> 63);
+            let bit = (is_not_too_small & is_not_too_large) as u8;
+            let index = i * bits_per_coefficient + j;
+            msg[index / 8] |= bit << (index % 8);
         }
-        msg[ctr] = byte;
     }
     msg
 }
 
-const fn num_set_bits(a: u8) -> u8 {
-    let mut sum = 0;
-    let mut i = 0;
-    while i < 8 {
-        let bit = if a & (1 << i) != 0 { 1 } else { 0 };
-        sum += bit;
-        i += 1;
-    }
-    sum
-}
-
-const fn num_set_bits_table() -> [u8; 256] {
-    let mut table: [u8; 256] = [0u8; 256];
-    let mut i = 1;
-    while i < 256 {
-        table[i] = num_set_bits(i as u8);
-        i += 1;
-    }
-    table
+pub fn sample_short_bfield_element(randomness: &[u8; 8]) -> BFieldElement {
+    sample_short_bfield_element_with_eta(randomness, MAX_NOISE_ETA)
 }
 
-pub fn sample_short_bfield_element(randomness: &[u8; 8]) -> BFieldElement {
-    const NUM_SET_BITS: [u8; 256] = num_set_bits_table();
-    let left = ((NUM_SET_BITS[randomness[0] as usize] as u64) << (3 * 16))
-        + ((NUM_SET_BITS[randomness[1] as usize] as u64) << (2 * 16))
-        + ((NUM_SET_BITS[randomness[2] as usize] as u64) << 16)
-        + (NUM_SET_BITS[randomness[3] as usize] as u64);
-    let right = ((NUM_SET_BITS[randomness[4] as usize] as u64) << (3 * 16))
-        + ((NUM_SET_BITS[randomness[5] as usize] as u64) << (2 * 16))
-        + ((NUM_SET_BITS[randomness[6] as usize] as u64) << 16)
-        + (NUM_SET_BITS[randomness[7] as usize] as u64);
+/// Sample a field element whose four 16-bit limbs each follow a centered binomial
+/// distribution with bound `eta`, _i.e._, are differences of the number of set bits in two
+/// `eta`-bit values. The bound must not exceed [`MAX_NOISE_ETA`].
+pub fn sample_short_bfield_element_with_eta(randomness: &[u8; 8], eta: u32) -> BFieldElement {
+    debug_assert!(eta <= MAX_NOISE_ETA);
+    let mask = ((1_u16 << eta) - 1) as u8;
+
+    // Counting the set bits instead of looking them up in a table avoids memory accesses
+    // at secret-dependent addresses.
+    let num_set_bits = |i: usize| u64::from((randomness[i] & mask).count_ones());
+
+    let left = (num_set_bits(0) << (3 * 16))
+        + (num_set_bits(1) << (2 * 16))
+        + (num_set_bits(2) << 16)
+        + num_set_bits(3);
+    let right = (num_set_bits(4) << (3 * 16))
+        + (num_set_bits(5) << (2 * 16))
+        + (num_set_bits(6) << 16)
+        + num_set_bits(7);
     BFieldElement::new(left) - BFieldElement::new(right)
 }
 
 /// The Module is a matrix over the cyclotomic ring (i.e., the ring
-/// of residue classes of polynomials modulo X^64+1). The matrix
+/// of residue classes of polynomials modulo X^D+1). The matrix
 /// contains N cyclotomic ring elements in total.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ModuleElement<const N: usize> {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Zeroize)]
+pub struct ModuleElement<const N: usize, const D: usize = CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES> {
     #[serde(with = "BigArray")]
-    elements: [CyclotomicRingElement; N],
+    elements: [CyclotomicRingElement<D>; N],
 }
 
-impl<const N: usize> ModuleElement<N> {
+impl<const N: usize, const D: usize> ModuleElement<N, D> {
     pub fn sample_short(randomness: &[u8]) -> Self {
-        debug_assert!(randomness.len() >= 8 * 64 * N);
+        Self::sample_short_with_eta(randomness, MAX_NOISE_ETA)
+    }
+
+    /// Like [`sample_short`](Self::sample_short), but with a configurable bound `eta` of
+    /// the limb-wise centered binomial noise. See also [`sample_short_bfield_element`].
+    pub fn sample_short_with_eta(randomness: &[u8], eta: u32) -> Self {
+        debug_assert!(randomness.len() >= 8 * D * N);
         let mut elements = [CyclotomicRingElement::zero(); N];
         for n in 0..N {
-            elements[n] =
-                CyclotomicRingElement::sample_short(&randomness[8 * 64 * n..8 * 64 * (n + 1)]);
+            elements[n] = CyclotomicRingElement::sample_short_with_eta(
+                &randomness[8 * D * n..8 * D * (n + 1)],
+                eta,
+            );
         }
         Self { elements }
     }
 
     pub fn sample_uniform(randomness: &[u8]) -> Self {
-        debug_assert!(randomness.len() >= N * 9 * 64);
+        debug_assert!(randomness.len() >= N * 9 * D);
         ModuleElement {
             elements: (0..N)
                 .map(|i| {
-                    CyclotomicRingElement::sample_uniform(&randomness[i * 9 * 64..(i + 1) * 9 * 64])
+                    CyclotomicRingElement::sample_uniform(&randomness[i * 9 * D..(i + 1) * 9 * D])
                 })
                 .collect_vec()
                 .try_into()
@@ -455,20 +615,28 @@ impl<const N: usize> ModuleElement<N> {
 
     pub fn ntt(&self) -> Self {
         let mut copy = *self;
-        for n in 0..N {
-            coset_ntt_noswap_64(&mut copy.elements[n].coefficients);
-        }
+        copy.ntt_in_place();
         copy
     }
 
     pub fn intt(&self) -> Self {
         let mut copy = *self;
-        for n in 0..N {
-            coset_intt_noswap_64(&mut copy.elements[n].coefficients);
-        }
+        copy.intt_in_place();
         copy
     }
 
+    fn ntt_in_place(&mut self) {
+        for element in &mut self.elements {
+            coset_ntt_noswap(&mut element.coefficients);
+        }
+    }
+
+    fn intt_in_place(&mut self) {
+        for element in &mut self.elements {
+            coset_intt_noswap(&mut element.coefficients);
+        }
+    }
+
     /// Multiply two module elements from a pair of matrix-
     /// multiplication-compatible modules. This method uses
     /// hadamard multiplication for cyclotomic ring elements, which
@@ -489,9 +657,9 @@ impl<const N: usize> ModuleElement<N> {
         const INNER: usize,
         const OUT_N: usize,
     >(
-        lhs: ModuleElement<LHS_N>,
-        rhs: ModuleElement<RHS_N>,
-    ) -> ModuleElement<OUT_N> {
+        lhs: ModuleElement<LHS_N, D>,
+        rhs: ModuleElement<RHS_N, D>,
+    ) -> ModuleElement<OUT_N, D> {
         debug_assert_eq!(LHS_H * INNER, LHS_N);
         debug_assert_eq!(INNER * RHS_W, RHS_N);
         debug_assert_eq!(LHS_H * RHS_W, OUT_N);
@@ -531,9 +699,9 @@ impl<const N: usize> ModuleElement<N> {
         const INNER: usize,
         const OUT_N: usize,
     >(
-        lhs: ModuleElement<LHS_N>,
-        rhs: ModuleElement<RHS_N>,
-    ) -> ModuleElement<OUT_N> {
+        lhs: ModuleElement<LHS_N, D>,
+        rhs: ModuleElement<RHS_N, D>,
+    ) -> ModuleElement<OUT_N, D> {
         debug_assert_eq!(LHS_H * INNER, LHS_N);
         debug_assert_eq!(INNER * RHS_W, RHS_N);
         debug_assert_eq!(LHS_H * RHS_W, OUT_N);
@@ -572,9 +740,9 @@ impl<const N: usize> ModuleElement<N> {
         const INNER: usize,
         const OUT_N: usize,
     >(
-        lhs: ModuleElement<LHS_N>,
-        rhs: ModuleElement<RHS_N>,
-    ) -> ModuleElement<OUT_N> {
+        lhs: ModuleElement<LHS_N, D>,
+        rhs: ModuleElement<RHS_N, D>,
+    ) -> ModuleElement<OUT_N, D> {
         debug_assert_eq!(LHS_H * INNER, LHS_N);
         debug_assert_eq!(INNER * RHS_W, RHS_N);
         debug_assert_eq!(LHS_H * RHS_W, OUT_N);
@@ -589,35 +757,35 @@ impl<const N: usize> ModuleElement<N> {
     }
 }
 
-impl<const N: usize> Add for ModuleElement<N> {
-    type Output = ModuleElement<N>;
+impl<const N: usize, const D: usize> Add for ModuleElement<N, D> {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let elements: [CyclotomicRingElement; N] = (0..N)
+        let elements: [CyclotomicRingElement<D>; N] = (0..N)
             .into_par_iter()
             .map(|i| self.elements[i] + rhs.elements[i])
             .collect::<Vec<_>>()
             .try_into()
             .unwrap();
-        ModuleElement::<N> { elements }
+        Self { elements }
     }
 }
 
-impl<const N: usize> Sub for ModuleElement<N> {
-    type Output = ModuleElement<N>;
+impl<const N: usize, const D: usize> Sub for ModuleElement<N, D> {
+    type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let elements: [CyclotomicRingElement; N] = (0..N)
+        let elements: [CyclotomicRingElement<D>; N] = (0..N)
             .into_par_iter()
             .map(|i| self.elements[i] - rhs.elements[i])
             .collect::<Vec<_>>()
             .try_into()
             .unwrap();
-        ModuleElement::<N> { elements }
+        Self { elements }
     }
 }
 
-impl<const N: usize> Zero for ModuleElement<N> {
+impl<const N: usize, const D: usize> Zero for ModuleElement<N, D> {
     fn zero() -> Self {
         Self {
             elements: [CyclotomicRingElement::zero(); N],
@@ -629,186 +797,22 @@ impl<const N: usize> Zero for ModuleElement<N> {
     }
 }
 
-pub mod kem {
-    use itertools::Itertools;
-    use serde_derive::Deserialize;
-    use serde_derive::Serialize;
-    use sha3::digest::ExtendableOutput;
-    use sha3::digest::Update;
-    use sha3::Digest as Sha3Digest;
-    use sha3::Sha3_256;
-    use sha3::Shake256;
-
-    use super::embed_msg;
-    use super::extract_msg;
-    use super::CyclotomicRingElement;
-    use super::ModuleElement;
-    use super::CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES;
-    use crate::math::b_field_element::BFieldElement;
-
-    #[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
-    pub struct SecretKey {
-        key: [u8; 32],
-        seed: [u8; 32],
-    }
-
-    #[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
-    pub struct PublicKey {
-        seed: [u8; 32],
-        ga: ModuleElement<4>,
-    }
-
-    #[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
-    pub struct Ciphertext {
-        bg: ModuleElement<4>,
-        bga_m: ModuleElement<1>,
-    }
-
-    pub const CIPHERTEXT_SIZE_IN_BFES: usize = CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES * 5;
-
-    impl From<[BFieldElement; CIPHERTEXT_SIZE_IN_BFES]> for Ciphertext {
-        fn from(value: [BFieldElement; CIPHERTEXT_SIZE_IN_BFES]) -> Self {
-            let (bg_slice, bga_m_slice) = value.split_at(4 * CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES);
-
-            let bg_array: [BFieldElement; 4 * CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES] =
-                bg_slice.try_into().unwrap();
-            let bga_m_array: [BFieldElement; CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES] =
-                bga_m_slice.try_into().unwrap();
-
-            let bg_module = ModuleElement {
-                elements: bg_array
-                    .chunks(CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES)
-                    .map(|sl| {
-                        CyclotomicRingElement::from(
-                            std::convert::TryInto::<
-                                [BFieldElement; CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES],
-                            >::try_into(sl)
-                            .unwrap(),
-                        )
-                    })
-                    .collect_vec()
-                    .try_into()
-                    .unwrap(),
-            };
-            let bga_m_module = ModuleElement {
-                elements: [CyclotomicRingElement::from(bga_m_array); 1],
-            };
-
-            Self {
-                bg: bg_module,
-                bga_m: bga_m_module,
-            }
-        }
-    }
-
-    impl From<Ciphertext> for [BFieldElement; CIPHERTEXT_SIZE_IN_BFES] {
-        fn from(value: Ciphertext) -> Self {
-            let bg_slice = value
-                .bg
-                .elements
-                .iter()
-                .flat_map(|e| e.coefficients)
-                .collect_vec();
-            let bga_m_slice = value
-                .bga_m
-                .elements
-                .iter()
-                .flat_map(|e| e.coefficients)
-                .collect_vec();
-            [bg_slice, bga_m_slice].concat().try_into().unwrap()
-        }
-    }
-
-    /// randomness extension
-    pub(super) fn shake256<const NUM_OUT_BYTES: usize>(
-        randomness: impl AsRef<[u8]>,
-    ) -> [u8; NUM_OUT_BYTES] {
-        let mut hasher = Shake256::default();
-        hasher.update(randomness.as_ref());
-
-        let mut result = [0u8; NUM_OUT_BYTES];
-        hasher.finalize_xof_into(&mut result);
-        result
-    }
-
-    fn derive_public_matrix(seed: &[u8; 32]) -> ModuleElement<16> {
-        const NUM_BYTES: usize = 9 * 64 * 16;
-        let randomness = shake256::<NUM_BYTES>(seed);
-        ModuleElement::<16>::sample_uniform(&randomness)
-    }
-
-    fn derive_secret_vectors(seed: &[u8; 32]) -> (ModuleElement<4>, ModuleElement<4>) {
-        const NUM_BYTES: usize = 2 * 4 * 64 * 8;
-        let randomness = shake256::<NUM_BYTES>(seed);
-        let a = ModuleElement::<4>::sample_short(&randomness[0..(NUM_BYTES / 2)]);
-        let b = ModuleElement::<4>::sample_short(&randomness[(NUM_BYTES / 2)..]);
-        (a, b)
-    }
-
-    /// Generate a public-secret key pair for key encapsulation.
-    pub fn keygen(randomness: [u8; 32]) -> (SecretKey, PublicKey) {
-        const OUTPUT_LENGTH: usize = 32;
-        let seed: [u8; OUTPUT_LENGTH] = shake256([randomness.to_vec(), vec![0u8]].concat());
-        let key: [u8; OUTPUT_LENGTH] = shake256([randomness.to_vec(), vec![1u8]].concat());
-
-        let sk = SecretKey { key, seed };
-
-        let pk = derive_public_key(&key, &seed);
-        (sk, pk)
-    }
-
-    fn derive_public_key(key: &[u8; 32], seed: &[u8; 32]) -> PublicKey {
-        let (a, c) = derive_secret_vectors(key);
-        let g = derive_public_matrix(seed);
-        let ga = ModuleElement::<16>::multiply_hadamard::<4, 16, 1, 4, 4, 4>(g, a.ntt()) + c.ntt();
-
-        PublicKey { seed: *seed, ga }
-    }
-
-    /// Generate a ciphertext with the given seed (`payload`) from
-    /// which to derive all randomness.
-    fn generate_ciphertext_derandomized(pk: PublicKey, payload: [u8; 32]) -> Ciphertext {
-        let (b, d) = derive_secret_vectors(&payload);
-        let b_ntt = b.ntt();
-        let d_ntt = d.ntt();
-        let g = derive_public_matrix(&pk.seed);
-        let bg = ModuleElement::<9>::multiply_hadamard::<1, 4, 4, 16, 4, 4>(b_ntt, g) + d_ntt;
-
-        let m = embed_msg(payload);
-        let bga_m = ModuleElement::<3>::multiply_hadamard::<1, 4, 1, 4, 4, 1>(b_ntt, pk.ga)
-            + ModuleElement::<1> { elements: [m] }.ntt();
+// The derive macro cannot infer the types involved in const-generic arrays, so BFieldCodec
+// is implemented manually for ModuleElement.
+impl<const N: usize, const D: usize> BFieldCodec for ModuleElement<N, D> {
+    type Error = <[CyclotomicRingElement<D>; N] as BFieldCodec>::Error;
 
-        Ciphertext { bg, bga_m }
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let elements = *<[CyclotomicRingElement<D>; N]>::decode(sequence)?;
+        Ok(Box::new(Self { elements }))
     }
 
-    /// Encapsulate: generate a ciphertext and an associated shared
-    /// symmetric key.
-    pub fn enc(pk: PublicKey, randomness: [u8; 32]) -> ([u8; 32], Ciphertext) {
-        const OUTPUT_LENGTH: usize = 32;
-        let payload: [u8; OUTPUT_LENGTH] = shake256(randomness);
-        let ciphertext = generate_ciphertext_derandomized(pk, payload);
-        let shared_key: [u8; 32] = Sha3_256::digest(payload).into();
-
-        (shared_key, ciphertext)
+    fn encode(&self) -> Vec<BFieldElement> {
+        self.elements.encode()
     }
 
-    /// Decapsulate: use the secret key to extract the corresponding
-    /// shared symmetric key from a ciphertext (if successful).
-    pub fn dec(sk: SecretKey, ctxt: Ciphertext) -> Option<[u8; 32]> {
-        let (a, _) = derive_secret_vectors(&sk.key);
-        let bga = ModuleElement::<3>::multiply_hadamard::<1, 4, 1, 4, 4, 1>(ctxt.bg, a.ntt());
-        let m = (ctxt.bga_m - bga).intt();
-        let payload = extract_msg(m.elements[0]);
-
-        let pk = derive_public_key(&sk.key, &sk.seed);
-        let regenerated_ciphertext = generate_ciphertext_derandomized(pk, payload);
-
-        if regenerated_ciphertext != ctxt {
-            return None;
-        }
-
-        let shared_key = Sha3_256::digest(payload).into();
-        Some(shared_key)
+    fn static_length() -> Option<usize> {
+        <[CyclotomicRingElement<D>; N]>::static_length()
     }
 }
 
@@ -825,6 +829,7 @@ mod lattice_test {
 
     use super::kem::shake256;
     use super::kem::SecretKey;
+    use super::kem::SharedKey;
     use super::kem::CIPHERTEXT_SIZE_IN_BFES;
     use crate::math::b_field_element::BFieldElement;
     use crate::math::lattice::kem::Ciphertext;
@@ -883,6 +888,77 @@ mod lattice_test {
         assert_eq!(msg, extracted);
     }
 
+    fn fast_mul_agrees_with_schoolbook_mul<const D: usize>() {
+        let a: [BFieldElement; D] = std::array::from_fn(|_| random());
+        let b: [BFieldElement; D] = std::array::from_fn(|_| random());
+
+        let mut c_schoolbook = [BFieldElement::ZERO; D];
+        for i in 0..D {
+            for j in 0..D {
+                if i + j >= D {
+                    c_schoolbook[i + j - D] -= a[i] * b[j];
+                } else {
+                    c_schoolbook[i + j] += a[i] * b[j];
+                }
+            }
+        }
+
+        let c_fast = (CyclotomicRingElement { coefficients: a }
+            * CyclotomicRingElement { coefficients: b })
+        .coefficients;
+
+        assert_eq!(c_fast, c_schoolbook);
+    }
+
+    #[test]
+    fn test_fast_mul_degree_128() {
+        fast_mul_agrees_with_schoolbook_mul::<128>();
+    }
+
+    #[test]
+    fn test_fast_mul_degree_256() {
+        fast_mul_agrees_with_schoolbook_mul::<256>();
+    }
+
+    #[test]
+    fn ntt_and_intt_are_inverses_for_degree_128() {
+        let mut array: [BFieldElement; 128] = std::array::from_fn(|_| random());
+        let original = array;
+        coset_ntt_noswap_128(&mut array);
+        coset_intt_noswap_128(&mut array);
+        assert_eq!(original, array);
+    }
+
+    #[test]
+    fn ntt_and_intt_are_inverses_for_degree_256() {
+        let mut array: [BFieldElement; 256] = std::array::from_fn(|_| random());
+        let original = array;
+        coset_ntt_noswap_256(&mut array);
+        coset_intt_noswap_256(&mut array);
+        assert_eq!(original, array);
+    }
+
+    #[test]
+    fn test_embedding_for_all_supported_degrees() {
+        let msg: [u8; 32] = random();
+        assert_eq!(msg, extract_message::<128>(&embed_message::<128>(msg)));
+        assert_eq!(msg, extract_message::<256>(&embed_message::<256>(msg)));
+    }
+
+    #[test]
+    fn branch_free_message_extraction_rounds_every_limb_correctly() {
+        for chunk in 0..1 << 16 {
+            let mut embedding = CyclotomicRingElement::<256>::zero();
+            embedding.coefficients[0] = BFieldElement::new(chunk);
+            let expected_bit = (1 << 14..=3 << 14).contains(&chunk) as u8;
+            assert_eq!(
+                expected_bit,
+                extract_message(&embedding)[0],
+                "chunk: {chunk}"
+            );
+        }
+    }
+
     #[test]
     fn test_module_distributivity() {
         let mut rng = thread_rng();
@@ -935,7 +1011,7 @@ mod lattice_test {
         // correctness
         let (sk, pk) = kem::keygen(key_randomness);
         let (alice_key, ctxt) = kem::enc(pk, ctxt_randomness);
-        if let Some(bob_key) = kem::dec(sk, ctxt) {
+        if let Some(bob_key) = kem::dec(&sk, ctxt) {
             assert_eq!(alice_key, bob_key);
         } else {
             panic!()
@@ -944,7 +1020,7 @@ mod lattice_test {
         // sanity
         rng.fill_bytes(&mut key_randomness);
         let (other_sk, _) = kem::keygen(key_randomness);
-        assert!(kem::dec(other_sk, ctxt).is_none());
+        assert!(kem::dec(&other_sk, ctxt).is_none());
     }
 
     #[test]
@@ -995,7 +1071,7 @@ mod lattice_test {
         assert_eq!(ctxt, ctxt_again);
 
         let alice_key_as_json: String = serde_json::to_string(&alice_key).unwrap();
-        let alice_key_again = serde_json::from_str::<[u8; 32]>(&alice_key_as_json).unwrap();
+        let alice_key_again = serde_json::from_str::<SharedKey>(&alice_key_as_json).unwrap();
         assert_eq!(alice_key, alice_key_again);
     }
 }
@@ -0,0 +1,1473 @@
+//! Key encapsulation based on the hardness of Module-LWE over the ring
+//! `Fp[X] / (X^DEGREE + 1)`.
+//!
+//! The key encapsulation mechanism is generic over a [`ParameterSet`], which fixes the
+//! module rank, the ring degree, and the noise distribution. The functions without explicit
+//! parameters, like [`keygen`], use the default preset [`Rank4Degree64`].
+//!
+//! ## Presets
+//!
+//! | preset             | rank | degree | noise η | public key | ciphertext | estimate |
+//! |:-------------------|-----:|-------:|--------:|-----------:|-----------:|---------:|
+//! | [`Rank4Degree64`]  |    4 |     64 |       8 |        256 |        320 |      212 |
+//! | [`Rank2Degree128`] |    2 |    128 |       8 |        256 |        384 |      212 |
+//! | [`Rank2Degree256`] |    2 |    256 |       8 |        512 |        768 |      478 |
+//!
+//! Sizes are in number of [`BFieldElement`]s, not counting the public key's 32-byte seed.
+//!
+//! The security estimates are heuristic core-SVP estimates, in bits, for the classical
+//! primal attack. Because the noise is small only in every 16-bit limb of a field element,
+//! the estimates treat each limb of the secret and error vectors as a separate short unknown,
+//! giving an instance of dimension `8 · rank · degree` with `rank · degree` equations modulo
+//! `p`. These parameters have not been subjected to dedicated cryptanalysis.
+//!
+//! ## Encodings
+//!
+//! Public keys can be encoded as [hex](PublicKey::to_hex). They are too long for bech32m,
+//! but their [fingerprints](PublicKey::fingerprint) can be encoded as
+//! [bech32m](PublicKey::fingerprint_bech32m), for example to compare keys out of band.
+
+use std::error::Error;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::primitives::decode::CheckedHrpstringError;
+use bech32::primitives::decode::PaddingError;
+use bech32::Bech32m;
+use bech32::Hrp;
+use itertools::Itertools;
+use num_traits::Zero;
+use serde::de::DeserializeOwned;
+use serde::Serialize as SerializeTrait;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+use sha3::digest::ExtendableOutput;
+use sha3::digest::Update;
+use sha3::digest::XofReader;
+use sha3::Digest as Sha3Digest;
+use sha3::Sha3_256;
+use sha3::Shake256;
+use thiserror::Error;
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+use zeroize::Zeroizing;
+
+use super::embed_message_into;
+use super::extract_message;
+use super::sample_short_bfield_element_with_eta;
+use super::CyclotomicRingElement;
+use super::ModuleElement;
+use super::CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES;
+use super::MAX_NOISE_ETA;
+use crate::error::ParseBFieldElementError;
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::bfield_codec::BFieldCodecError;
+
+/// A set of parameters for the key encapsulation mechanism. Implemented by [`Parameters`]
+/// only; see there for the presets.
+pub trait ParameterSet:
+    private::Sealed + Debug + Copy + Clone + Default + PartialEq + Eq + Hash + Send + Sync + 'static
+{
+    /// The number of ring elements in the secret and error vectors.
+    const RANK: usize;
+
+    /// The degree of the cyclotomic ring `Fp[X] / (X^DEGREE + 1)`.
+    const DEGREE: usize;
+
+    /// The bound of the noise, which follows a centered binomial distribution in every
+    /// 16-bit limb. See also [`sample_short_bfield_element_with_eta`].
+    ///
+    /// [`sample_short_bfield_element_with_eta`]: super::sample_short_bfield_element_with_eta
+    const NOISE_ETA: u32;
+
+    /// The number of [`BFieldElement`]s in the public key, not counting the seed.
+    const PUBLIC_KEY_SIZE_IN_BFES: usize = Self::RANK * Self::DEGREE;
+
+    /// The number of [`BFieldElement`]s in a [`Ciphertext`].
+    const CIPHERTEXT_SIZE_IN_BFES: usize = (Self::RANK + 1) * Self::DEGREE;
+}
+
+mod private {
+    use super::*;
+
+    pub trait Sealed: Sized {
+        /// The module elements of rank `RANK` over the ring of degree `DEGREE`.
+        type Vector: Debug
+            + Copy
+            + Eq
+            + Send
+            + Sync
+            + SerializeTrait
+            + DeserializeOwned
+            + BFieldCodec;
+
+        /// One ring element of degree `DEGREE`, as a module element of rank 1.
+        type Scalar: Debug
+            + Copy
+            + Eq
+            + Send
+            + Sync
+            + SerializeTrait
+            + DeserializeOwned
+            + BFieldCodec;
+
+        fn derive_public_key(key: &[u8; 32], seed: &[u8; 32]) -> PublicKey<Self>
+        where
+            Self: ParameterSet;
+
+        /// Generate a ciphertext with the given seed (`payload`) from
+        /// which to derive all randomness.
+        fn generate_ciphertext_derandomized(
+            pk: &PublicKey<Self>,
+            payload: &[u8; 32],
+        ) -> Ciphertext<Self>
+        where
+            Self: ParameterSet;
+
+        /// Extract the payload from the ciphertext, without checking the ciphertext's
+        /// validity.
+        fn extract_payload(key: &[u8; 32], ctxt: &Ciphertext<Self>) -> Zeroizing<[u8; 32]>
+        where
+            Self: ParameterSet;
+    }
+}
+
+/// The parameters of the key encapsulation mechanism:
+///  - `RANK` is the number of ring elements in the secret and error vectors;
+///  - `DEGREE` is the degree of the cyclotomic ring, one of 64, 128, or 256;
+///  - `NOISE_ETA` is the bound of the noise in every 16-bit limb, at most 8.
+///
+/// Parameters violating these constraints fail to compile when used.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Parameters<const RANK: usize, const DEGREE: usize, const NOISE_ETA: u32>;
+
+/// The default parameter set. See the [module-level documentation](self) for a comparison
+/// of all presets.
+pub type Rank4Degree64 = Parameters<4, 64, 8>;
+
+/// A parameter set with the same module dimension as [`Rank4Degree64`] but a smaller public
+/// matrix.
+pub type Rank2Degree128 = Parameters<2, 128, 8>;
+
+/// A parameter set with twice the module dimension of [`Rank4Degree64`].
+pub type Rank2Degree256 = Parameters<2, 256, 8>;
+
+impl<const RANK: usize, const DEGREE: usize, const NOISE_ETA: u32>
+    Parameters<RANK, DEGREE, NOISE_ETA>
+{
+    const ARE_VALID: () = {
+        assert!(RANK > 0, "the rank must be positive");
+        assert!(
+            DEGREE == 64 || DEGREE == 128 || DEGREE == 256,
+            "the ring degree must be 64, 128, or 256"
+        );
+        assert!(
+            0 < NOISE_ETA && NOISE_ETA <= MAX_NOISE_ETA,
+            "the noise bound must be positive and at most MAX_NOISE_ETA"
+        );
+    };
+
+    /// The public matrix, as a list of rows, in NTT domain.
+    fn derive_public_matrix(seed: &[u8; 32]) -> [ModuleElement<RANK, DEGREE>; RANK] {
+        let num_bytes_per_row = RANK * 9 * DEGREE;
+        let randomness = shake256_vec(seed, RANK * num_bytes_per_row);
+        let rows = randomness
+            .chunks_exact(num_bytes_per_row)
+            .map(ModuleElement::sample_uniform)
+            .collect_vec();
+
+        rows.try_into().unwrap()
+    }
+
+    fn derive_secret_vectors(
+        seed: &[u8; 32],
+    ) -> (SecretVector<RANK, DEGREE>, SecretVector<RANK, DEGREE>) {
+        let num_bytes = 2 * RANK * DEGREE * 8;
+        let randomness = Zeroizing::new(shake256_vec(seed, num_bytes));
+        let (a_randomness, b_randomness) = randomness.split_at(num_bytes / 2);
+        let a = SecretVector::sample_short_with_eta(a_randomness, NOISE_ETA);
+        let b = SecretVector::sample_short_with_eta(b_randomness, NOISE_ETA);
+        (a, b)
+    }
+
+    /// The product `matrix · vector`. All arguments are in NTT domain.
+    fn matrix_vector_product(
+        matrix: &[ModuleElement<RANK, DEGREE>; RANK],
+        vector: &ModuleElement<RANK, DEGREE>,
+    ) -> ModuleElement<RANK, DEGREE> {
+        let mut product = ModuleElement::zero();
+        for (element, row) in product.elements.iter_mut().zip(matrix) {
+            Self::add_inner_product(element, row, vector);
+        }
+        product
+    }
+
+    /// The product `vector · matrix`. All arguments are in NTT domain.
+    fn vector_matrix_product(
+        vector: &ModuleElement<RANK, DEGREE>,
+        matrix: &[ModuleElement<RANK, DEGREE>; RANK],
+    ) -> ModuleElement<RANK, DEGREE> {
+        let mut product = ModuleElement::zero();
+        for (row, vector_element) in matrix.iter().zip(&vector.elements) {
+            for (element, matrix_element) in product.elements.iter_mut().zip(&row.elements) {
+                element.add_hadamard_product(vector_element, matrix_element);
+            }
+        }
+        product
+    }
+
+    /// Add the inner product of two vectors in NTT domain to `accumulator`.
+    fn add_inner_product(
+        accumulator: &mut CyclotomicRingElement<DEGREE>,
+        lhs: &ModuleElement<RANK, DEGREE>,
+        rhs: &ModuleElement<RANK, DEGREE>,
+    ) {
+        for (lhs_element, rhs_element) in lhs.elements.iter().zip(&rhs.elements) {
+            accumulator.add_hadamard_product(lhs_element, rhs_element);
+        }
+    }
+
+    /// Add `summand` to `vector`, element by element.
+    fn add_assign(vector: &mut ModuleElement<RANK, DEGREE>, summand: &ModuleElement<RANK, DEGREE>) {
+        for (element, summand_element) in vector.elements.iter_mut().zip(&summand.elements) {
+            *element += summand_element;
+        }
+    }
+}
+
+impl<const RANK: usize, const DEGREE: usize, const NOISE_ETA: u32> private::Sealed
+    for Parameters<RANK, DEGREE, NOISE_ETA>
+{
+    type Vector = ModuleElement<RANK, DEGREE>;
+    type Scalar = ModuleElement<1, DEGREE>;
+
+    fn derive_public_key(key: &[u8; 32], seed: &[u8; 32]) -> PublicKey<Self> {
+        let () = Self::ARE_VALID;
+
+        let (mut a, mut c) = Self::derive_secret_vectors(key);
+        a.ntt();
+        c.ntt();
+        let g = Self::derive_public_matrix(seed);
+        let mut ga = Self::matrix_vector_product(&g, &a);
+        Self::add_assign(&mut ga, &c);
+
+        PublicKey { seed: *seed, ga }
+    }
+
+    fn generate_ciphertext_derandomized(
+        pk: &PublicKey<Self>,
+        payload: &[u8; 32],
+    ) -> Ciphertext<Self> {
+        let (mut b, mut d) = Self::derive_secret_vectors(payload);
+        b.ntt();
+        d.ntt();
+        let g = Self::derive_public_matrix(&pk.seed);
+        let mut bg = Self::vector_matrix_product(&b, &g);
+        Self::add_assign(&mut bg, &d);
+
+        let mut m = SecretVector::<1, DEGREE>::zero();
+        embed_message_into(payload, &mut m.elements[0]);
+        m.ntt();
+        let mut bga_m = ModuleElement::zero();
+        Self::add_inner_product(&mut bga_m.elements[0], &b, &pk.ga);
+        bga_m.elements[0] += &m.elements[0];
+
+        Ciphertext { bg, bga_m }
+    }
+
+    fn extract_payload(key: &[u8; 32], ctxt: &Ciphertext<Self>) -> Zeroizing<[u8; 32]> {
+        let (mut a, _) = Self::derive_secret_vectors(key);
+        a.ntt();
+        let mut bga = SecretVector::<1, DEGREE>::zero();
+        Self::add_inner_product(&mut bga.elements[0], &ctxt.bg, &a);
+        let mut m = SecretVector::<1, DEGREE>::zero();
+        m.elements[0] += &ctxt.bga_m.elements[0];
+        m.elements[0] -= &bga.elements[0];
+        m.intt();
+        Zeroizing::new(extract_message(&m.elements[0]))
+    }
+}
+
+/// A module element that holds secrets, like the short secret vectors or the embedded
+/// payload. Unlike [`ModuleElement`], it is not `Copy`: it lives on the heap, is only
+/// modified in place, and is wiped from memory when dropped.
+struct SecretVector<const N: usize, const D: usize>(Box<ModuleElement<N, D>>);
+
+impl<const N: usize, const D: usize> SecretVector<N, D> {
+    fn zero() -> Self {
+        Self(Box::new(ModuleElement::zero()))
+    }
+
+    /// Like [`ModuleElement::sample_short_with_eta`], but without intermediate copies.
+    fn sample_short_with_eta(randomness: &[u8], eta: u32) -> Self {
+        let mut vector = Self::zero();
+        let coefficients = vector
+            .0
+            .elements
+            .iter_mut()
+            .flat_map(|e| &mut e.coefficients);
+        for (coefficient, chunk) in coefficients.zip(randomness.chunks_exact(8)) {
+            *coefficient = sample_short_bfield_element_with_eta(chunk.try_into().unwrap(), eta);
+        }
+        vector
+    }
+
+    fn ntt(&mut self) {
+        self.0.ntt_in_place();
+    }
+
+    fn intt(&mut self) {
+        self.0.intt_in_place();
+    }
+}
+
+impl<const N: usize, const D: usize> Deref for SecretVector<N, D> {
+    type Target = ModuleElement<N, D>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize, const D: usize> DerefMut for SecretVector<N, D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const N: usize, const D: usize> Drop for SecretVector<N, D> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<const RANK: usize, const DEGREE: usize, const NOISE_ETA: u32> ParameterSet
+    for Parameters<RANK, DEGREE, NOISE_ETA>
+{
+    const RANK: usize = RANK;
+    const DEGREE: usize = DEGREE;
+    const NOISE_ETA: u32 = NOISE_ETA;
+}
+
+/// Wiped from memory when dropped.
+#[derive(Clone, Debug, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+#[serde(bound = "")]
+pub struct SecretKey<P: ParameterSet = Rank4Degree64> {
+    key: [u8; 32],
+    seed: [u8; 32],
+
+    #[serde(skip)]
+    #[zeroize(skip)]
+    parameters: PhantomData<P>,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PublicKey<P: ParameterSet = Rank4Degree64> {
+    seed: [u8; 32],
+    ga: P::Vector,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Ciphertext<P: ParameterSet = Rank4Degree64> {
+    bg: P::Vector,
+    bga_m: P::Scalar,
+}
+
+/// A symmetric key shared through [encapsulation](enc). Wiped from memory when dropped.
+#[derive(Clone, Debug, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct SharedKey([u8; 32]);
+
+impl SharedKey {
+    fn from_payload(payload: &[u8; 32]) -> Self {
+        Self(Sha3_256::digest(payload).into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+// Comparisons of secrets take time independent of the position of the first difference.
+impl PartialEq for SharedKey {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedKey {}
+
+impl<P: ParameterSet> PartialEq for SecretKey<P> {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&*self.to_bytes(), &*other.to_bytes())
+    }
+}
+
+impl<P: ParameterSet> Eq for SecretKey<P> {}
+
+pub const CIPHERTEXT_SIZE_IN_BFES: usize = Rank4Degree64::CIPHERTEXT_SIZE_IN_BFES;
+
+impl From<[BFieldElement; CIPHERTEXT_SIZE_IN_BFES]> for Ciphertext {
+    fn from(value: [BFieldElement; CIPHERTEXT_SIZE_IN_BFES]) -> Self {
+        let (bg_slice, bga_m_slice) = value.split_at(4 * CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES);
+
+        let bg_array: [BFieldElement; 4 * CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES] =
+            bg_slice.try_into().unwrap();
+        let bga_m_array: [BFieldElement; CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES] =
+            bga_m_slice.try_into().unwrap();
+
+        let bg_module = ModuleElement {
+            elements: bg_array
+                .chunks(CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES)
+                .map(|sl| {
+                    CyclotomicRingElement::from(
+                        std::convert::TryInto::<
+                            [BFieldElement; CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES],
+                        >::try_into(sl)
+                        .unwrap(),
+                    )
+                })
+                .collect_vec()
+                .try_into()
+                .unwrap(),
+        };
+        let bga_m_module = ModuleElement {
+            elements: [CyclotomicRingElement::from(bga_m_array); 1],
+        };
+
+        Self {
+            bg: bg_module,
+            bga_m: bga_m_module,
+        }
+    }
+}
+
+impl From<Ciphertext> for [BFieldElement; CIPHERTEXT_SIZE_IN_BFES] {
+    fn from(value: Ciphertext) -> Self {
+        let bg_slice = value
+            .bg
+            .elements
+            .iter()
+            .flat_map(|e| e.coefficients)
+            .collect_vec();
+        let bga_m_slice = value
+            .bga_m
+            .elements
+            .iter()
+            .flat_map(|e| e.coefficients)
+            .collect_vec();
+        [bg_slice, bga_m_slice].concat().try_into().unwrap()
+    }
+}
+
+/// The prefix of the human-readable part of [bech32m-encoded public key
+/// fingerprints](PublicKey::fingerprint_bech32m). The full human-readable part also
+/// identifies the [`ParameterSet`].
+pub const PUBLIC_KEY_FINGERPRINT_HRP_PREFIX: &str = "kemfp";
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum KemDecodingError {
+    #[error("expected {expected} bytes but got {actual}")]
+    InvalidNumBytes { expected: usize, actual: usize },
+
+    #[error("invalid field element")]
+    InvalidFieldElement(#[from] ParseBFieldElementError),
+
+    #[error("hex decoding error")]
+    HexDecode(#[from] hex::FromHexError),
+
+    #[error("bech32m decoding error")]
+    Bech32mDecode(#[from] CheckedHrpstringError),
+
+    #[error("non-canonical bech32m padding")]
+    Bech32mPadding(#[from] PaddingError),
+
+    #[error("expected human-readable part “{expected}” but got “{actual}”")]
+    InvalidHumanReadablePart { expected: String, actual: String },
+}
+
+/// The canonical byte encoding of a sequence of field elements: the concatenation of their
+/// little-endian byte representations.
+fn bfes_to_bytes(bfes: Vec<BFieldElement>) -> impl Iterator<Item = u8> {
+    bfes.into_iter()
+        .flat_map(<[u8; BFieldElement::BYTES]>::from)
+}
+
+/// The inverse of [`bfes_to_bytes`]. Rejects non-canonical field elements.
+fn bfes_from_bytes(bytes: &[u8]) -> Result<Vec<BFieldElement>, KemDecodingError> {
+    let bfes = bytes
+        .chunks(BFieldElement::BYTES)
+        .map(BFieldElement::try_from)
+        .try_collect()?;
+    Ok(bfes)
+}
+
+fn check_num_bytes(bytes: &[u8], expected: usize) -> Result<(), KemDecodingError> {
+    if bytes.len() != expected {
+        let actual = bytes.len();
+        return Err(KemDecodingError::InvalidNumBytes { expected, actual });
+    }
+    Ok(())
+}
+
+impl<P: ParameterSet> SecretKey<P> {
+    /// The number of bytes in the [canonical byte encoding](Self::to_bytes).
+    pub const BYTES: usize = 64;
+
+    /// The canonical byte encoding: the key followed by the seed.
+    pub fn to_bytes(&self) -> Zeroizing<[u8; 64]> {
+        let mut bytes = Zeroizing::new([0; 64]);
+        bytes[..32].copy_from_slice(&self.key);
+        bytes[32..].copy_from_slice(&self.seed);
+        bytes
+    }
+
+    /// Decode the [canonical byte encoding](Self::to_bytes).
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, KemDecodingError> {
+        check_num_bytes(bytes, Self::BYTES)?;
+        let (key, seed) = bytes.split_at(32);
+
+        Ok(Self {
+            key: key.try_into().unwrap(),
+            seed: seed.try_into().unwrap(),
+            parameters: PhantomData,
+        })
+    }
+}
+
+impl<P: ParameterSet> PublicKey<P> {
+    /// The number of bytes in the [canonical byte encoding](Self::to_bytes).
+    pub const BYTES: usize = 32 + P::PUBLIC_KEY_SIZE_IN_BFES * BFieldElement::BYTES;
+
+    /// The canonical byte encoding: the seed followed by the little-endian bytes of all
+    /// coefficients. Its length is [`Self::BYTES`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.seed
+            .into_iter()
+            .chain(bfes_to_bytes(self.ga.encode()))
+            .collect()
+    }
+
+    /// Decode the [canonical byte encoding](Self::to_bytes). Rejects encodings of
+    /// non-canonical field elements.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, KemDecodingError> {
+        check_num_bytes(bytes, Self::BYTES)?;
+        let (seed, ga) = bytes.split_at(32);
+        let ga = *P::Vector::decode(&bfes_from_bytes(ga)?).unwrap();
+
+        Ok(Self {
+            seed: seed.try_into().unwrap(),
+            ga,
+        })
+    }
+
+    /// Encode the [canonical byte encoding](Self::to_bytes) as lowercase hex.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// Decode a hex string produced by [`Self::to_hex`]. Must not include a leading “0x”.
+    pub fn try_from_hex(data: impl AsRef<[u8]>) -> Result<Self, KemDecodingError> {
+        Self::try_from_bytes(&hex::decode(data)?)
+    }
+
+    /// The SHA3-256 digest of the [canonical byte encoding](Self::to_bytes).
+    pub fn fingerprint(&self) -> [u8; 32] {
+        Sha3_256::digest(self.to_bytes()).into()
+    }
+
+    /// Encode the [fingerprint](Self::fingerprint) as a bech32m string. The human-readable
+    /// part starts with [`PUBLIC_KEY_FINGERPRINT_HRP_PREFIX`] and identifies the
+    /// [`ParameterSet`].
+    pub fn fingerprint_bech32m(&self) -> String {
+        bech32::encode::<Bech32m>(Self::fingerprint_hrp(), &self.fingerprint()).unwrap()
+    }
+
+    /// Whether a bech32m string produced by [`Self::fingerprint_bech32m`] is the fingerprint
+    /// of this key. Fails if the string is not a valid fingerprint for this
+    /// [`ParameterSet`].
+    pub fn matches_fingerprint_bech32m(&self, string: &str) -> Result<bool, KemDecodingError> {
+        let checked = CheckedHrpstring::new::<Bech32m>(string)?;
+        let expected = Self::fingerprint_hrp();
+        if checked.hrp() != expected {
+            return Err(KemDecodingError::InvalidHumanReadablePart {
+                expected: expected.to_string(),
+                actual: checked.hrp().to_string(),
+            });
+        }
+        checked.validate_segwit_padding()?;
+
+        let fingerprint = checked.byte_iter().collect_vec();
+        check_num_bytes(&fingerprint, 32)?;
+        Ok(fingerprint == self.fingerprint())
+    }
+
+    fn fingerprint_hrp() -> Hrp {
+        let hrp = format!(
+            "{PUBLIC_KEY_FINGERPRINT_HRP_PREFIX}-{}-{}-{}",
+            P::RANK,
+            P::DEGREE,
+            P::NOISE_ETA
+        );
+        Hrp::parse(&hrp).unwrap()
+    }
+}
+
+impl<P: ParameterSet> Ciphertext<P> {
+    /// The number of bytes in the [canonical byte encoding](Self::to_bytes).
+    pub const BYTES: usize = P::CIPHERTEXT_SIZE_IN_BFES * BFieldElement::BYTES;
+
+    /// The canonical byte encoding: the little-endian bytes of all coefficients. Its length
+    /// is [`Self::BYTES`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let bfes = [self.bg.encode(), self.bga_m.encode()].concat();
+        bfes_to_bytes(bfes).collect()
+    }
+
+    /// Decode the [canonical byte encoding](Self::to_bytes). Rejects encodings of
+    /// non-canonical field elements.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, KemDecodingError> {
+        check_num_bytes(bytes, Self::BYTES)?;
+        let bfes = bfes_from_bytes(bytes)?;
+        let (bg, bga_m) = bfes.split_at(P::PUBLIC_KEY_SIZE_IN_BFES);
+
+        Ok(Self {
+            bg: *P::Vector::decode(bg).unwrap(),
+            bga_m: *P::Scalar::decode(bga_m).unwrap(),
+        })
+    }
+}
+
+// The derive macro cannot handle the phantom parameter set, so BFieldCodec is implemented
+// manually for the key types and the ciphertext. The encodings agree with what the derive
+// macro would produce for the fields.
+impl<P: ParameterSet> BFieldCodec for SecretKey<P> {
+    type Error = BFieldCodecError;
+
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let (key, seed) = *<([u8; 32], [u8; 32])>::decode(sequence)?;
+        let parameters = PhantomData;
+        Ok(Box::new(Self {
+            key,
+            seed,
+            parameters,
+        }))
+    }
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        (self.key, self.seed).encode()
+    }
+
+    fn static_length() -> Option<usize> {
+        <([u8; 32], [u8; 32])>::static_length()
+    }
+}
+
+impl<P: ParameterSet> BFieldCodec for PublicKey<P> {
+    type Error = BFieldCodecError;
+
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let (seed, ga) = *<([u8; 32], P::Vector)>::decode(sequence)?;
+        Ok(Box::new(Self { seed, ga }))
+    }
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        (self.seed, self.ga).encode()
+    }
+
+    fn static_length() -> Option<usize> {
+        <([u8; 32], P::Vector)>::static_length()
+    }
+}
+
+impl<P: ParameterSet> BFieldCodec for Ciphertext<P> {
+    type Error = BFieldCodecError;
+
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let (bg, bga_m) = *<(P::Vector, P::Scalar)>::decode(sequence)?;
+        Ok(Box::new(Self { bg, bga_m }))
+    }
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        (self.bg, self.bga_m).encode()
+    }
+
+    fn static_length() -> Option<usize> {
+        <(P::Vector, P::Scalar)>::static_length()
+    }
+}
+
+/// randomness extension
+pub(super) fn shake256<const NUM_OUT_BYTES: usize>(
+    randomness: impl AsRef<[u8]>,
+) -> [u8; NUM_OUT_BYTES] {
+    let mut hasher = Shake256::default();
+    hasher.update(randomness.as_ref());
+
+    let mut result = [0u8; NUM_OUT_BYTES];
+    hasher.finalize_xof_into(&mut result);
+    result
+}
+
+/// randomness extension, for output lengths not known at compile time
+fn shake256_vec(randomness: impl AsRef<[u8]>, num_out_bytes: usize) -> Vec<u8> {
+    let mut hasher = Shake256::default();
+    hasher.update(randomness.as_ref());
+
+    let mut result = vec![0u8; num_out_bytes];
+    hasher.finalize_xof_into(&mut result);
+    result
+}
+
+/// Generate a public-secret key pair for key encapsulation, using the default
+/// [`ParameterSet`]. See also [`keygen_with_parameters`].
+pub fn keygen(randomness: [u8; 32]) -> (SecretKey, PublicKey) {
+    keygen_with_parameters(randomness)
+}
+
+/// Generate a public-secret key pair for key encapsulation with the given [`ParameterSet`].
+/// All other functions infer the parameter set from their arguments.
+pub fn keygen_with_parameters<P: ParameterSet>(
+    randomness: [u8; 32],
+) -> (SecretKey<P>, PublicKey<P>) {
+    let seed_preimage = Zeroizing::new([&randomness[..], &[0]].concat());
+    let key_preimage = Zeroizing::new([&randomness[..], &[1]].concat());
+    let sk = SecretKey {
+        key: shake256(&*key_preimage),
+        seed: shake256(&*seed_preimage),
+        parameters: PhantomData,
+    };
+
+    let pk = P::derive_public_key(&sk.key, &sk.seed);
+    (sk, pk)
+}
+
+/// Encapsulate: generate a ciphertext and an associated shared
+/// symmetric key.
+pub fn enc<P: ParameterSet>(pk: PublicKey<P>, randomness: [u8; 32]) -> (SharedKey, Ciphertext<P>) {
+    let payload = Zeroizing::new(shake256(randomness));
+    let ciphertext = P::generate_ciphertext_derandomized(&pk, &payload);
+
+    (SharedKey::from_payload(&payload), ciphertext)
+}
+
+/// Decapsulate: use the secret key to extract the corresponding
+/// shared symmetric key from a ciphertext (if successful).
+///
+/// Whether decapsulation succeeds is not secret. Decapsulation avoids explicit branches on
+/// the secret key and the shared key, and compares ciphertexts in constant time. It does not
+/// run in constant time, though: [`BFieldElement`] addition, for example, branches on
+/// whether it needs to reduce.
+pub fn dec<P: ParameterSet>(sk: &SecretKey<P>, ctxt: Ciphertext<P>) -> Option<SharedKey> {
+    let payload = P::extract_payload(&sk.key, &ctxt);
+
+    let pk = P::derive_public_key(&sk.key, &sk.seed);
+    let regenerated_ciphertext = P::generate_ciphertext_derandomized(&pk, &payload);
+
+    // Where the ciphertexts differ depends on the secret key, so the comparison must not
+    // stop early.
+    if !constant_time_eq(&regenerated_ciphertext.to_bytes(), &ctxt.to_bytes()) {
+        return None;
+    }
+
+    Some(SharedKey::from_payload(&payload))
+}
+
+/// The version of the format of [`SealedMessage`]s produced by [`seal`].
+pub const SEALED_MESSAGE_VERSION: u8 = 1;
+
+const SEAL_DOMAIN_SEPARATOR: &[u8] = b"twenty-first lattice kem seal";
+
+/// A message encrypted to a [`PublicKey`] with [`seal`].
+///
+/// The message is encrypted with a stream cipher and authenticated with a MAC, both of
+/// which are keyed from a shared key that is encapsulated under the recipient's public
+/// key.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SealedMessage<P: ParameterSet = Rank4Degree64> {
+    version: u8,
+    ciphertext: Ciphertext<P>,
+    payload: Vec<u8>,
+    tag: [u8; 32],
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SealedMessageError {
+    #[error("unsupported version {0}; expected {SEALED_MESSAGE_VERSION}")]
+    UnsupportedVersion(u8),
+
+    #[error("decapsulation failed")]
+    DecapsulationFailure,
+
+    #[error("invalid authentication tag")]
+    InvalidTag,
+
+    #[error("payload is not a sequence of field elements")]
+    InvalidFieldElement(#[from] ParseBFieldElementError),
+
+    #[error("payload cannot be decoded")]
+    Decode(#[source] Box<dyn Error + Send + Sync>),
+}
+
+impl<P: ParameterSet> SealedMessage<P> {
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+/// The key for the stream cipher and the key for the MAC.
+fn derive_seal_keys(
+    shared_key: &SharedKey,
+    version: u8,
+) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+    let preimage = [shared_key.as_bytes(), SEAL_DOMAIN_SEPARATOR, &[version]].concat();
+    let preimage = Zeroizing::new(preimage);
+    let keys = Zeroizing::new(shake256::<64>(&*preimage));
+    let (stream_key, mac_key) = keys.split_at(32);
+
+    (
+        Zeroizing::new(stream_key.try_into().unwrap()),
+        Zeroizing::new(mac_key.try_into().unwrap()),
+    )
+}
+
+/// XOR the data with the key stream. Encryption and decryption are the same operation.
+fn apply_key_stream(stream_key: &[u8; 32], data: &[u8]) -> Vec<u8> {
+    let mut hasher = Shake256::default();
+    hasher.update(stream_key);
+    let mut key_stream = Zeroizing::new(vec![0; data.len()]);
+    hasher.finalize_xof().read(&mut key_stream);
+
+    data.iter()
+        .zip_eq(key_stream.iter())
+        .map(|(d, k)| d ^ k)
+        .collect()
+}
+
+/// Authenticate everything that goes into the sealed message, including the version and
+/// the encapsulation. Since SHA3 is not susceptible to length extension, prefixing the
+/// key gives a secure MAC.
+fn authentication_tag<P: ParameterSet>(
+    mac_key: &[u8; 32],
+    version: u8,
+    ciphertext: Ciphertext<P>,
+    payload: &[u8],
+) -> [u8; 32] {
+    let payload_len = u64::try_from(payload.len()).unwrap().to_le_bytes();
+
+    let mut mac_input = Zeroizing::new(mac_key.to_vec());
+    mac_input.push(version);
+    mac_input.extend(ciphertext.to_bytes());
+    mac_input.extend(payload_len);
+    mac_input.extend(payload);
+
+    Sha3_256::digest(&*mac_input).into()
+}
+
+/// Compare two byte strings of equal length in time independent of the position of the
+/// first difference.
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    let difference = lhs.iter().zip_eq(rhs).fold(0, |acc, (l, r)| acc | (l ^ r));
+    std::hint::black_box(difference) == 0
+}
+
+/// Encrypt a message of arbitrary length to the holder of the secret key corresponding
+/// to the given public key. See also [`open`].
+pub fn seal<P: ParameterSet>(
+    pk: PublicKey<P>,
+    plaintext: &[u8],
+    randomness: [u8; 32],
+) -> SealedMessage<P> {
+    let version = SEALED_MESSAGE_VERSION;
+    let (shared_key, ciphertext) = enc(pk, randomness);
+    let (stream_key, mac_key) = derive_seal_keys(&shared_key, version);
+    let payload = apply_key_stream(&stream_key, plaintext);
+    let tag = authentication_tag(&mac_key, version, ciphertext, &payload);
+
+    SealedMessage {
+        version,
+        ciphertext,
+        payload,
+        tag,
+    }
+}
+
+/// Decrypt a message produced by [`seal`]. Fails if the sealed message was not
+/// produced for the given secret key or if it was tampered with.
+pub fn open<P: ParameterSet>(
+    sk: &SecretKey<P>,
+    sealed_message: &SealedMessage<P>,
+) -> Result<Vec<u8>, SealedMessageError> {
+    let SealedMessage {
+        version,
+        ciphertext,
+        ref payload,
+        tag,
+    } = *sealed_message;
+    if version != SEALED_MESSAGE_VERSION {
+        return Err(SealedMessageError::UnsupportedVersion(version));
+    }
+
+    let shared_key = dec(sk, ciphertext).ok_or(SealedMessageError::DecapsulationFailure)?;
+    let (stream_key, mac_key) = derive_seal_keys(&shared_key, version);
+    let expected_tag = authentication_tag(&mac_key, version, ciphertext, payload);
+    if !constant_time_eq(&tag, &expected_tag) {
+        return Err(SealedMessageError::InvalidTag);
+    }
+
+    Ok(apply_key_stream(&stream_key, payload))
+}
+
+/// Like [`seal`], but for any [`BFieldCodec`]-encodable payload. See also
+/// [`open_decodable`].
+pub fn seal_encodable<T: BFieldCodec, P: ParameterSet>(
+    pk: PublicKey<P>,
+    payload: &T,
+    randomness: [u8; 32],
+) -> SealedMessage<P> {
+    let plaintext = payload
+        .encode()
+        .into_iter()
+        .flat_map(<[u8; BFieldElement::BYTES]>::from)
+        .collect_vec();
+
+    seal(pk, &plaintext, randomness)
+}
+
+/// Like [`open`], but decodes the plaintext. See also [`seal_encodable`].
+pub fn open_decodable<T: BFieldCodec, P: ParameterSet>(
+    sk: &SecretKey<P>,
+    sealed_message: &SealedMessage<P>,
+) -> Result<Box<T>, SealedMessageError> {
+    let plaintext = open(sk, sealed_message)?;
+    let sequence = plaintext
+        .chunks(BFieldElement::BYTES)
+        .map(BFieldElement::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    T::decode(&sequence).map_err(|e| SealedMessageError::Decode(e.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use std::time::Instant;
+
+    use proptest_arbitrary_interop::arb;
+    use rand::rngs::StdRng;
+    use rand::Rng;
+    use rand::SeedableRng;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::math::digest::Digest;
+    use crate::math::x_field_element::XFieldElement;
+
+    #[proptest(cases = 20)]
+    fn sealed_message_can_be_opened(
+        key_randomness: [u8; 32],
+        seal_randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..1000))] plaintext: Vec<u8>,
+    ) {
+        let (sk, pk) = keygen(key_randomness);
+        let sealed_message = seal(pk, &plaintext, seal_randomness);
+        prop_assert_eq!(SEALED_MESSAGE_VERSION, sealed_message.version());
+        prop_assert_eq!(plaintext, open(&sk, &sealed_message)?);
+    }
+
+    #[proptest(cases = 20)]
+    fn sealed_message_cannot_be_opened_with_other_key(
+        key_randomness: [u8; 32],
+        #[filter(#key_randomness != #other_key_randomness)] other_key_randomness: [u8; 32],
+        seal_randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] plaintext: Vec<u8>,
+    ) {
+        let (_, pk) = keygen(key_randomness);
+        let (other_sk, _) = keygen(other_key_randomness);
+        let sealed_message = seal(pk, &plaintext, seal_randomness);
+        let err = open(&other_sk, &sealed_message).unwrap_err();
+        let is_decapsulation_failure = matches!(err, SealedMessageError::DecapsulationFailure);
+        prop_assert!(is_decapsulation_failure);
+    }
+
+    #[proptest(cases = 20)]
+    fn sealed_message_with_tampered_payload_is_rejected(
+        key_randomness: [u8; 32],
+        seal_randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 1..100))] plaintext: Vec<u8>,
+        #[strategy(0..#plaintext.len())] byte_index: usize,
+        #[strategy(1..=u8::MAX)] flip: u8,
+    ) {
+        let (sk, pk) = keygen(key_randomness);
+        let mut sealed_message = seal(pk, &plaintext, seal_randomness);
+        sealed_message.payload[byte_index] ^= flip;
+
+        let err = open(&sk, &sealed_message).unwrap_err();
+        let is_invalid_tag = matches!(err, SealedMessageError::InvalidTag);
+        prop_assert!(is_invalid_tag);
+    }
+
+    #[proptest(cases = 20)]
+    fn sealed_message_with_truncated_payload_is_rejected(
+        key_randomness: [u8; 32],
+        seal_randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 1..100))] plaintext: Vec<u8>,
+    ) {
+        let (sk, pk) = keygen(key_randomness);
+        let mut sealed_message = seal(pk, &plaintext, seal_randomness);
+        sealed_message.payload.pop();
+        prop_assert!(open(&sk, &sealed_message).is_err());
+    }
+
+    #[proptest(cases = 20)]
+    fn sealed_message_with_tampered_tag_is_rejected(
+        key_randomness: [u8; 32],
+        seal_randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] plaintext: Vec<u8>,
+        #[strategy(0..32_usize)] byte_index: usize,
+        #[strategy(1..=u8::MAX)] flip: u8,
+    ) {
+        let (sk, pk) = keygen(key_randomness);
+        let mut sealed_message = seal(pk, &plaintext, seal_randomness);
+        sealed_message.tag[byte_index] ^= flip;
+        prop_assert!(open(&sk, &sealed_message).is_err());
+    }
+
+    #[proptest(cases = 20)]
+    fn sealed_message_with_swapped_encapsulation_is_rejected(
+        key_randomness: [u8; 32],
+        seal_randomness: [u8; 32],
+        #[filter(#seal_randomness != #other_seal_randomness)] other_seal_randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] plaintext: Vec<u8>,
+    ) {
+        let (sk, pk) = keygen(key_randomness);
+        let mut sealed_message = seal(pk, &plaintext, seal_randomness);
+        sealed_message.ciphertext = seal(pk, &plaintext, other_seal_randomness).ciphertext;
+        prop_assert!(open(&sk, &sealed_message).is_err());
+    }
+
+    #[proptest(cases = 20)]
+    fn sealed_message_with_unknown_version_is_rejected(
+        key_randomness: [u8; 32],
+        seal_randomness: [u8; 32],
+        #[filter(#version != SEALED_MESSAGE_VERSION)] version: u8,
+    ) {
+        let (sk, pk) = keygen(key_randomness);
+        let mut sealed_message = seal(pk, b"versioned", seal_randomness);
+        sealed_message.version = version;
+
+        let err = open(&sk, &sealed_message).unwrap_err();
+        let is_unsupported_version =
+            matches!(err, SealedMessageError::UnsupportedVersion(v) if v == version);
+        prop_assert!(is_unsupported_version);
+    }
+
+    #[proptest(cases = 20)]
+    fn sealed_encodable_payload_can_be_opened_and_decoded(
+        key_randomness: [u8; 32],
+        seal_randomness: [u8; 32],
+        #[strategy(arb())] payload: (Digest, Vec<XFieldElement>, u64),
+    ) {
+        let (sk, pk) = keygen(key_randomness);
+        let sealed_message = seal_encodable(pk, &payload, seal_randomness);
+        let opened = open_decodable::<(Digest, Vec<XFieldElement>, u64), _>(&sk, &sealed_message)?;
+        prop_assert_eq!(payload, *opened);
+    }
+
+    #[test]
+    fn opening_plaintext_of_invalid_length_as_decodable_fails() {
+        let (sk, pk) = keygen([0; 32]);
+        let sealed_message = seal(pk, b"seven b", [1; 32]);
+        let err = open_decodable::<Digest, _>(&sk, &sealed_message).unwrap_err();
+        assert!(matches!(err, SealedMessageError::InvalidFieldElement(_)));
+    }
+
+    #[test]
+    fn opening_plaintext_of_wrong_type_as_decodable_fails() {
+        let (sk, pk) = keygen([0; 32]);
+        let sealed_message = seal_encodable(pk, &42_u64, [1; 32]);
+        let err = open_decodable::<Digest, _>(&sk, &sealed_message).unwrap_err();
+        assert!(matches!(err, SealedMessageError::Decode(_)));
+    }
+
+    #[test]
+    fn sealed_message_can_be_serialized_and_deserialized() {
+        let (sk, pk) = keygen([0; 32]);
+        let sealed_message = seal(pk, b"serde", [1; 32]);
+        let json = serde_json::to_string(&sealed_message).unwrap();
+        let sealed_message_again: SealedMessage = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(sealed_message, sealed_message_again);
+        assert_eq!(b"serde".to_vec(), open(&sk, &sealed_message_again).unwrap());
+    }
+
+    fn encapsulated_key_can_be_decapsulated<P: ParameterSet>(
+        key_randomness: [u8; 32],
+        enc_randomness: [u8; 32],
+    ) -> Result<(), TestCaseError> {
+        let (sk, pk) = keygen_with_parameters::<P>(key_randomness);
+        let (shared_key, ciphertext) = enc(pk, enc_randomness);
+        prop_assert_eq!(P::PUBLIC_KEY_SIZE_IN_BFES, pk.ga.encode().len());
+        prop_assert_eq!(
+            P::CIPHERTEXT_SIZE_IN_BFES,
+            ciphertext.bg.encode().len() + ciphertext.bga_m.encode().len()
+        );
+        prop_assert_eq!(Some(shared_key), dec(&sk, ciphertext));
+        Ok(())
+    }
+
+    #[proptest(cases = 20)]
+    fn encapsulated_key_can_be_decapsulated_rank_4_degree_64(
+        key_randomness: [u8; 32],
+        enc_randomness: [u8; 32],
+    ) {
+        encapsulated_key_can_be_decapsulated::<Rank4Degree64>(key_randomness, enc_randomness)?;
+    }
+
+    #[proptest(cases = 20)]
+    fn encapsulated_key_can_be_decapsulated_rank_2_degree_128(
+        key_randomness: [u8; 32],
+        enc_randomness: [u8; 32],
+    ) {
+        encapsulated_key_can_be_decapsulated::<Rank2Degree128>(key_randomness, enc_randomness)?;
+    }
+
+    #[proptest(cases = 20)]
+    fn encapsulated_key_can_be_decapsulated_rank_2_degree_256(
+        key_randomness: [u8; 32],
+        enc_randomness: [u8; 32],
+    ) {
+        encapsulated_key_can_be_decapsulated::<Rank2Degree256>(key_randomness, enc_randomness)?;
+    }
+
+    #[proptest(cases = 20)]
+    fn encapsulated_key_can_be_decapsulated_with_custom_parameters(
+        key_randomness: [u8; 32],
+        enc_randomness: [u8; 32],
+    ) {
+        type Custom = Parameters<3, 128, 2>;
+        encapsulated_key_can_be_decapsulated::<Custom>(key_randomness, enc_randomness)?;
+    }
+
+    #[proptest(cases = 20)]
+    fn decapsulation_with_other_key_fails_for_non_default_parameters(
+        key_randomness: [u8; 32],
+        #[filter(#key_randomness != #other_key_randomness)] other_key_randomness: [u8; 32],
+        enc_randomness: [u8; 32],
+    ) {
+        let (_, pk) = keygen_with_parameters::<Rank2Degree256>(key_randomness);
+        let (other_sk, _) = keygen_with_parameters::<Rank2Degree256>(other_key_randomness);
+        let (_, ciphertext) = enc(pk, enc_randomness);
+        prop_assert!(dec(&other_sk, ciphertext).is_none());
+    }
+
+    #[proptest(cases = 20)]
+    fn sealed_message_can_be_opened_for_non_default_parameters(
+        key_randomness: [u8; 32],
+        seal_randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] plaintext: Vec<u8>,
+    ) {
+        let (sk, pk) = keygen_with_parameters::<Rank2Degree128>(key_randomness);
+        let sealed_message = seal(pk, &plaintext, seal_randomness);
+        prop_assert_eq!(plaintext, open(&sk, &sealed_message)?);
+    }
+
+    /// The hashes of the public key and the ciphertext, and the shared key.
+    fn known_answers<P: ParameterSet>() -> [String; 3] {
+        let hash = |encoding: Vec<BFieldElement>| {
+            let bytes = encoding
+                .into_iter()
+                .flat_map(<[u8; BFieldElement::BYTES]>::from)
+                .collect_vec();
+            hex::encode(Sha3_256::digest(bytes))
+        };
+
+        let key_randomness = std::array::from_fn(|i| i as u8);
+        let enc_randomness = std::array::from_fn(|i| 2 * i as u8);
+        let (sk, pk) = keygen_with_parameters::<P>(key_randomness);
+        let (shared_key, ciphertext) = enc(pk, enc_randomness);
+        assert_eq!(Some(&shared_key), dec(&sk, ciphertext).as_ref());
+
+        let ciphertext = [ciphertext.bg.encode(), ciphertext.bga_m.encode()].concat();
+        [
+            hash(pk.ga.encode()),
+            hash(ciphertext),
+            hex::encode(shared_key.as_bytes()),
+        ]
+    }
+
+    #[test]
+    fn rank_4_degree_64_known_answers() {
+        let expected = [
+            "c4dd4d18c23b58e1527054d1d512d255b23d0655e52990ee2ada535d1e03369f",
+            "274393d2b618b46ce2e3242b971d9effea637996463a98791608ee53d65ba384",
+            "67817aa5a1abaeef910a2a9ebb5bf3696e9ee65288670a826ecf87d8f9d44e23",
+        ];
+        assert_eq!(expected, known_answers::<Rank4Degree64>());
+    }
+
+    #[test]
+    fn rank_2_degree_128_known_answers() {
+        let expected = [
+            "bdba6c0e0be5a9d71fe764c1513147c8947fab45de016efa084a8537421180b5",
+            "f9573bd93617944f2407861f40420d6e354d433d27b972b688524aa0c224e314",
+            "67817aa5a1abaeef910a2a9ebb5bf3696e9ee65288670a826ecf87d8f9d44e23",
+        ];
+        assert_eq!(expected, known_answers::<Rank2Degree128>());
+    }
+
+    #[test]
+    fn rank_2_degree_256_known_answers() {
+        let expected = [
+            "4492af0230c8514455f2b6179313192148d10916b6fd98e8decfb9d8c99547e0",
+            "5ee0d163206d02b4edd91a5cecc097394a2b3536b7a17796d2b8ce774b11ff4a",
+            "67817aa5a1abaeef910a2a9ebb5bf3696e9ee65288670a826ecf87d8f9d44e23",
+        ];
+        assert_eq!(expected, known_answers::<Rank2Degree256>());
+    }
+
+    #[proptest(cases = 20)]
+    fn keys_and_ciphertexts_survive_byte_encoding(
+        key_randomness: [u8; 32],
+        enc_randomness: [u8; 32],
+    ) {
+        let (sk, pk) = keygen(key_randomness);
+        let (_, ciphertext) = enc(pk, enc_randomness);
+
+        let sk_bytes = sk.to_bytes();
+        let pk_bytes = pk.to_bytes();
+        let ciphertext_bytes = ciphertext.to_bytes();
+        prop_assert_eq!(SecretKey::<Rank4Degree64>::BYTES, sk_bytes.len());
+        prop_assert_eq!(PublicKey::<Rank4Degree64>::BYTES, pk_bytes.len());
+        prop_assert_eq!(Ciphertext::<Rank4Degree64>::BYTES, ciphertext_bytes.len());
+
+        prop_assert_eq!(sk, SecretKey::try_from_bytes(sk_bytes.as_slice())?);
+        prop_assert_eq!(pk, PublicKey::try_from_bytes(&pk_bytes)?);
+        prop_assert_eq!(ciphertext, Ciphertext::try_from_bytes(&ciphertext_bytes)?);
+    }
+
+    #[proptest(cases = 20)]
+    fn keys_and_ciphertexts_survive_bfield_codec(
+        key_randomness: [u8; 32],
+        enc_randomness: [u8; 32],
+    ) {
+        let (sk, pk) = keygen_with_parameters::<Rank2Degree128>(key_randomness);
+        let (_, ciphertext) = enc(pk, enc_randomness);
+
+        let ciphertext_encoding = ciphertext.encode();
+        prop_assert_eq!(
+            Some(ciphertext_encoding.len()),
+            Ciphertext::<Rank2Degree128>::static_length()
+        );
+
+        let sk_again = *SecretKey::decode(&sk.encode())?;
+        prop_assert_eq!(sk, sk_again);
+        prop_assert_eq!(pk, *PublicKey::decode(&pk.encode())?);
+        prop_assert_eq!(ciphertext, *Ciphertext::decode(&ciphertext_encoding)?);
+    }
+
+    #[proptest(cases = 20)]
+    fn public_key_survives_string_encodings(key_randomness: [u8; 32]) {
+        let (_, pk) = keygen_with_parameters::<Rank2Degree256>(key_randomness);
+        prop_assert_eq!(pk, PublicKey::try_from_hex(pk.to_hex())?);
+    }
+
+    #[proptest(cases = 20)]
+    fn public_key_fingerprint_is_standard_bech32m(
+        key_randomness: [u8; 32],
+        other_key_randomness: [u8; 32],
+    ) {
+        prop_assume!(key_randomness != other_key_randomness);
+        let (_, pk) = keygen_with_parameters::<Rank2Degree256>(key_randomness);
+        let (_, other_pk) = keygen_with_parameters::<Rank2Degree256>(other_key_randomness);
+
+        let fingerprint = pk.fingerprint_bech32m();
+        prop_assert!(fingerprint.starts_with("kemfp-2-256-8"));
+        prop_assert!(fingerprint.len() <= 90);
+        prop_assert!(pk.matches_fingerprint_bech32m(&fingerprint)?);
+        prop_assert!(pk.matches_fingerprint_bech32m(&fingerprint.to_uppercase())?);
+        prop_assert!(!other_pk.matches_fingerprint_bech32m(&fingerprint)?);
+
+        let (hrp, data) = bech32::decode(&fingerprint).unwrap();
+        prop_assert_eq!(PublicKey::<Rank2Degree256>::fingerprint_hrp(), hrp);
+        prop_assert_eq!(pk.fingerprint().to_vec(), data);
+    }
+
+    #[proptest(cases = 20)]
+    fn non_canonical_field_elements_are_rejected(
+        key_randomness: [u8; 32],
+        enc_randomness: [u8; 32],
+        #[strategy(0..PublicKey::<Rank4Degree64>::BYTES / BFieldElement::BYTES)]
+        element_index: usize,
+        #[strategy(BFieldElement::P..)] non_canonical_value: u64,
+    ) {
+        let (_, pk) = keygen(key_randomness);
+        let (_, ciphertext) = enc(pk, enc_randomness);
+        let expected_err = KemDecodingError::InvalidFieldElement(
+            ParseBFieldElementError::NotCanonical(non_canonical_value),
+        );
+
+        let mut ciphertext_bytes = ciphertext.to_bytes();
+        let start = element_index * BFieldElement::BYTES;
+        let end = start + BFieldElement::BYTES;
+        ciphertext_bytes[start..end].copy_from_slice(&non_canonical_value.to_le_bytes());
+        let ciphertext_err =
+            Ciphertext::<Rank4Degree64>::try_from_bytes(&ciphertext_bytes).unwrap_err();
+        prop_assert_eq!(&expected_err, &ciphertext_err);
+
+        let mut pk_bytes = pk.to_bytes();
+        let Some(element_bytes) = pk_bytes.get_mut(32 + start..32 + end) else {
+            return Ok(());
+        };
+        element_bytes.copy_from_slice(&non_canonical_value.to_le_bytes());
+        let pk_err = PublicKey::<Rank4Degree64>::try_from_bytes(&pk_bytes).unwrap_err();
+        prop_assert_eq!(expected_err, pk_err);
+    }
+
+    #[proptest(cases = 20)]
+    fn encodings_of_wrong_length_are_rejected(
+        key_randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] extra_bytes: Vec<u8>,
+        truncate: bool,
+    ) {
+        let (sk, pk) = keygen(key_randomness);
+        let mut sk_bytes = sk.to_bytes().to_vec();
+        let mut pk_bytes = pk.to_bytes();
+        if truncate {
+            sk_bytes.truncate(extra_bytes.len().min(SecretKey::<Rank4Degree64>::BYTES - 1));
+            pk_bytes.truncate(extra_bytes.len());
+        } else {
+            prop_assume!(!extra_bytes.is_empty());
+            sk_bytes.extend(&extra_bytes);
+            pk_bytes.extend(&extra_bytes);
+        }
+
+        let sk_err = SecretKey::<Rank4Degree64>::try_from_bytes(&sk_bytes).unwrap_err();
+        let pk_err = PublicKey::<Rank4Degree64>::try_from_bytes(&pk_bytes).unwrap_err();
+        let sk_err_is_length_err = matches!(sk_err, KemDecodingError::InvalidNumBytes { .. });
+        let pk_err_is_length_err = matches!(pk_err, KemDecodingError::InvalidNumBytes { .. });
+        prop_assert!(sk_err_is_length_err);
+        prop_assert!(pk_err_is_length_err);
+    }
+
+    #[test]
+    fn public_key_fingerprint_for_other_parameters_is_rejected() {
+        let (_, pk) = keygen_with_parameters::<Rank2Degree128>([0; 32]);
+        let (_, other_pk) = keygen([0; 32]);
+        let err = other_pk
+            .matches_fingerprint_bech32m(&pk.fingerprint_bech32m())
+            .unwrap_err();
+        let KemDecodingError::InvalidHumanReadablePart { expected, actual } = err else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!("kemfp-4-64-8", expected);
+        assert_eq!("kemfp-2-128-8", actual);
+    }
+
+    #[test]
+    fn tampered_bech32m_public_key_fingerprint_is_rejected() {
+        let (_, pk) = keygen([0; 32]);
+        let mut bech32m = pk.fingerprint_bech32m().into_bytes();
+        let last = bech32m.last_mut().unwrap();
+        *last = if *last == b'q' { b'p' } else { b'q' };
+        let bech32m = String::from_utf8(bech32m).unwrap();
+
+        let err = pk.matches_fingerprint_bech32m(&bech32m).unwrap_err();
+        assert!(matches!(err, KemDecodingError::Bech32mDecode(_)));
+    }
+
+    #[test]
+    fn secret_key_and_shared_key_can_be_wiped() {
+        let (mut sk, pk) = keygen([42; 32]);
+        let (mut shared_key, _) = enc(pk, [17; 32]);
+        sk.zeroize();
+        shared_key.zeroize();
+
+        assert_eq!([0; SecretKey::<Rank4Degree64>::BYTES], *sk.to_bytes());
+        assert_eq!(&[0; 32], shared_key.as_bytes());
+    }
+
+    /// Welch's t-statistic for the hypothesis that both samples have the same mean.
+    fn welch_t_statistic(samples: [Vec<f64>; 2]) -> f64 {
+        let [(mean_0, var_0, n_0), (mean_1, var_1, n_1)] = samples.map(|sample| {
+            let n = sample.len() as f64;
+            let mean = sample.iter().sum::<f64>() / n;
+            let variance = sample.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            (mean, variance, n)
+        });
+
+        (mean_0 - mean_1) / (var_0 / n_0 + var_1 / n_1).sqrt()
+    }
+
+    /// A [dudect](https://eprint.iacr.org/2016/1123)-style leakage test: decapsulate one
+    /// fixed ciphertext under one fixed secret key, and fresh ciphertexts under fresh secret
+    /// keys, in random order, and check that the mean running times agree. Branching on the
+    /// secret key or the shared key makes the t-statistic grow with the number of
+    /// measurements. With the default number of measurements, only differences of several
+    /// percent of the running time are caught reliably.
+    ///
+    /// Timings are only meaningful in release mode on an otherwise idle machine:
+    /// `cargo test --release -- --ignored decapsulation_time`
+    #[test]
+    #[ignore = "timing-sensitive; run in release mode on an idle machine"]
+    fn decapsulation_time_is_independent_of_secrets() {
+        const NUM_MEASUREMENTS: usize = 5_000;
+
+        // The threshold above which dudect considers a leak certain.
+        const T_THRESHOLD: f64 = 10.0;
+
+        // Like dudect, discard the slowest measurements, which are mostly interrupted runs.
+        const CROP_PERCENTILE: usize = 90;
+
+        // Scheduling work across threads is a major source of noise.
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let (fixed_sk, fixed_pk) = keygen(rng.gen());
+        let (_, fixed_ciphertext) = enc(fixed_pk, rng.gen());
+        let mut timings = [vec![], vec![]];
+        thread_pool.install(|| {
+            for _ in 0..NUM_MEASUREMENTS {
+                let class = rng.gen_range(0..2);
+                let (sk, ciphertext) = match class {
+                    0 => (fixed_sk.clone(), fixed_ciphertext),
+                    _ => {
+                        let (sk, pk) = keygen(rng.gen());
+                        (sk, enc(pk, rng.gen()).1)
+                    }
+                };
+
+                let start = Instant::now();
+                let shared_key = std::hint::black_box(dec(&sk, std::hint::black_box(ciphertext)));
+                timings[class].push(start.elapsed().as_secs_f64());
+                assert!(shared_key.is_some());
+            }
+        });
+
+        let mut all_timings = timings.concat();
+        all_timings.sort_by(f64::total_cmp);
+        let crop_threshold = all_timings[all_timings.len() * CROP_PERCENTILE / 100];
+        let timings = timings.map(|class_timings| {
+            class_timings
+                .into_iter()
+                .filter(|&timing| timing < crop_threshold)
+                .collect()
+        });
+
+        let t_statistic = welch_t_statistic(timings);
+        assert!(t_statistic.abs() < T_THRESHOLD, "t = {t_statistic}");
+    }
+}
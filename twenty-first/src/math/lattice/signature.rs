@@ -0,0 +1,511 @@
+//! Signatures in the style of [Dilithium][dilithium], _i.e._, Fiat–Shamir with aborts,
+//! over the ring `Fp[X] / (X^64 + 1)` with `p = 2^64 - 2^32 + 1`.
+//!
+//! The public key is `t = A·s1 + s2`, where `A` is a uniformly random matrix derived from a
+//! public seed, and `s1`, `s2` are short secret vectors. To sign, sample a masking vector
+//! `y`, derive the challenge `c` from the message and the high bits of `A·y`, and reveal
+//! `z = y + c·s1`. Whenever `z` or the low bits of `A·y - c·s2` would leak information about
+//! the secret, signing aborts and retries with a fresh `y`. The verifier recomputes the high
+//! bits of `A·z - c·t` and checks that they hash to the same challenge.
+//!
+//! Unlike Dilithium, the public key is not compressed, so no hints are needed. All
+//! randomness – the public matrix, the secret vectors, the masking vectors, and the
+//! challenges – is derived with Shake256, which makes both key generation and signing
+//! deterministic.
+//!
+//! The parameters are obtained by scaling those of Dilithium2 to the much larger modulus
+//! while keeping the module dimension (rank times ring degree) at 1024. They have not
+//! been subjected to dedicated cryptanalysis.
+//!
+//! [dilithium]: https://pq-crystals.org/dilithium/
+
+use itertools::Itertools;
+use num_traits::ConstZero;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+use sha3::digest::ExtendableOutput;
+use sha3::digest::Update;
+use sha3::digest::XofReader;
+use sha3::Shake256;
+
+use super::kem::shake256;
+use super::CyclotomicRingElement;
+use super::ModuleElement;
+use super::CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES;
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+
+/// The number of rows of the public matrix `A`.
+const K: usize = 16;
+
+/// The number of columns of the public matrix `A`.
+const L: usize = 16;
+
+/// The secret vectors' coefficients lie in `[-2^LOG2_ETA, 2^LOG2_ETA)`.
+const LOG2_ETA: u32 = 40;
+
+/// The masking vectors' coefficients lie in `[-2^LOG2_GAMMA_1, 2^LOG2_GAMMA_1)`.
+const LOG2_GAMMA_1: u32 = 58;
+
+const GAMMA_1: u64 = 1 << LOG2_GAMMA_1;
+
+/// Half the modulus of the decomposition into high and low bits.
+const GAMMA_2: u64 = (BFieldElement::P - 1) / 64;
+
+/// Every challenge has `CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES` coefficients in `[-2, 2]`,
+/// giving a challenge space of size `5^64 ≈ 2^148`.
+const CHALLENGE_BOUND: u64 = 2;
+
+/// An upper bound on `‖c·s‖∞` for any challenge `c` and secret vector `s`.
+const BETA: u64 = CHALLENGE_BOUND * CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES as u64 * (1 << LOG2_ETA);
+
+const _: () = assert!(BETA < GAMMA_1 && BETA < GAMMA_2);
+
+const RING_ELEMENT_SAMPLING_NUM_BYTES: usize = 8 * CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize, BFieldCodec)]
+pub struct SecretKey {
+    key: [u8; 32],
+    seed: [u8; 32],
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize, BFieldCodec)]
+pub struct PublicKey {
+    seed: [u8; 32],
+
+    /// `A·s1 + s2`, in NTT domain
+    t: ModuleElement<K>,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize, BFieldCodec)]
+pub struct Signature {
+    challenge_seed: [u8; 32],
+    z: ModuleElement<L>,
+}
+
+/// The public matrix `A`, in NTT domain. Because the NTT of a uniformly random ring element
+/// is itself uniformly random, the matrix is sampled in NTT domain directly.
+fn derive_public_matrix(seed: &[u8; 32]) -> ModuleElement<{ K * L }> {
+    const NUM_BYTES: usize = 9 * CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES;
+    let elements = (0..K * L)
+        .map(|i| {
+            let index = u16::try_from(i).unwrap().to_le_bytes();
+            let randomness = shake256::<NUM_BYTES>([&seed[..], &index].concat());
+            CyclotomicRingElement::sample_uniform(&randomness)
+        })
+        .collect_vec();
+
+    ModuleElement {
+        elements: elements.try_into().unwrap(),
+    }
+}
+
+fn derive_secret_vectors(key: &[u8; 32]) -> (ModuleElement<L>, ModuleElement<K>) {
+    const NUM_BYTES: usize = (L + K) * RING_ELEMENT_SAMPLING_NUM_BYTES;
+    let randomness = shake256::<NUM_BYTES>(key);
+    let (s1_randomness, s2_randomness) = randomness.split_at(L * RING_ELEMENT_SAMPLING_NUM_BYTES);
+    let s1 = sample_centered(s1_randomness, LOG2_ETA);
+    let s2 = sample_centered(s2_randomness, LOG2_ETA);
+
+    (s1, s2)
+}
+
+/// Sample a module element whose coefficients are uniformly distributed in
+/// `[-2^log2_bound, 2^log2_bound)`, consuming 8 bytes of randomness per coefficient.
+fn sample_centered<const N: usize>(randomness: &[u8], log2_bound: u32) -> ModuleElement<N> {
+    debug_assert!(randomness.len() >= N * RING_ELEMENT_SAMPLING_NUM_BYTES);
+    let mask = (1 << (log2_bound + 1)) - 1;
+    let offset = BFieldElement::new(1 << log2_bound);
+    let sample = |bytes: &[u8]| {
+        let value = u64::from_le_bytes(bytes.try_into().unwrap()) & mask;
+        BFieldElement::new(value) - offset
+    };
+
+    let elements = randomness
+        .chunks_exact(RING_ELEMENT_SAMPLING_NUM_BYTES)
+        .take(N)
+        .map(|chunk| chunk.chunks_exact(8).map(sample).collect_vec())
+        .map(|coefficients| CyclotomicRingElement {
+            coefficients: coefficients.try_into().unwrap(),
+        })
+        .collect_vec();
+
+    ModuleElement {
+        elements: elements.try_into().unwrap(),
+    }
+}
+
+/// Sample the challenge ring element from the challenge seed. Every coefficient is uniformly
+/// distributed in `[-CHALLENGE_BOUND, CHALLENGE_BOUND]`.
+fn sample_challenge(challenge_seed: &[u8; 32]) -> CyclotomicRingElement {
+    const NUM_VALUES: u8 = 2 * CHALLENGE_BOUND as u8 + 1;
+    const MAX_ACCEPTED_BYTE: u8 = u8::MAX - u8::MAX % NUM_VALUES - 1;
+
+    let mut hasher = Shake256::default();
+    hasher.update(challenge_seed);
+    let mut reader = hasher.finalize_xof();
+
+    let mut coefficients = [BFieldElement::ZERO; CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES];
+    for coefficient in &mut coefficients {
+        let mut byte = [0];
+        reader.read(&mut byte);
+        while byte[0] > MAX_ACCEPTED_BYTE {
+            reader.read(&mut byte);
+        }
+        let value = u64::from(byte[0] % NUM_VALUES);
+        *coefficient = BFieldElement::new(value) - BFieldElement::new(CHALLENGE_BOUND);
+    }
+
+    CyclotomicRingElement { coefficients }
+}
+
+/// The absolute value of the centered representative of the field element.
+fn centered_abs(element: BFieldElement) -> u64 {
+    let value = element.value();
+    value.min(BFieldElement::P - value)
+}
+
+fn infinity_norm<const N: usize>(module_element: &ModuleElement<N>) -> u64 {
+    module_element
+        .elements
+        .iter()
+        .flat_map(|element| element.coefficients)
+        .map(centered_abs)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Split the field element `r` into `(r1, r0)` such that `r = r1·2·GAMMA_2 + r0` with
+/// `r0` in `(-GAMMA_2, GAMMA_2]`. The corner case `r - r0 = p - 1` is mapped to
+/// `(0, r0 - 1)`, which guarantees that `r1` lies in `[0, 32)`.
+fn decompose(r: BFieldElement) -> (u8, BFieldElement) {
+    let alpha = 2 * GAMMA_2;
+    let r = r.value();
+    let remainder = r % alpha;
+    let (r0, r_minus_r0) = if remainder <= GAMMA_2 {
+        (BFieldElement::new(remainder), r - remainder)
+    } else {
+        (
+            -BFieldElement::new(alpha - remainder),
+            r + (alpha - remainder),
+        )
+    };
+
+    if r_minus_r0 == BFieldElement::P - 1 {
+        return (0, r0 - BFieldElement::new(1));
+    }
+    let r1 = u8::try_from(r_minus_r0 / alpha).unwrap();
+
+    (r1, r0)
+}
+
+fn high_bits(w: &ModuleElement<K>) -> Vec<u8> {
+    w.elements
+        .iter()
+        .flat_map(|element| element.coefficients)
+        .map(|coefficient| decompose(coefficient).0)
+        .collect()
+}
+
+fn low_bits_infinity_norm(w: &ModuleElement<K>) -> u64 {
+    w.elements
+        .iter()
+        .flat_map(|element| element.coefficients)
+        .map(|coefficient| centered_abs(decompose(coefficient).1))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Bind the message to the public key.
+fn message_representative(public_key: &PublicKey, message: &[u8]) -> [u8; 64] {
+    let public_key_bytes = public_key
+        .encode()
+        .into_iter()
+        .flat_map(|bfe| bfe.value().to_le_bytes())
+        .collect_vec();
+    let public_key_hash = shake256::<64>(public_key_bytes);
+
+    shake256([&public_key_hash[..], message].concat())
+}
+
+fn challenge_seed(message_representative: &[u8; 64], w1: &[u8]) -> [u8; 32] {
+    shake256([&message_representative[..], w1].concat())
+}
+
+/// Multiply every element of the module element by the ring element. Both arguments and
+/// the result are in NTT domain.
+fn scale<const N: usize>(
+    module_element: ModuleElement<N>,
+    scalar: CyclotomicRingElement,
+) -> ModuleElement<N> {
+    let elements = module_element
+        .elements
+        .map(|element| CyclotomicRingElement::hadamard(element, scalar));
+    ModuleElement { elements }
+}
+
+fn ntt(element: CyclotomicRingElement) -> CyclotomicRingElement {
+    ModuleElement {
+        elements: [element],
+    }
+    .ntt()
+    .elements[0]
+}
+
+/// Generate a secret-public key pair for signing.
+pub fn keygen(randomness: [u8; 32]) -> (SecretKey, PublicKey) {
+    const OUTPUT_LENGTH: usize = 32;
+    let seed: [u8; OUTPUT_LENGTH] = shake256([randomness.to_vec(), vec![0u8]].concat());
+    let key: [u8; OUTPUT_LENGTH] = shake256([randomness.to_vec(), vec![1u8]].concat());
+
+    let sk = SecretKey { key, seed };
+    let pk = derive_public_key(&key, &seed);
+    (sk, pk)
+}
+
+fn derive_public_key(key: &[u8; 32], seed: &[u8; 32]) -> PublicKey {
+    let (s1, s2) = derive_secret_vectors(key);
+    let a = derive_public_matrix(seed);
+    let t =
+        ModuleElement::<K>::multiply_hadamard::<K, { K * L }, 1, L, L, K>(a, s1.ntt()) + s2.ntt();
+
+    PublicKey { seed: *seed, t }
+}
+
+/// Sign the message. Signing is deterministic: signing the same message twice with the
+/// same secret key produces the same signature.
+pub fn sign(sk: &SecretKey, message: &[u8]) -> Signature {
+    let pk = derive_public_key(&sk.key, &sk.seed);
+    let mu = message_representative(&pk, message);
+
+    let (s1, s2) = derive_secret_vectors(&sk.key);
+    let s1_ntt = s1.ntt();
+    let s2_ntt = s2.ntt();
+    let a = derive_public_matrix(&sk.seed);
+    let masking_seed = shake256::<64>([&sk.key[..], &mu].concat());
+
+    for nonce in 0_u64.. {
+        const NUM_BYTES: usize = L * RING_ELEMENT_SAMPLING_NUM_BYTES;
+        let nonce = nonce.to_le_bytes();
+        let randomness = shake256::<NUM_BYTES>([&masking_seed[..], &nonce].concat());
+        let y = sample_centered::<L>(&randomness, LOG2_GAMMA_1);
+
+        let w = ModuleElement::<K>::multiply_hadamard::<K, { K * L }, 1, L, L, K>(a, y.ntt());
+        let w1 = high_bits(&w.intt());
+        let challenge_seed = challenge_seed(&mu, &w1);
+        let c_ntt = ntt(sample_challenge(&challenge_seed));
+
+        let z = y + scale(s1_ntt, c_ntt).intt();
+        if infinity_norm(&z) >= GAMMA_1 - BETA {
+            continue;
+        }
+
+        let r = (w - scale(s2_ntt, c_ntt)).intt();
+        if low_bits_infinity_norm(&r) >= GAMMA_2 - BETA {
+            continue;
+        }
+
+        return Signature { challenge_seed, z };
+    }
+
+    unreachable!("signing must succeed before the nonce overflows")
+}
+
+pub fn verify(pk: &PublicKey, message: &[u8], signature: &Signature) -> bool {
+    if infinity_norm(&signature.z) >= GAMMA_1 - BETA {
+        return false;
+    }
+
+    let mu = message_representative(pk, message);
+    let a = derive_public_matrix(&pk.seed);
+    let c_ntt = ntt(sample_challenge(&signature.challenge_seed));
+    let az =
+        ModuleElement::<K>::multiply_hadamard::<K, { K * L }, 1, L, L, K>(a, signature.z.ntt());
+    let w = (az - scale(pk.t, c_ntt)).intt();
+    let w1 = high_bits(&w);
+
+    challenge_seed(&mu, &w1) == signature.challenge_seed
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use sha3::Digest as Sha3Digest;
+    use sha3::Sha3_256;
+    use test_strategy::proptest;
+
+    use super::*;
+
+    #[proptest(cases = 10)]
+    fn signature_for_message_verifies(
+        randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] message: Vec<u8>,
+    ) {
+        let (sk, pk) = keygen(randomness);
+        let signature = sign(&sk, &message);
+        prop_assert!(verify(&pk, &message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_for_other_message_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] message: Vec<u8>,
+        #[strategy(vec(any::<u8>(), 0..100))]
+        #[filter(#message != #other_message)]
+        other_message: Vec<u8>,
+    ) {
+        let (sk, pk) = keygen(randomness);
+        let signature = sign(&sk, &message);
+        prop_assert!(!verify(&pk, &other_message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_does_not_verify_under_other_public_key(
+        randomness: [u8; 32],
+        #[filter(#randomness != #other_randomness)] other_randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] message: Vec<u8>,
+    ) {
+        let (sk, _) = keygen(randomness);
+        let (_, other_pk) = keygen(other_randomness);
+        let signature = sign(&sk, &message);
+        prop_assert!(!verify(&other_pk, &message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_with_tampered_response_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] message: Vec<u8>,
+        #[strategy(0..L)] element_index: usize,
+        #[strategy(0..CYCLOTOMIC_RING_ELEMENT_SIZE_IN_BFES)] coefficient_index: usize,
+        #[strategy(1..1_u64 << 20)] offset: u64,
+    ) {
+        let (sk, pk) = keygen(randomness);
+        let mut signature = sign(&sk, &message);
+        signature.z.elements[element_index].coefficients[coefficient_index] +=
+            BFieldElement::new(offset);
+        prop_assert!(!verify(&pk, &message, &signature));
+    }
+
+    #[proptest(cases = 10)]
+    fn signature_with_tampered_challenge_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] message: Vec<u8>,
+        #[strategy(0..32_usize)] byte_index: usize,
+        #[strategy(1..=u8::MAX)] flip: u8,
+    ) {
+        let (sk, pk) = keygen(randomness);
+        let mut signature = sign(&sk, &message);
+        signature.challenge_seed[byte_index] ^= flip;
+        prop_assert!(!verify(&pk, &message, &signature));
+    }
+
+    #[test]
+    fn signature_with_too_large_response_does_not_verify() {
+        let (sk, pk) = keygen([0; 32]);
+        let message = b"message";
+        let mut signature = sign(&sk, message);
+        signature.z.elements[0].coefficients[0] = BFieldElement::new(GAMMA_1 - BETA);
+        assert!(!verify(&pk, message, &signature));
+    }
+
+    #[proptest(cases = 5)]
+    fn signing_is_deterministic(
+        randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] message: Vec<u8>,
+    ) {
+        let (sk, _) = keygen(randomness);
+        prop_assert_eq!(sign(&sk, &message), sign(&sk, &message));
+    }
+
+    #[proptest]
+    fn decomposition_recombines_to_original(#[strategy(arb())] r: BFieldElement) {
+        let (r1, r0) = decompose(r);
+        prop_assert!(r1 < 32);
+        prop_assert!(centered_abs(r0) <= GAMMA_2);
+
+        let recombined = BFieldElement::new(u64::from(r1) * 2 * GAMMA_2) + r0;
+        prop_assert_eq!(r, recombined);
+    }
+
+    #[proptest]
+    fn adding_small_element_does_not_change_high_bits_if_low_bits_are_small(
+        #[strategy(arb())] r: BFieldElement,
+        #[strategy(-(BETA as i64)..=BETA as i64)] small: i64,
+    ) {
+        let (_, r0) = decompose(r);
+        prop_assume!(centered_abs(r0) < GAMMA_2 - BETA);
+
+        let small = BFieldElement::from(small);
+        prop_assert_eq!(decompose(r).0, decompose(r + small).0);
+    }
+
+    #[proptest]
+    fn challenge_coefficients_are_small(challenge_seed: [u8; 32]) {
+        let challenge = sample_challenge(&challenge_seed);
+        for coefficient in challenge.coefficients {
+            prop_assert!(centered_abs(coefficient) <= CHALLENGE_BOUND);
+        }
+    }
+
+    #[proptest]
+    fn sampled_secret_vectors_are_short(key: [u8; 32]) {
+        let (s1, s2) = derive_secret_vectors(&key);
+        prop_assert!(infinity_norm(&s1) <= 1 << LOG2_ETA);
+        prop_assert!(infinity_norm(&s2) <= 1 << LOG2_ETA);
+    }
+
+    #[proptest(cases = 5)]
+    fn keys_and_signature_can_be_encoded_and_decoded(
+        randomness: [u8; 32],
+        #[strategy(vec(any::<u8>(), 0..100))] message: Vec<u8>,
+    ) {
+        let (sk, pk) = keygen(randomness);
+        let signature = sign(&sk, &message);
+
+        prop_assert_eq!(sk, *SecretKey::decode(&sk.encode())?);
+        prop_assert_eq!(pk, *PublicKey::decode(&pk.encode())?);
+        prop_assert_eq!(signature, *Signature::decode(&signature.encode())?);
+    }
+
+    #[test]
+    fn keys_and_signature_can_be_serialized_and_deserialized() {
+        let (sk, pk) = keygen([42; 32]);
+        let signature = sign(&sk, b"serde");
+
+        let sk_as_json = serde_json::to_string(&sk).unwrap();
+        assert_eq!(sk, serde_json::from_str(&sk_as_json).unwrap());
+
+        let pk_as_json = serde_json::to_string(&pk).unwrap();
+        assert_eq!(pk, serde_json::from_str(&pk_as_json).unwrap());
+
+        let signature_as_json = serde_json::to_string(&signature).unwrap();
+        assert_eq!(signature, serde_json::from_str(&signature_as_json).unwrap());
+    }
+
+    #[test]
+    fn known_answers() {
+        let hash = |encoding: Vec<BFieldElement>| {
+            let bytes = encoding
+                .into_iter()
+                .flat_map(|bfe| bfe.value().to_le_bytes())
+                .collect_vec();
+            hex::encode(Sha3_256::digest(bytes))
+        };
+
+        let randomness = std::array::from_fn(|i| i as u8);
+        let message = b"twenty-first lattice signatures";
+        let (sk, pk) = keygen(randomness);
+        let signature = sign(&sk, message);
+        assert!(verify(&pk, message, &signature));
+
+        let expected_sk_hash = "8c860f63c1e53fd580d6f31dc662b6823c7aceb38345397a5963253942a0e604";
+        let expected_pk_hash = "d2415440008cd7a30a6a9eb9d72a530b13c3bf72c4f4c4630d1aedbd55a79af9";
+        let expected_signature_hash =
+            "623d2527d0458c9be46df6eb8c2c5de166f9bb4ecc7682bccf404b0db54fefaf";
+        assert_eq!(expected_sk_hash, hash(sk.encode()));
+        assert_eq!(expected_pk_hash, hash(pk.encode()));
+        assert_eq!(expected_signature_hash, hash(signature.encode()));
+    }
+}
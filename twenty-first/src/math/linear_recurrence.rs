@@ -0,0 +1,265 @@
+//! Linear recurrences over [finite fields](FiniteField).
+//!
+//! A sequence `s_0, s_1, …` satisfies the linear recurrence with characteristic polynomial
+//! `P(X) = Σ_k p_k·X^k` of degree `L` if `Σ_k p_k·s_{i+k} = 0` for all `i`. The
+//! [Berlekamp–Massey algorithm](berlekamp_massey) finds the shortest such recurrence for a
+//! given sequence, and [`nth_term`] extrapolates the sequence.
+
+use std::ops::MulAssign;
+
+use num_traits::One;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::polynomial::Polynomial;
+use crate::math::traits::FiniteField;
+
+/// The characteristic polynomial of the shortest linear recurrence satisfied by the given
+/// sequence.
+///
+/// The returned polynomial `P(X) = Σ_k p_k·X^k` is monic. Its degree `L`, the _linear
+/// complexity_ of the sequence, is minimal such that `Σ_k p_k·s_{i+k} = 0` for all
+/// `0 ≤ i < sequence.len() - L`. If the sequence has at least `2·L` terms, the polynomial is
+/// unique.
+///
+/// # Examples
+///
+/// ```
+/// # use twenty_first::prelude::*;
+/// # use twenty_first::math::linear_recurrence::berlekamp_massey;
+/// let fibonacci = bfe_vec![0, 1, 1, 2, 3, 5, 8, 13];
+/// let characteristic_polynomial = berlekamp_massey(&fibonacci); // x² - x - 1
+/// assert_eq!(Polynomial::new(bfe_vec![-1, -1, 1]), characteristic_polynomial);
+/// ```
+pub fn berlekamp_massey<FF: FiniteField>(sequence: &[FF]) -> Polynomial<'static, FF> {
+    // the connection polynomial C(X) = 1 + c_1·X + … + c_L·X^L is the reversal of P(X)
+    let mut connection = vec![FF::ONE];
+    let mut previous_connection = vec![FF::ONE];
+    let mut linear_complexity = 0;
+    let mut previous_discrepancy = FF::ONE;
+    let mut shift = 1;
+
+    for (n, &term) in sequence.iter().enumerate() {
+        let discrepancy = connection
+            .iter()
+            .skip(1)
+            .zip(sequence[..n].iter().rev())
+            .fold(term, |acc, (&c, &s)| acc + c * s);
+        if discrepancy.is_zero() {
+            shift += 1;
+            continue;
+        }
+
+        let correction_factor = discrepancy / previous_discrepancy;
+        let updated_len = connection.len().max(previous_connection.len() + shift);
+        let mut updated_connection = connection.clone();
+        updated_connection.resize(updated_len, FF::ZERO);
+        for (c, &b) in updated_connection[shift..]
+            .iter_mut()
+            .zip(&previous_connection)
+        {
+            *c -= correction_factor * b;
+        }
+
+        if 2 * linear_complexity <= n {
+            linear_complexity = n + 1 - linear_complexity;
+            previous_connection = connection;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+        connection = updated_connection;
+    }
+
+    connection.resize(linear_complexity + 1, FF::ZERO);
+    connection.reverse();
+    Polynomial::new(connection)
+}
+
+/// The `n`th term of the sequence that satisfies the linear recurrence with the given
+/// characteristic polynomial and starts with the given terms.
+///
+/// With `r(X) = X^n mod P(X)`, the `n`th term is `Σ_k r_k·s_k`. The remainder is computed by
+/// square-and-multiply, reducing with [`Polynomial::fast_reduce`] after every step. This takes
+/// O(log(n)) polynomial multiplications of degree `L`, the degree of the characteristic
+/// polynomial, instead of the O(n·L) operations for stepping through the recurrence.
+///
+/// # Panics
+///
+/// Panics if
+/// - the characteristic polynomial is zero, or
+/// - fewer initial terms than the degree of the characteristic polynomial are given.
+///
+/// # Examples
+///
+/// ```
+/// # use twenty_first::prelude::*;
+/// # use twenty_first::math::linear_recurrence::nth_term;
+/// let fibonacci_polynomial = Polynomial::new(bfe_vec![-1, -1, 1]);
+/// let f_90 = nth_term(&fibonacci_polynomial, &bfe_vec![0, 1], 90);
+/// assert_eq!(bfe!(2_880_067_194_370_816_120_u64), f_90);
+/// ```
+pub fn nth_term<FF>(characteristic_polynomial: &Polynomial<FF>, initial_terms: &[FF], n: u64) -> FF
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+{
+    let Ok(order) = usize::try_from(characteristic_polynomial.degree()) else {
+        panic!("the characteristic polynomial must not be zero");
+    };
+    assert!(
+        initial_terms.len() >= order,
+        "need at least {order} initial terms, but got {}",
+        initial_terms.len()
+    );
+
+    x_to_the_power_mod(n, characteristic_polynomial)
+        .coefficients()
+        .iter()
+        .zip(initial_terms)
+        .fold(FF::ZERO, |acc, (&r, &s)| acc + r * s)
+}
+
+/// `X^exponent mod modulus`, using square-and-multiply.
+fn x_to_the_power_mod<FF>(exponent: u64, modulus: &Polynomial<FF>) -> Polynomial<'static, FF>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+{
+    let mut acc = Polynomial::one().fast_reduce(modulus);
+    for bit_index in (0..u64::BITS - exponent.leading_zeros()).rev() {
+        acc = acc.square().fast_reduce(modulus);
+        if (exponent >> bit_index) & 1 == 1 {
+            acc = acc.shift_coefficients(1).fast_reduce(modulus);
+        }
+    }
+
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::ConstOne;
+    use num_traits::ConstZero;
+    use num_traits::Zero;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::bfe;
+    use crate::bfe_array;
+    use crate::bfe_vec;
+    use crate::math::traits::ModPowU64;
+    use crate::math::x_field_element::XFieldElement;
+
+    /// Extend the initial terms to `len` terms using the recurrence.
+    fn sequence<FF: FiniteField>(
+        characteristic_polynomial: &Polynomial<FF>,
+        initial_terms: &[FF],
+        len: usize,
+    ) -> Vec<FF> {
+        let coefficients = characteristic_polynomial.coefficients();
+        let (&leading_coefficient, tail) = coefficients.split_last().unwrap();
+        let order = tail.len();
+
+        let mut sequence = initial_terms[..order].to_vec();
+        while sequence.len() < len {
+            let window = &sequence[sequence.len() - order..];
+            let weighted_sum = tail
+                .iter()
+                .zip(window)
+                .fold(FF::ZERO, |acc, (&p, &s)| acc + p * s);
+            sequence.push(-weighted_sum / leading_coefficient);
+        }
+        sequence.truncate(len);
+        sequence
+    }
+
+    #[test]
+    fn shortest_recurrence_of_zero_sequence_is_trivial() {
+        let characteristic_polynomial = berlekamp_massey(&[BFieldElement::ZERO; 10]);
+        assert!(characteristic_polynomial.is_one());
+        assert!(berlekamp_massey::<BFieldElement>(&[]).is_one());
+    }
+
+    #[test]
+    fn shortest_recurrence_of_impulse_is_x_to_the_length() {
+        let impulse = bfe_vec![0, 0, 1, 0, 0, 0, 0];
+        let characteristic_polynomial = berlekamp_massey(&impulse);
+        assert_eq!(Polynomial::x_to_the(3), characteristic_polynomial);
+    }
+
+    #[proptest]
+    fn shortest_recurrence_is_satisfied_by_sequence(
+        #[strategy(vec(arb(), 0..50))] sequence: Vec<BFieldElement>,
+    ) {
+        let characteristic_polynomial = berlekamp_massey(&sequence);
+        prop_assert_eq!(
+            Some(BFieldElement::ONE),
+            characteristic_polynomial.leading_coefficient()
+        );
+
+        let coefficients = characteristic_polynomial.coefficients();
+        for window in sequence.windows(coefficients.len()) {
+            let weighted_sum = coefficients
+                .iter()
+                .zip(window)
+                .fold(BFieldElement::ZERO, |acc, (&p, &s)| acc + p * s);
+            prop_assert!(weighted_sum.is_zero());
+        }
+    }
+
+    #[proptest]
+    fn shortest_recurrence_of_random_recurrent_sequence_is_found(
+        #[strategy(1_usize..30)] _order: usize,
+        #[strategy(vec(arb(), #_order))] tail: Vec<XFieldElement>,
+        #[strategy(vec(arb(), #_order))] initial_terms: Vec<XFieldElement>,
+    ) {
+        let characteristic_polynomial = Polynomial::new([tail, vec![XFieldElement::ONE]].concat());
+        let sequence = sequence(&characteristic_polynomial, &initial_terms, 2 * _order);
+        prop_assert_eq!(characteristic_polynomial, berlekamp_massey(&sequence));
+    }
+
+    #[proptest]
+    fn nth_term_agrees_with_stepping_through_recurrence(
+        #[strategy(0_usize..30)] _order: usize,
+        #[strategy(vec(arb(), #_order + 1))]
+        #[filter(!#coefficients[#_order].is_zero())]
+        coefficients: Vec<BFieldElement>,
+        #[strategy(vec(arb(), #_order))] initial_terms: Vec<BFieldElement>,
+        #[strategy(0_usize..300)] n: usize,
+    ) {
+        let characteristic_polynomial = Polynomial::new(coefficients);
+        let sequence = sequence(&characteristic_polynomial, &initial_terms, n + 1);
+        let nth_term = nth_term(&characteristic_polynomial, &initial_terms, n as u64);
+        prop_assert_eq!(sequence[n], nth_term);
+    }
+
+    #[proptest]
+    fn nth_term_of_geometric_sequence_is_power(
+        #[strategy(arb())] initial_term: BFieldElement,
+        #[strategy(arb())] ratio: BFieldElement,
+        n: u64,
+    ) {
+        let characteristic_polynomial = Polynomial::new(vec![-ratio, BFieldElement::ONE]);
+        let nth_term = nth_term(&characteristic_polynomial, &[initial_term], n);
+        prop_assert_eq!(initial_term * ratio.mod_pow_u64(n), nth_term);
+    }
+
+    #[test]
+    fn recurrence_found_by_berlekamp_massey_extrapolates_sequence() {
+        let squares = (0..10_u64).map(|i| bfe!(i * i)).collect::<Vec<_>>();
+        let characteristic_polynomial = berlekamp_massey(&squares);
+        assert_eq!(3, characteristic_polynomial.degree());
+
+        let term = nth_term(&characteristic_polynomial, &squares, 1 << 20);
+        assert_eq!(bfe!(1_u64 << 40), term);
+    }
+
+    #[test]
+    #[should_panic(expected = "initial terms")]
+    fn nth_term_panics_on_too_few_initial_terms() {
+        let fibonacci_polynomial = Polynomial::new(bfe_vec![-1, -1, 1]);
+        nth_term(&fibonacci_polynomial, &bfe_array![0], 5);
+    }
+}
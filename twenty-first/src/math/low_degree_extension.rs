@@ -0,0 +1,278 @@
+//! Batched low-degree extension of tables, as needed by [STARK][stark] provers.
+//!
+//! [stark]: https://eprint.iacr.org/2018/046
+
+use std::ops::MulAssign;
+
+use itertools::Itertools;
+use num_traits::Zero;
+use rayon::prelude::*;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::digest::Digest;
+use crate::math::ntt::NttPlan;
+use crate::math::tip5::Tip5;
+use crate::math::traits::FiniteField;
+
+/// Extend every column of a table from the trace domain to the (larger) extension domain.
+///
+/// Each column is interpreted as the evaluations of a polynomial on the trace domain
+/// `trace_offset·⟨ω_t⟩`, where ω_t is the primitive root of unity of order `trace_len`. The
+/// polynomial is interpolated and then evaluated on the extension domain
+/// `extension_offset·⟨ω_e⟩`, where ω_e is the primitive root of unity of order `extension_len`.
+/// All columns are processed in parallel.
+///
+/// # Example
+///
+/// ```
+/// # use twenty_first::prelude::*;
+/// # use twenty_first::math::low_degree_extension::LowDegreeExtension;
+/// let lde = LowDegreeExtension::new(bfe!(1), 4, BFieldElement::generator(), 16);
+/// let rows = vec![bfe_vec![1, 2], bfe_vec![3, 4], bfe_vec![5, 6], bfe_vec![7, 8]];
+/// let extended_rows = lde.extend_rows(&rows);
+/// assert_eq!(16, extended_rows.len());
+///
+/// let leafs = LowDegreeExtension::hash_rows(&extended_rows);
+/// let merkle_tree = MerkleTree::new::<CpuParallel>(&leafs).unwrap();
+/// assert_eq!(16, merkle_tree.num_leafs());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LowDegreeExtension {
+    trace_offset: BFieldElement,
+    trace_len: usize,
+    extension_offset: BFieldElement,
+    extension_len: usize,
+}
+
+impl LowDegreeExtension {
+    /// # Panics
+    ///
+    /// Panics if
+    /// - either length is not a power of two,
+    /// - the `trace_len` exceeds the `extension_len`,
+    /// - the `extension_len` exceeds [`u32::MAX`], or
+    /// - either offset is zero.
+    pub fn new(
+        trace_offset: BFieldElement,
+        trace_len: usize,
+        extension_offset: BFieldElement,
+        extension_len: usize,
+    ) -> Self {
+        assert!(
+            trace_len.is_power_of_two(),
+            "trace length must be a power of two"
+        );
+        assert!(
+            extension_len.is_power_of_two(),
+            "extension length must be a power of two"
+        );
+        assert!(
+            trace_len <= extension_len,
+            "trace length must not exceed extension length"
+        );
+        assert!(
+            u32::try_from(extension_len).is_ok(),
+            "extension length must not exceed u32::MAX"
+        );
+        assert!(!trace_offset.is_zero(), "trace offset must not be zero");
+        assert!(
+            !extension_offset.is_zero(),
+            "extension offset must not be zero"
+        );
+
+        Self {
+            trace_offset,
+            trace_len,
+            extension_offset,
+            extension_len,
+        }
+    }
+
+    pub fn trace_offset(&self) -> BFieldElement {
+        self.trace_offset
+    }
+
+    pub fn trace_len(&self) -> usize {
+        self.trace_len
+    }
+
+    pub fn extension_offset(&self) -> BFieldElement {
+        self.extension_offset
+    }
+
+    pub fn extension_len(&self) -> usize {
+        self.extension_len
+    }
+
+    /// Extend every column. Both input and output are column-major, _i.e._, each inner vector is
+    /// one column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any column's length is not the [trace length](Self::trace_len).
+    pub fn extend_columns<FF>(&self, columns: &[Vec<FF>]) -> Vec<Vec<FF>>
+    where
+        FF: FiniteField + MulAssign<BFieldElement>,
+    {
+        let trace_plan = NttPlan::cached(self.trace_len.ilog2());
+        let extension_plan = NttPlan::cached(self.extension_len.ilog2());
+
+        columns
+            .par_iter()
+            .map(|column| {
+                assert_eq!(self.trace_len, column.len(), "column length must match");
+                let mut extended_column = Vec::with_capacity(self.extension_len);
+                extended_column.extend_from_slice(column);
+                trace_plan.coset_inverse(&mut extended_column, self.trace_offset);
+                extended_column.resize(self.extension_len, FF::ZERO);
+                extension_plan.coset_forward(&mut extended_column, self.extension_offset);
+                extended_column
+            })
+            .collect()
+    }
+
+    /// Extend every column of a row-major table. Both input and output are row-major, _i.e._,
+    /// each inner vector is one row. The output can be [hashed row-wise](Self::hash_rows) to get
+    /// the leafs of a [`MerkleTree`](crate::prelude::MerkleTree).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of rows is not the [trace length](Self::trace_len) or if the rows'
+    /// lengths differ.
+    pub fn extend_rows<FF>(&self, rows: &[Vec<FF>]) -> Vec<Vec<FF>>
+    where
+        FF: FiniteField + MulAssign<BFieldElement>,
+    {
+        assert_eq!(self.trace_len, rows.len(), "number of rows must match");
+        let columns = transpose(rows, self.trace_len);
+        let extended_columns = self.extend_columns(&columns);
+        transpose(&extended_columns, self.extension_len)
+    }
+
+    /// Hash every row using [`Tip5`], in parallel.
+    pub fn hash_rows<FF>(rows: &[Vec<FF>]) -> Vec<Digest>
+    where
+        FF: BFieldCodec + Send + Sync,
+    {
+        rows.par_iter().map(Tip5::hash).collect()
+    }
+}
+
+/// Transpose a table, turning rows into columns or vice versa. Every inner vector of the table
+/// must have the same length. The `inner_len` is the length of the resulting inner vectors; it is
+/// only needed if the table is empty.
+fn transpose<FF: Copy + Send + Sync>(table: &[Vec<FF>], inner_len: usize) -> Vec<Vec<FF>> {
+    let Some(width) = table.iter().map(Vec::len).all_equal_value().ok() else {
+        assert!(table.is_empty(), "all rows must have the same length");
+        return vec![vec![]; inner_len];
+    };
+
+    (0..width)
+        .into_par_iter()
+        .map(|i| table.iter().map(|row| row[i]).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+    use test_strategy::Arbitrary;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, Arbitrary)]
+    struct LdeParameters {
+        #[strategy(0_u32..6)]
+        log2_trace_len: u32,
+
+        #[strategy(0_u32..4)]
+        log2_expansion_factor: u32,
+
+        #[strategy(arb())]
+        #[filter(!#trace_offset.is_zero())]
+        trace_offset: BFieldElement,
+
+        #[strategy(arb())]
+        #[filter(!#extension_offset.is_zero())]
+        extension_offset: BFieldElement,
+    }
+
+    impl LdeParameters {
+        fn lde(&self) -> LowDegreeExtension {
+            let trace_len = 1 << self.log2_trace_len;
+            let extension_len = trace_len << self.log2_expansion_factor;
+            LowDegreeExtension::new(
+                self.trace_offset,
+                trace_len,
+                self.extension_offset,
+                extension_len,
+            )
+        }
+    }
+
+    #[proptest(cases = 30)]
+    fn extended_columns_are_evaluations_of_interpolants(
+        params: LdeParameters,
+        #[strategy(vec(vec(arb(), 1 << #params.log2_trace_len), 0..5))] columns: Vec<
+            Vec<XFieldElement>,
+        >,
+    ) {
+        let lde = params.lde();
+        let extended_columns = lde.extend_columns(&columns);
+        prop_assert_eq!(columns.len(), extended_columns.len());
+
+        for (column, extended_column) in columns.into_iter().zip_eq(extended_columns) {
+            let polynomial = Polynomial::fast_coset_interpolate(lde.trace_offset(), &column);
+            let expected =
+                polynomial.fast_coset_evaluate(lde.extension_offset(), lde.extension_len());
+            prop_assert_eq!(expected, extended_column);
+        }
+    }
+
+    #[proptest(cases = 30)]
+    fn extending_rows_is_extending_columns_transposed(
+        params: LdeParameters,
+        #[strategy(0_usize..5)] _num_columns: usize,
+        #[strategy(vec(vec(arb(), #_num_columns), 1 << #params.log2_trace_len))] rows: Vec<
+            Vec<BFieldElement>,
+        >,
+    ) {
+        let lde = params.lde();
+        let columns = transpose(&rows, _num_columns);
+        let extended_columns = lde.extend_columns(&columns);
+        let extended_rows = lde.extend_rows(&rows);
+
+        prop_assert_eq!(lde.extension_len(), extended_rows.len());
+        prop_assert_eq!(
+            transpose(&extended_columns, lde.extension_len()),
+            extended_rows
+        );
+    }
+
+    #[proptest(cases = 30)]
+    fn hashing_rows_hashes_every_row(
+        #[strategy(vec(vec(arb(), 3), 0..10))] rows: Vec<Vec<XFieldElement>>,
+    ) {
+        let digests = LowDegreeExtension::hash_rows(&rows);
+        let expected = rows.iter().map(Tip5::hash).collect_vec();
+        prop_assert_eq!(expected, digests);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not exceed extension length")]
+    fn trace_length_must_not_exceed_extension_length() {
+        LowDegreeExtension::new(bfe!(1), 8, bfe!(1), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "column length must match")]
+    fn columns_must_have_trace_length() {
+        let lde = LowDegreeExtension::new(bfe!(1), 4, bfe!(7), 8);
+        lde.extend_columns(&[bfe_vec![1, 2]]);
+    }
+}
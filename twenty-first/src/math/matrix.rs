@@ -0,0 +1,870 @@
+//! Dense matrices over [finite fields](FiniteField), and the linear algebra on top of them.
+//!
+//! Most operations are based on [Gauss-Jordan elimination](Matrix::reduced_row_echelon_form).
+//! Multiplication and elimination of matrices with at least [`PARALLEL_THRESHOLD`] entries
+//! are parallelized using rayon.
+
+use std::ops::Add;
+use std::ops::Index;
+use std::ops::IndexMut;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+
+use arbitrary::Arbitrary;
+use arbitrary::Unstructured;
+use itertools::Itertools;
+use rayon::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::bfield_codec::BFieldCodecError;
+use crate::math::traits::FiniteField;
+
+pub mod sparse;
+pub mod structured;
+
+/// Matrices with at least this many entries are multiplied and row-reduced in parallel.
+pub const PARALLEL_THRESHOLD: usize = 1 << 14;
+
+/// The maximal number of rows, as well as the maximal number of columns, of a [`Matrix`]
+/// [constructed](Matrix::new) from given entries.
+///
+/// Matrices with one dimension 0 have no entries, however large the other dimension. Without
+/// this limit, the size of, for example, a [`Matrix::mul_vector`] result would be unbounded by
+/// the size of a decoded matrix.
+pub const MAX_DIMENSION: usize = 1 << 24;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+#[non_exhaustive]
+pub enum MatrixError {
+    #[error("expected {expected} entries, but got {actual}")]
+    InvalidNumEntries { expected: usize, actual: usize },
+
+    #[error("dimensions {num_rows}×{num_cols} exceed the maximum of {MAX_DIMENSION}")]
+    DimensionTooLarge { num_rows: usize, num_cols: usize },
+
+    #[error("all rows must have the same length")]
+    RaggedRows,
+
+    #[error("the matrix is not square")]
+    NotSquare,
+
+    #[error("the matrix is singular")]
+    Singular,
+
+    #[error("expected a vector of length {expected}, but got length {actual}")]
+    DimensionMismatch { expected: usize, actual: usize },
+
+    #[error("the linear system has no solution")]
+    NoSolution,
+
+    #[error("column index {index} is out of bounds for a matrix with {num_cols} columns")]
+    ColumnIndexOutOfBounds { index: usize, num_cols: usize },
+
+    #[error("the row points and column points of a Cauchy matrix must be disjoint")]
+    OverlappingCauchyPoints,
+}
+
+/// A dense matrix, stored in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedMatrix<FF>")]
+pub struct Matrix<FF> {
+    num_rows: usize,
+    num_cols: usize,
+    entries: Vec<FF>,
+}
+
+/// Helper for deserialization, which must check the matrix' dimensions.
+#[derive(Deserialize)]
+struct UncheckedMatrix<FF> {
+    num_rows: usize,
+    num_cols: usize,
+    entries: Vec<FF>,
+}
+
+impl<FF> TryFrom<UncheckedMatrix<FF>> for Matrix<FF> {
+    type Error = MatrixError;
+
+    fn try_from(matrix: UncheckedMatrix<FF>) -> Result<Self, Self::Error> {
+        Self::new(matrix.num_rows, matrix.num_cols, matrix.entries)
+    }
+}
+
+/// The result of [Gauss-Jordan elimination](Matrix::reduced_row_echelon_form).
+struct RowReduction<FF> {
+    pivot_columns: Vec<usize>,
+
+    /// The product of all pivots and the sign of the row permutation. For a square matrix of
+    /// full rank, this is its determinant.
+    determinant: FF,
+}
+
+impl<FF> Matrix<FF> {
+    /// A matrix with the given dimensions and `entries` in row-major order.
+    ///
+    /// # Errors
+    ///
+    /// Errors if
+    /// - either dimension exceeds [`MAX_DIMENSION`], or
+    /// - the number of entries is not the product of the dimensions.
+    pub fn new(num_rows: usize, num_cols: usize, entries: Vec<FF>) -> Result<Self, MatrixError> {
+        let too_large = MatrixError::DimensionTooLarge { num_rows, num_cols };
+        if num_rows > MAX_DIMENSION || num_cols > MAX_DIMENSION {
+            return Err(too_large);
+        }
+
+        let expected = num_rows.checked_mul(num_cols).ok_or(too_large)?;
+        if entries.len() != expected {
+            let actual = entries.len();
+            return Err(MatrixError::InvalidNumEntries { expected, actual });
+        }
+
+        Ok(Self {
+            num_rows,
+            num_cols,
+            entries,
+        })
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.num_rows == self.num_cols
+    }
+
+    /// The entries in row-major order.
+    pub fn entries(&self) -> &[FF] {
+        &self.entries
+    }
+
+    pub fn row(&self, row: usize) -> &[FF] {
+        &self.entries[row * self.num_cols..(row + 1) * self.num_cols]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[FF]> {
+        // `chunks_exact` panics on chunk size 0
+        (0..self.num_rows).map(|row| self.row(row))
+    }
+
+    fn swap_rows(&mut self, row: usize, other_row: usize) {
+        if row == other_row {
+            return;
+        }
+        let (row, other_row) = (row.min(other_row), row.max(other_row));
+        let (head, tail) = self.entries.split_at_mut(other_row * self.num_cols);
+        head[row * self.num_cols..(row + 1) * self.num_cols]
+            .swap_with_slice(&mut tail[..self.num_cols]);
+    }
+}
+
+impl<FF: FiniteField> Matrix<FF> {
+    pub fn zeros(num_rows: usize, num_cols: usize) -> Self {
+        let entries = vec![FF::ZERO; num_rows * num_cols];
+        Self {
+            num_rows,
+            num_cols,
+            entries,
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut identity = Self::zeros(n, n);
+        for i in 0..n {
+            identity[(i, i)] = FF::ONE;
+        }
+        identity
+    }
+
+    /// A matrix with the given rows, all of which must have the same length.
+    pub fn from_rows(rows: &[Vec<FF>]) -> Result<Self, MatrixError> {
+        let num_cols = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != num_cols) {
+            return Err(MatrixError::RaggedRows);
+        }
+
+        let entries = rows.concat();
+        Self::new(rows.len(), num_cols, entries)
+    }
+
+    pub fn column(&self, col: usize) -> Vec<FF> {
+        self.rows().map(|row| row[col]).collect()
+    }
+
+    pub fn transpose(&self) -> Self {
+        let entries = (0..self.num_cols)
+            .flat_map(|col| self.rows().map(move |row| row[col]))
+            .collect();
+        Self {
+            num_rows: self.num_cols,
+            num_cols: self.num_rows,
+            entries,
+        }
+    }
+
+    /// The matrix-vector product `self · vector`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the vector is not the number of columns.
+    pub fn mul_vector(&self, vector: &[FF]) -> Vec<FF> {
+        assert_eq!(self.num_cols, vector.len(), "dimension mismatch");
+        self.rows().map(|row| Self::dot(row, vector)).collect()
+    }
+
+    fn dot(lhs: &[FF], rhs: &[FF]) -> FF {
+        lhs.iter()
+            .zip(rhs)
+            .map(|(&l, &r)| l * r)
+            .fold(FF::ZERO, Add::add)
+    }
+
+    fn is_large(&self) -> bool {
+        self.entries.len() >= PARALLEL_THRESHOLD
+    }
+
+    fn mul_matrix(&self, other: &Self) -> Self {
+        assert_eq!(
+            self.num_cols, other.num_rows,
+            "number of columns of the left-hand side must equal number of rows of the \
+            right-hand side"
+        );
+
+        // multiply with the transpose to make the inner loop cache-friendly
+        let other_transposed = other.transpose();
+        let row_times_other = |row: &[FF]| {
+            other_transposed
+                .rows()
+                .map(|col| Self::dot(row, col))
+                .collect_vec()
+        };
+
+        let entries = if self.is_large() || other.is_large() {
+            (0..self.num_rows)
+                .into_par_iter()
+                .flat_map(|row| row_times_other(self.row(row)))
+                .collect()
+        } else {
+            self.rows().flat_map(row_times_other).collect()
+        };
+
+        Self {
+            num_rows: self.num_rows,
+            num_cols: other.num_cols,
+            entries,
+        }
+    }
+
+    /// Bring the matrix into reduced row echelon form, returning the indices of the pivot
+    /// columns alongside. The number of pivot columns is the [rank](Self::rank) of the matrix.
+    pub fn reduced_row_echelon_form(&self) -> (Self, Vec<usize>) {
+        let mut matrix = self.clone();
+        let RowReduction { pivot_columns, .. } = matrix.row_reduce();
+        (matrix, pivot_columns)
+    }
+
+    /// In-place Gauss-Jordan elimination.
+    fn row_reduce(&mut self) -> RowReduction<FF> {
+        let mut pivot_columns = vec![];
+        let mut determinant = FF::ONE;
+
+        for col in 0..self.num_cols {
+            let pivot_row = pivot_columns.len();
+            if pivot_row == self.num_rows {
+                break;
+            }
+            let is_candidate = |&row: &usize| !self[(row, col)].is_zero();
+            let Some(candidate_row) = (pivot_row..self.num_rows).find(is_candidate) else {
+                continue;
+            };
+            if candidate_row != pivot_row {
+                self.swap_rows(candidate_row, pivot_row);
+                determinant = -determinant;
+            }
+
+            let pivot = self[(pivot_row, col)];
+            determinant *= pivot;
+            let pivot_inverse = pivot.inverse();
+            let num_cols = self.num_cols;
+            let pivot_row_entries = &mut self.entries[pivot_row * num_cols..][..num_cols];
+            for entry in &mut pivot_row_entries[col..] {
+                *entry *= pivot_inverse;
+            }
+
+            // Entries left of `col` are zero in the pivot row.
+            let pivot_row_tail = pivot_row_entries[col..].to_vec();
+            let eliminate = |(row, entries): (usize, &mut [FF])| {
+                let factor = entries[col];
+                if row == pivot_row || factor.is_zero() {
+                    return;
+                }
+                for (entry, &pivot_entry) in entries[col..].iter_mut().zip(&pivot_row_tail) {
+                    *entry -= factor * pivot_entry;
+                }
+            };
+            if self.is_large() {
+                self.entries
+                    .par_chunks_mut(num_cols)
+                    .enumerate()
+                    .for_each(eliminate);
+            } else {
+                self.entries
+                    .chunks_mut(num_cols)
+                    .enumerate()
+                    .for_each(eliminate);
+            }
+
+            pivot_columns.push(col);
+        }
+
+        RowReduction {
+            pivot_columns,
+            determinant,
+        }
+    }
+
+    pub fn rank(&self) -> usize {
+        self.reduced_row_echelon_form().1.len()
+    }
+
+    pub fn determinant(&self) -> Result<FF, MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare);
+        }
+
+        let mut matrix = self.clone();
+        let reduction = matrix.row_reduce();
+        if reduction.pivot_columns.len() < self.num_rows {
+            return Ok(FF::ZERO);
+        }
+        Ok(reduction.determinant)
+    }
+
+    pub fn inverse(&self) -> Result<Self, MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare);
+        }
+
+        let n = self.num_rows;
+        let mut augmented = self.hstack(&Self::identity(n));
+        let reduction = augmented.row_reduce();
+        if reduction
+            .pivot_columns
+            .iter()
+            .take_while(|&&col| col < n)
+            .count()
+            < n
+        {
+            return Err(MatrixError::Singular);
+        }
+
+        let entries = augmented.rows().flat_map(|row| &row[n..]).copied();
+        Ok(Self {
+            num_rows: n,
+            num_cols: n,
+            entries: entries.collect(),
+        })
+    }
+
+    /// A basis of the kernel (also known as the null space), _i.e._, of all vectors `v` with
+    /// `self · v = 0`.
+    pub fn kernel_basis(&self) -> Vec<Vec<FF>> {
+        let (reduced, pivot_columns) = self.reduced_row_echelon_form();
+        let free_columns = (0..self.num_cols).filter(|col| !pivot_columns.contains(col));
+
+        free_columns
+            .map(|free_col| {
+                let mut basis_vector = vec![FF::ZERO; self.num_cols];
+                basis_vector[free_col] = FF::ONE;
+                for (row, &pivot_col) in pivot_columns.iter().enumerate() {
+                    basis_vector[pivot_col] = -reduced[(row, free_col)];
+                }
+                basis_vector
+            })
+            .collect()
+    }
+
+    /// Some solution `x` of the linear system `self · x = rhs`. If the system is
+    /// underdetermined, all free variables are set to zero. Add any linear combination of the
+    /// [kernel basis](Self::kernel_basis) to get all other solutions.
+    pub fn solve(&self, rhs: &[FF]) -> Result<Vec<FF>, MatrixError> {
+        if rhs.len() != self.num_rows {
+            let expected = self.num_rows;
+            let actual = rhs.len();
+            return Err(MatrixError::DimensionMismatch { expected, actual });
+        }
+
+        let rhs_column = Self {
+            num_rows: rhs.len(),
+            num_cols: 1,
+            entries: rhs.to_vec(),
+        };
+        let mut augmented = self.hstack(&rhs_column);
+        let RowReduction { pivot_columns, .. } = augmented.row_reduce();
+        if pivot_columns.last() == Some(&self.num_cols) {
+            return Err(MatrixError::NoSolution);
+        }
+
+        let mut solution = vec![FF::ZERO; self.num_cols];
+        for (row, pivot_col) in pivot_columns.into_iter().enumerate() {
+            solution[pivot_col] = augmented[(row, self.num_cols)];
+        }
+        Ok(solution)
+    }
+
+    /// The matrix `[self | other]`.
+    fn hstack(&self, other: &Self) -> Self {
+        debug_assert_eq!(self.num_rows, other.num_rows);
+        let entries = self
+            .rows()
+            .zip(other.rows())
+            .flat_map(|(row, other_row)| [row, other_row].concat())
+            .collect();
+
+        Self {
+            num_rows: self.num_rows,
+            num_cols: self.num_cols + other.num_cols,
+            entries,
+        }
+    }
+}
+
+impl<FF> Index<(usize, usize)> for Matrix<FF> {
+    type Output = FF;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(col < self.num_cols, "column index out of bounds");
+        &self.entries[row * self.num_cols + col]
+    }
+}
+
+impl<FF> IndexMut<(usize, usize)> for Matrix<FF> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(col < self.num_cols, "column index out of bounds");
+        &mut self.entries[row * self.num_cols + col]
+    }
+}
+
+impl<FF: FiniteField> Mul for &Matrix<FF> {
+    type Output = Matrix<FF>;
+
+    fn mul(self, other: Self) -> Matrix<FF> {
+        self.mul_matrix(other)
+    }
+}
+
+impl<FF: FiniteField> Mul for Matrix<FF> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.mul_matrix(&other)
+    }
+}
+
+impl<FF: FiniteField> Mul<FF> for Matrix<FF> {
+    type Output = Self;
+
+    fn mul(mut self, scalar: FF) -> Self {
+        for entry in &mut self.entries {
+            *entry *= scalar;
+        }
+        self
+    }
+}
+
+impl<FF: FiniteField> Add for Matrix<FF> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        assert_eq!(
+            (self.num_rows, self.num_cols),
+            (other.num_rows, other.num_cols),
+            "dimension mismatch"
+        );
+        for (entry, other_entry) in self.entries.iter_mut().zip(other.entries) {
+            *entry += other_entry;
+        }
+        self
+    }
+}
+
+impl<FF: FiniteField> Neg for Matrix<FF> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        for entry in &mut self.entries {
+            *entry = -*entry;
+        }
+        self
+    }
+}
+
+impl<FF: FiniteField> Sub for Matrix<FF> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<FF: BFieldCodec + Clone> BFieldCodec for Matrix<FF> {
+    type Error = BFieldCodecError;
+
+    fn decode(sequence: &[BFieldElement]) -> Result<Box<Self>, Self::Error> {
+        let (num_rows, num_cols, entries) = *<(u64, u64, Vec<FF>)>::decode(sequence)
+            .map_err(|e| BFieldCodecError::InnerDecodingFailure(e.into()))?;
+        let num_rows = usize::try_from(num_rows)?;
+        let num_cols = usize::try_from(num_cols)?;
+        let matrix = Self::new(num_rows, num_cols, entries)
+            .map_err(|e| BFieldCodecError::InnerDecodingFailure(e.into()))?;
+
+        Ok(Box::new(matrix))
+    }
+
+    fn encode(&self) -> Vec<BFieldElement> {
+        let num_rows = self.num_rows as u64;
+        let num_cols = self.num_cols as u64;
+        (num_rows, num_cols, self.entries.clone()).encode()
+    }
+
+    fn static_length() -> Option<usize> {
+        None
+    }
+}
+
+impl<'a, FF> Arbitrary<'a> for Matrix<FF>
+where
+    FF: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let num_rows = u.int_in_range(0..=10)?;
+        let num_cols = u.int_in_range(0..=10)?;
+        let entries = (0..num_rows * num_cols)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<_>>()?;
+
+        Ok(Self {
+            num_rows,
+            num_cols,
+            entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::iproduct;
+    use num_traits::ConstOne;
+    use num_traits::ConstZero;
+    use num_traits::Zero;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::bfe_array;
+    use crate::bfe_vec;
+    use crate::math::tip5;
+    use crate::math::x_field_element::XFieldElement;
+
+    /// A strategy for matrices of the given dimensions.
+    fn matrix<FF>(num_rows: usize, num_cols: usize) -> impl Strategy<Value = Matrix<FF>>
+    where
+        FF: FiniteField + for<'a> arbitrary::Arbitrary<'a> + 'static,
+    {
+        vec(arb(), num_rows * num_cols)
+            .prop_map(move |entries| Matrix::new(num_rows, num_cols, entries).unwrap())
+    }
+
+    /// A random square matrix of low rank, with all columns in the span of `rank` vectors.
+    fn low_rank_matrix(n: usize, rank: usize) -> impl Strategy<Value = Matrix<BFieldElement>> {
+        (matrix(n, rank), matrix(rank, n)).prop_map(|(left, right)| left * right)
+    }
+
+    #[test]
+    fn constructing_matrix_with_wrong_number_of_entries_fails() {
+        let Err(err) = Matrix::new(2, 3, bfe_vec![1, 2, 3, 4, 5]) else {
+            panic!("constructing a matrix with too few entries must fail");
+        };
+        let expected = MatrixError::InvalidNumEntries {
+            expected: 6,
+            actual: 5,
+        };
+        assert_eq!(expected, err);
+
+        let rows = [bfe_vec![1, 2], bfe_vec![3]];
+        assert_eq!(Err(MatrixError::RaggedRows), Matrix::from_rows(&rows));
+    }
+
+    #[proptest]
+    fn rows_and_columns_are_consistent_with_indexing(
+        #[strategy(1_usize..10)] _num_rows: usize,
+        #[strategy(1_usize..10)] _num_cols: usize,
+        #[strategy(matrix(#_num_rows, #_num_cols))] matrix: Matrix<BFieldElement>,
+    ) {
+        let rows = matrix.rows().map(<[_]>::to_vec).collect_vec();
+        prop_assert_eq!(&matrix, &Matrix::from_rows(&rows)?);
+        for (row, col) in iproduct!(0..matrix.num_rows(), 0..matrix.num_cols()) {
+            prop_assert_eq!(matrix[(row, col)], matrix.row(row)[col]);
+            prop_assert_eq!(matrix[(row, col)], matrix.column(col)[row]);
+            prop_assert_eq!(matrix[(row, col)], matrix.transpose()[(col, row)]);
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn matrix_multiplication_is_associative_and_has_identity(
+        #[strategy(0_usize..8)] _n: usize,
+        #[strategy(0_usize..8)] _m: usize,
+        #[strategy(0_usize..8)] _k: usize,
+        #[strategy(0_usize..8)] _l: usize,
+        #[strategy(matrix(#_n, #_m))] a: Matrix<XFieldElement>,
+        #[strategy(matrix(#_m, #_k))] b: Matrix<XFieldElement>,
+        #[strategy(matrix(#_k, #_l))] c: Matrix<XFieldElement>,
+    ) {
+        prop_assert_eq!(&(&a * &b) * &c, &a * &(&b * &c));
+        prop_assert_eq!(&a, &(&a * &Matrix::identity(a.num_cols())));
+        prop_assert_eq!(&a, &(&Matrix::identity(a.num_rows()) * &a));
+    }
+
+    #[proptest(cases = 50)]
+    fn transpose_of_product_is_product_of_transposes(
+        #[strategy(0_usize..8)] _n: usize,
+        #[strategy(0_usize..8)] _m: usize,
+        #[strategy(0_usize..8)] _k: usize,
+        #[strategy(matrix(#_n, #_m))] a: Matrix<BFieldElement>,
+        #[strategy(matrix(#_m, #_k))] b: Matrix<BFieldElement>,
+    ) {
+        prop_assert_eq!((&a * &b).transpose(), &b.transpose() * &a.transpose());
+    }
+
+    #[proptest(cases = 50)]
+    fn matrix_vector_product_agrees_with_matrix_product(
+        #[strategy(0_usize..8)] _n: usize,
+        #[strategy(0_usize..8)] _m: usize,
+        #[strategy(matrix(#_n, #_m))] a: Matrix<BFieldElement>,
+        #[strategy(vec(arb(), #_m))] v: Vec<BFieldElement>,
+    ) {
+        let v_as_matrix = Matrix::new(v.len(), 1, v.clone())?;
+        let product = &a * &v_as_matrix;
+        prop_assert_eq!(product.entries(), a.mul_vector(&v));
+    }
+
+    #[proptest(cases = 50)]
+    fn addition_and_scalar_multiplication_distribute(
+        #[strategy(0_usize..8)] _n: usize,
+        #[strategy(0_usize..8)] _m: usize,
+        #[strategy(matrix(#_n, #_m))] a: Matrix<BFieldElement>,
+        #[strategy(matrix(#_n, #_m))] b: Matrix<BFieldElement>,
+        #[strategy(arb())] scalar: BFieldElement,
+    ) {
+        let sum_times_scalar = (a.clone() + b.clone()) * scalar;
+        prop_assert_eq!(sum_times_scalar, a.clone() * scalar + b.clone() * scalar);
+        prop_assert_eq!(a.clone() - a, Matrix::zeros(_n, _m));
+    }
+
+    #[proptest(cases = 3)]
+    fn parallel_multiplication_agrees_with_sequential_multiplication(
+        #[strategy(matrix(130, 130))] a: Matrix<BFieldElement>,
+        #[strategy(matrix(130, 3))] b: Matrix<BFieldElement>,
+    ) {
+        prop_assert!(a.is_large());
+        prop_assert!(!b.is_large());
+
+        let product = &a * &b;
+        for col in 0..b.num_cols() {
+            prop_assert_eq!(product.column(col), a.mul_vector(&b.column(col)));
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn reduced_row_echelon_form_is_reduced(
+        #[strategy(0_usize..8)] _n: usize,
+        #[strategy(0_usize..8)] _rank: usize,
+        #[strategy(low_rank_matrix(#_n, #_rank))] matrix: Matrix<BFieldElement>,
+    ) {
+        let (reduced, pivot_columns) = matrix.reduced_row_echelon_form();
+        prop_assert!(pivot_columns.len() <= _rank);
+        prop_assert!(pivot_columns.is_sorted());
+
+        for (row, &pivot_col) in pivot_columns.iter().enumerate() {
+            prop_assert!(reduced.row(row)[..pivot_col].iter().all(Zero::is_zero));
+            let mut column = reduced.column(pivot_col);
+            prop_assert_eq!(BFieldElement::ONE, column[row]);
+            column[row] = BFieldElement::ZERO;
+            prop_assert!(column.iter().all(Zero::is_zero));
+        }
+        for row in pivot_columns.len()..reduced.num_rows() {
+            prop_assert!(reduced.row(row).iter().all(Zero::is_zero));
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn rank_plus_kernel_dimension_is_number_of_columns(
+        #[strategy(0_usize..8)] _num_rows: usize,
+        #[strategy(0_usize..8)] _num_cols: usize,
+        #[strategy(matrix(#_num_rows, #_num_cols))] matrix: Matrix<XFieldElement>,
+    ) {
+        let kernel_basis = matrix.kernel_basis();
+        prop_assert_eq!(matrix.num_cols(), matrix.rank() + kernel_basis.len());
+        for vector in kernel_basis {
+            prop_assert!(matrix.mul_vector(&vector).iter().all(Zero::is_zero));
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn kernel_of_low_rank_matrix_has_expected_dimension(
+        #[strategy(1_usize..8)] _n: usize,
+        #[strategy(0_usize..#_n)] _rank: usize,
+        #[strategy(low_rank_matrix(#_n, #_rank))] matrix: Matrix<BFieldElement>,
+    ) {
+        prop_assert!(matrix.rank() <= _rank);
+        prop_assert!(!matrix.kernel_basis().is_empty());
+        prop_assert_eq!(BFieldElement::ZERO, matrix.determinant()?);
+        prop_assert_eq!(Err(MatrixError::Singular), matrix.inverse());
+    }
+
+    #[proptest(cases = 50)]
+    fn determinant_is_multiplicative(
+        #[strategy(0_usize..8)] _n: usize,
+        #[strategy(matrix(#_n, #_n))] a: Matrix<XFieldElement>,
+        #[strategy(matrix(#_n, #_n))] b: Matrix<XFieldElement>,
+    ) {
+        let product_determinant = (&a * &b).determinant()?;
+        prop_assert_eq!(a.determinant()? * b.determinant()?, product_determinant);
+        prop_assert_eq!(a.determinant()?, a.transpose().determinant()?);
+    }
+
+    #[test]
+    fn determinant_of_small_matrices_agrees_with_explicit_formulae() {
+        let [a, b, c, d] = [3, 1, 4, 1].map(BFieldElement::new);
+        let matrix = Matrix::new(2, 2, vec![a, b, c, d]).unwrap();
+        assert_eq!(a * d - b * c, matrix.determinant().unwrap());
+
+        let swapped = Matrix::new(2, 2, vec![c, d, a, b]).unwrap();
+        assert_eq!(b * c - a * d, swapped.determinant().unwrap());
+
+        assert_eq!(Ok(BFieldElement::ONE), Matrix::identity(0).determinant());
+        assert_eq!(
+            Err(MatrixError::NotSquare),
+            Matrix::<BFieldElement>::zeros(2, 3).determinant()
+        );
+    }
+
+    #[proptest(cases = 50)]
+    fn inverse_is_inverse(
+        #[strategy(0_usize..8)] _n: usize,
+        #[strategy(matrix(#_n, #_n))]
+        #[filter(!#matrix.determinant().unwrap().is_zero())]
+        matrix: Matrix<BFieldElement>,
+    ) {
+        let inverse = matrix.inverse()?;
+        prop_assert_eq!(Matrix::identity(_n), &matrix * &inverse);
+        prop_assert_eq!(Matrix::identity(_n), &inverse * &matrix);
+    }
+
+    #[proptest(cases = 50)]
+    fn solution_of_linear_system_solves_system(
+        #[strategy(0_usize..8)] _num_rows: usize,
+        #[strategy(0_usize..8)] _num_cols: usize,
+        #[strategy(matrix(#_num_rows, #_num_cols))] matrix: Matrix<BFieldElement>,
+        #[strategy(vec(arb(), #_num_cols))] x: Vec<BFieldElement>,
+    ) {
+        let rhs = matrix.mul_vector(&x);
+        let solution = matrix.solve(&rhs)?;
+        prop_assert_eq!(rhs, matrix.mul_vector(&solution));
+    }
+
+    #[test]
+    fn inconsistent_linear_system_has_no_solution() {
+        let matrix = Matrix::from_rows(&[bfe_vec![1, 2], bfe_vec![2, 4]]).unwrap();
+        assert_eq!(
+            Err(MatrixError::NoSolution),
+            matrix.solve(&bfe_array![1, 1])
+        );
+
+        let expected = MatrixError::DimensionMismatch {
+            expected: 2,
+            actual: 3,
+        };
+        assert_eq!(Err(expected), matrix.solve(&bfe_array![1, 1, 1]));
+    }
+
+    #[proptest(cases = 3)]
+    fn parallel_elimination_agrees_with_matrix_product(
+        #[strategy(matrix(130, 130))] matrix: Matrix<BFieldElement>,
+        #[strategy(vec(arb(), 130))] x: Vec<BFieldElement>,
+    ) {
+        prop_assert!(matrix.is_large());
+        let rhs = matrix.mul_vector(&x);
+        prop_assert_eq!(&rhs, &matrix.mul_vector(&matrix.solve(&rhs)?));
+    }
+
+    #[test]
+    fn tip5_mds_matrix_is_invertible_and_has_non_zero_minors_of_size_up_to_two() {
+        let n = tip5::STATE_SIZE;
+        let first_column = tip5::MDS_MATRIX_FIRST_COLUMN.map(BFieldElement::from);
+        let entries = iproduct!(0..n, 0..n)
+            .map(|(row, col)| first_column[(row + n - col) % n])
+            .collect();
+        let mds = Matrix::new(n, n, entries).unwrap();
+        assert!(!mds.determinant().unwrap().is_zero());
+
+        assert!(mds.entries().iter().all(|entry| !entry.is_zero()));
+        for (rows, cols) in iproduct!((0..n).combinations(2), (0..n).combinations(2)) {
+            let mds = &mds;
+            let minor = rows
+                .iter()
+                .flat_map(|&row| cols.iter().map(move |&col| mds[(row, col)]))
+                .collect();
+            let minor = Matrix::new(2, 2, minor).unwrap();
+            assert!(!minor.determinant().unwrap().is_zero());
+        }
+    }
+
+    #[proptest]
+    fn bfield_codec_round_trip(#[strategy(arb())] matrix: Matrix<XFieldElement>) {
+        let encoding = matrix.encode();
+        prop_assert_eq!(&matrix, &*Matrix::decode(&encoding)?);
+    }
+
+    #[proptest]
+    fn serde_round_trip(#[strategy(arb())] matrix: Matrix<BFieldElement>) {
+        let json = serde_json::to_string(&matrix)?;
+        prop_assert_eq!(matrix, serde_json::from_str(&json)?);
+    }
+
+    #[test]
+    fn decoding_matrix_with_wrong_dimensions_fails() {
+        let encoding = (2_u64, 3_u64, bfe_vec![1, 2, 3, 4, 5]).encode();
+        assert!(Matrix::<BFieldElement>::decode(&encoding).is_err());
+
+        let json = r#"{"num_rows":2,"num_cols":3,"entries":[1,2,3,4,5]}"#;
+        assert!(serde_json::from_str::<Matrix<BFieldElement>>(json).is_err());
+    }
+
+    #[test]
+    fn decoding_matrix_with_huge_dimensions_fails() {
+        let huge_dimensions: [(u64, u64); 3] = [(1 << 32, 1 << 32), (1 << 62, 0), (0, 1 << 62)];
+        for (num_rows, num_cols) in huge_dimensions {
+            let encoding = (num_rows, num_cols, Vec::<BFieldElement>::new()).encode();
+            assert!(Matrix::<BFieldElement>::decode(&encoding).is_err());
+        }
+
+        let err = Matrix::<BFieldElement>::new(MAX_DIMENSION + 1, 0, vec![]).unwrap_err();
+        let expected = MatrixError::DimensionTooLarge {
+            num_rows: MAX_DIMENSION + 1,
+            num_cols: 0,
+        };
+        assert_eq!(expected, err);
+    }
+}
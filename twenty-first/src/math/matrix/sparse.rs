@@ -0,0 +1,320 @@
+//! Sparse matrices, and [Wiedemann's algorithm](SparseMatrix::solve) for solving linear systems
+//! with them.
+
+use std::ops::MulAssign;
+
+use rand::distributions::Distribution;
+use rand::distributions::Standard;
+use rand::Rng;
+
+use super::Matrix;
+use super::MatrixError;
+use crate::math::b_field_element::BFieldElement;
+use crate::math::linear_recurrence::berlekamp_massey;
+use crate::math::traits::FiniteField;
+
+/// The number of random projections [`SparseMatrix::solve`] tries before concluding that the
+/// matrix is singular.
+const MAX_NUM_WIEDEMANN_ATTEMPTS: usize = 4;
+
+/// A matrix that stores only its non-zero entries, as `(column, value)` pairs per row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMatrix<FF> {
+    num_cols: usize,
+    rows: Vec<Vec<(usize, FF)>>,
+}
+
+impl<FF> SparseMatrix<FF> {
+    /// A matrix with the given number of columns and, for every row, the `(column, value)` pairs
+    /// of its non-zero entries. Values of pairs with the same column are added.
+    pub fn new(num_cols: usize, rows: Vec<Vec<(usize, FF)>>) -> Result<Self, MatrixError> {
+        let column_indices = rows.iter().flatten().map(|&(col, _)| col);
+        if let Some(index) = column_indices.max().filter(|&col| col >= num_cols) {
+            return Err(MatrixError::ColumnIndexOutOfBounds { index, num_cols });
+        }
+
+        Ok(Self { num_cols, rows })
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.num_rows() == self.num_cols
+    }
+
+    /// The `(column, value)` pairs of the given row.
+    pub fn row(&self, row: usize) -> &[(usize, FF)] {
+        &self.rows[row]
+    }
+
+    pub fn num_stored_entries(&self) -> usize {
+        self.rows.iter().map(Vec::len).sum()
+    }
+}
+
+impl<FF: FiniteField> SparseMatrix<FF> {
+    /// # Panics
+    ///
+    /// Panics if the length of the vector is not the number of columns.
+    pub fn mul_vector(&self, vector: &[FF]) -> Vec<FF> {
+        assert_eq!(self.num_cols, vector.len(), "dimension mismatch");
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .fold(FF::ZERO, |acc, &(col, value)| acc + value * vector[col])
+            })
+            .collect()
+    }
+
+    pub fn to_dense(&self) -> Matrix<FF> {
+        let mut dense = Matrix::zeros(self.num_rows(), self.num_cols);
+        for (row_index, row) in self.rows.iter().enumerate() {
+            for &(col, value) in row {
+                dense[(row_index, col)] += value;
+            }
+        }
+
+        dense
+    }
+}
+
+impl<FF> SparseMatrix<FF>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+    Standard: Distribution<FF>,
+{
+    /// The unique solution `x` of the linear system `self · x = rhs`, computed with
+    /// [Wiedemann's algorithm][wiedemann].
+    ///
+    /// For a random vector `u`, the [shortest linear recurrence](berlekamp_massey) of the
+    /// sequence `u·A^i·rhs` is, with high probability, the minimal polynomial `m` of `A` with
+    /// respect to `rhs`. From `m(A)·rhs = 0` and `m(0) ≠ 0` follows `x` as a linear combination
+    /// of the vectors `A^i·rhs`. This requires O(n) matrix-vector products and O(n) memory,
+    /// where `n` is the number of rows, and never materializes a dense matrix.
+    ///
+    /// # Errors
+    ///
+    /// Errors if
+    /// - the matrix is not square,
+    /// - the length of `rhs` is not the number of rows, or
+    /// - the matrix is singular. Since the algorithm is randomized, this error is also returned,
+    ///   with negligible probability, for non-singular matrices.
+    ///
+    /// [wiedemann]: https://en.wikipedia.org/wiki/Block_Wiedemann_algorithm
+    pub fn solve(&self, rhs: &[FF]) -> Result<Vec<FF>, MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare);
+        }
+        if rhs.len() != self.num_rows() {
+            let expected = self.num_rows();
+            let actual = rhs.len();
+            return Err(MatrixError::DimensionMismatch { expected, actual });
+        }
+        if rhs.iter().all(|r| r.is_zero()) {
+            return Ok(rhs.to_vec());
+        }
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..MAX_NUM_WIEDEMANN_ATTEMPTS {
+            let projection = (0..self.num_cols).map(|_| rng.gen()).collect::<Vec<FF>>();
+            if let Some(solution) = self.wiedemann_attempt(&projection, rhs) {
+                return Ok(solution);
+            }
+        }
+
+        Err(MatrixError::Singular)
+    }
+
+    /// Some candidate solution of `self · x = rhs`, if the given projection leads to a
+    /// minimal polynomial with non-zero constant term and the candidate is correct.
+    fn wiedemann_attempt(&self, projection: &[FF], rhs: &[FF]) -> Option<Vec<FF>> {
+        let dot = |vector: &[FF]| {
+            projection
+                .iter()
+                .zip(vector)
+                .fold(FF::ZERO, |acc, (&u, &v)| acc + u * v)
+        };
+
+        let n = self.num_rows();
+        let mut krylov_vector = rhs.to_vec();
+        let mut projected_sequence = Vec::with_capacity(2 * n);
+        for _ in 0..2 * n {
+            projected_sequence.push(dot(&krylov_vector));
+            krylov_vector = self.mul_vector(&krylov_vector);
+        }
+
+        // m(X) = X^L + m_{L-1}·X^{L-1} + … + m_0 and m(A)·rhs = 0 give
+        // x = -(1/m_0)·(A^{L-1} + m_{L-1}·A^{L-2} + … + m_1)·rhs.
+        let minimal_polynomial = berlekamp_massey(&projected_sequence);
+        let (&constant_term, higher_terms) = minimal_polynomial.coefficients().split_first()?;
+        if constant_term.is_zero() {
+            return None;
+        }
+
+        let mut solution = vec![FF::ZERO; n];
+        for &coefficient in higher_terms.iter().rev() {
+            solution = self.mul_vector(&solution);
+            for (s, &r) in solution.iter_mut().zip(rhs) {
+                *s += coefficient * r;
+            }
+        }
+        let scale = -constant_term.inverse();
+        for s in &mut solution {
+            *s *= scale;
+        }
+
+        (self.mul_vector(&solution) == rhs).then_some(solution)
+    }
+}
+
+impl<FF: FiniteField> From<&Matrix<FF>> for SparseMatrix<FF> {
+    fn from(matrix: &Matrix<FF>) -> Self {
+        let rows = matrix
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .copied()
+                    .enumerate()
+                    .filter(|(_, value)| !value.is_zero())
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            num_cols: matrix.num_cols(),
+            rows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::bfe;
+    use crate::bfe_array;
+    use crate::math::x_field_element::XFieldElement;
+
+    /// A strategy for square sparse matrices where every row has at most `max_row_weight`
+    /// non-zero entries, and at least one on the diagonal.
+    fn sparse_matrix<FF>(n: usize, max_row_weight: usize) -> impl Strategy<Value = SparseMatrix<FF>>
+    where
+        FF: FiniteField + for<'a> arbitrary::Arbitrary<'a> + 'static,
+    {
+        let row = move |i| {
+            vec((0..n, arb()), 0..max_row_weight)
+                .prop_map(move |entries| [vec![(i, FF::ONE)], entries].concat())
+        };
+        let rows = (0..n).map(row).collect::<Vec<_>>();
+        rows.prop_map(move |rows| SparseMatrix::new(n, rows).unwrap())
+    }
+
+    #[test]
+    fn constructing_matrix_with_out_of_bounds_column_fails() {
+        let rows = vec![vec![(0, bfe!(1))], vec![(3, bfe!(2))]];
+        let err = SparseMatrix::new(3, rows).unwrap_err();
+        let expected = MatrixError::ColumnIndexOutOfBounds {
+            index: 3,
+            num_cols: 3,
+        };
+        assert_eq!(expected, err);
+    }
+
+    #[proptest]
+    fn conversion_to_and_from_dense_matrix_is_lossless(
+        #[strategy(arb())] matrix: Matrix<BFieldElement>,
+    ) {
+        let sparse = SparseMatrix::from(&matrix);
+        prop_assert_eq!(matrix.num_rows(), sparse.num_rows());
+        prop_assert_eq!(&matrix, &sparse.to_dense());
+    }
+
+    #[proptest]
+    fn matrix_vector_product_agrees_with_dense_matrix(
+        #[strategy(0_usize..20)] _n: usize,
+        #[strategy(sparse_matrix(#_n, 4))] matrix: SparseMatrix<BFieldElement>,
+        #[strategy(vec(arb(), #_n))] vector: Vec<BFieldElement>,
+    ) {
+        let dense_product = matrix.to_dense().mul_vector(&vector);
+        prop_assert_eq!(dense_product, matrix.mul_vector(&vector));
+    }
+
+    #[proptest(cases = 50)]
+    fn wiedemann_solution_agrees_with_gauss_jordan_solution(
+        #[strategy(0_usize..60)] _n: usize,
+        #[strategy(sparse_matrix(#_n, 3))] matrix: SparseMatrix<BFieldElement>,
+        #[strategy(vec(arb(), #_n))] rhs: Vec<BFieldElement>,
+    ) {
+        let dense = matrix.to_dense();
+        prop_assume!(dense.rank() == _n);
+
+        let solution = matrix.solve(&rhs)?;
+        prop_assert_eq!(dense.solve(&rhs)?, solution);
+    }
+
+    #[proptest(cases = 20)]
+    fn wiedemann_solver_works_over_extension_field(
+        #[strategy(1_usize..30)] _n: usize,
+        #[strategy(sparse_matrix(#_n, 3))] matrix: SparseMatrix<XFieldElement>,
+        #[strategy(vec(arb(), #_n))] rhs: Vec<XFieldElement>,
+    ) {
+        prop_assume!(matrix.to_dense().rank() == _n);
+        let solution = matrix.solve(&rhs)?;
+        prop_assert_eq!(rhs, matrix.mul_vector(&solution));
+    }
+
+    #[test]
+    fn wiedemann_solver_can_solve_system_with_large_sparse_matrix() {
+        // a cyclic shift plus twice the identity; far too large for Gauss-Jordan elimination
+        let n = 5_000;
+        let rows = (0..n)
+            .map(|i| vec![(i, bfe!(2)), ((i + 1) % n, bfe!(1))])
+            .collect();
+        let matrix = SparseMatrix::new(n, rows).unwrap();
+        let rhs = (0..n as u64).map(BFieldElement::new).collect::<Vec<_>>();
+
+        let solution = matrix.solve(&rhs).unwrap();
+        assert_eq!(rhs, matrix.mul_vector(&solution));
+    }
+
+    #[test]
+    fn wiedemann_solver_detects_singular_matrix() {
+        let rows = vec![
+            vec![(0, bfe!(1)), (1, bfe!(2))],
+            vec![(0, bfe!(2)), (1, bfe!(4))],
+            vec![(2, bfe!(1))],
+        ];
+        let matrix = SparseMatrix::new(3, rows).unwrap();
+        assert_eq!(
+            Err(MatrixError::Singular),
+            matrix.solve(&bfe_array![1, 0, 0])
+        );
+    }
+
+    #[test]
+    fn wiedemann_solver_rejects_malformed_systems() {
+        let wide_matrix = SparseMatrix::new(3, vec![vec![(0, bfe!(1))]]).unwrap();
+        assert_eq!(
+            Err(MatrixError::NotSquare),
+            wide_matrix.solve(&bfe_array![1])
+        );
+
+        let matrix = SparseMatrix::new(1, vec![vec![(0, bfe!(1))]]).unwrap();
+        let mismatch = Err(MatrixError::DimensionMismatch {
+            expected: 1,
+            actual: 2,
+        });
+        assert_eq!(mismatch, matrix.solve(&bfe_array![1, 2]));
+    }
+}
@@ -0,0 +1,444 @@
+//! Quasi-linear solvers for linear systems with structured matrices.
+//!
+//! For `n` points `p_0, …, p_{n-1}`, the [Vandermonde matrix](Matrix::vandermonde) `V` has
+//! entries `V[i][j] = p_i^j`. Solving `V·c = y` is polynomial interpolation. Solving
+//! `Vᵀ·w = s` recovers the weights `w` from the weighted power sums `s_j = Σ_i w_i·p_i^j`. For
+//! points `x_0, …, x_{n-1}` and `y_0, …, y_{n-1}`, the [Cauchy matrix](Matrix::cauchy) `C`
+//! has entries `C[i][j] = 1 / (x_i - y_j)`.
+//!
+//! The fast solvers build a [`ZerofierTree`] over the points and need O(n·log²(n)) field
+//! operations. Their `naive_` counterparts invert the dense matrix in O(n³) and are mostly
+//! useful as a reference.
+
+use std::ops::MulAssign;
+
+use num_traits::Zero;
+
+use super::Matrix;
+use super::MatrixError;
+use crate::math::b_field_element::BFieldElement;
+use crate::math::polynomial::Polynomial;
+use crate::math::traits::FiniteField;
+use crate::math::zerofier_tree::ZerofierTree;
+
+impl<FF: FiniteField> Matrix<FF> {
+    /// The square Vandermonde matrix with entries `points[i]^j`.
+    pub fn vandermonde(points: &[FF]) -> Self {
+        let n = points.len();
+        let entries = points
+            .iter()
+            .flat_map(|&point| {
+                std::iter::successors(Some(FF::ONE), move |&power| Some(power * point)).take(n)
+            })
+            .collect();
+
+        Self {
+            num_rows: n,
+            num_cols: n,
+            entries,
+        }
+    }
+
+    /// The Cauchy matrix with entries `1 / (row_points[i] - col_points[j])`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if some row point is equal to some column point.
+    pub fn cauchy(row_points: &[FF], col_points: &[FF]) -> Result<Self, MatrixError> {
+        let differences = row_points
+            .iter()
+            .flat_map(|&x| col_points.iter().map(move |&y| x - y))
+            .collect::<Vec<_>>();
+        if differences.iter().any(|d| d.is_zero()) {
+            return Err(MatrixError::OverlappingCauchyPoints);
+        }
+
+        Ok(Self {
+            num_rows: row_points.len(),
+            num_cols: col_points.len(),
+            entries: FF::batch_inversion(differences),
+        })
+    }
+}
+
+/// The vector `c` such that `V·c = rhs`, where `V` is the [Vandermonde
+/// matrix](Matrix::vandermonde) over the given points. Equivalently, the coefficients of the
+/// polynomial of degree less than `points.len()` that evaluates to `rhs[i]` in `points[i]`.
+///
+/// # Errors
+///
+/// Errors if the lengths of `points` and `rhs` differ, or if the points are not distinct.
+pub fn solve_vandermonde<FF>(points: &[FF], rhs: &[FF]) -> Result<Vec<FF>, MatrixError>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+{
+    check_dimension(points.len(), rhs)?;
+    let tree = ZerofierTree::new_from_domain(points);
+    let interpolant = interpolate(&tree, rhs)?;
+
+    Ok(padded_coefficients(&interpolant, points.len()))
+}
+
+/// The vector `w` such that `Vᵀ·w = rhs`, where `V` is the [Vandermonde
+/// matrix](Matrix::vandermonde) over the given points. Equivalently, the weights `w` for which
+/// `rhs[j] = Σ_i w[i]·points[i]^j`.
+///
+/// # Errors
+///
+/// Errors if the lengths of `points` and `rhs` differ, or if the points are not distinct.
+pub fn solve_transposed_vandermonde<FF>(points: &[FF], rhs: &[FF]) -> Result<Vec<FF>, MatrixError>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+{
+    let n = points.len();
+    check_dimension(n, rhs)?;
+    if n == 0 {
+        return Ok(vec![]);
+    }
+
+    // With Z(X) = Π_i (X - p_i) and its reversal Z̃(T) = Π_i (1 - p_i·T), the generating
+    // function of the power sums is Σ_j rhs[j]·T^j = Σ_i w_i / (1 - p_i·T) mod T^n. Hence,
+    // N(T) = Σ_i w_i·Π_{k≠i} (1 - p_k·T) is the product (Σ_j rhs[j]·T^j)·Z̃(T) mod T^n.
+    // Reversing N gives Σ_i w_i·Π_{k≠i} (X - p_k), which evaluates to w_i·Z'(p_i) in p_i.
+    let tree = ZerofierTree::new_from_domain(points);
+    let zerofier = tree.zerofier();
+    let reversed_zerofier =
+        Polynomial::new(zerofier.coefficients().iter().rev().copied().collect());
+    let numerator = Polynomial::new(rhs.to_vec())
+        .multiply(&reversed_zerofier)
+        .mod_x_to_the_n(n);
+    let mut numerator = padded_coefficients(&numerator, n);
+    numerator.reverse();
+
+    let numerators = Polynomial::new(numerator).divide_and_conquer_batch_evaluate(&tree);
+    let denominators = zerofier
+        .formal_derivative()
+        .divide_and_conquer_batch_evaluate(&tree);
+
+    divide_pointwise(numerators, denominators)
+}
+
+/// The vector `a` such that `C·a = rhs`, where `C` is the [Cauchy matrix](Matrix::cauchy)
+/// over the given row and column points.
+///
+/// # Errors
+///
+/// Errors if
+/// - the numbers of row points and column points differ,
+/// - the length of `rhs` is not the number of row points,
+/// - the row points or the column points are not distinct, or
+/// - some row point is equal to some column point.
+pub fn solve_cauchy<FF>(
+    row_points: &[FF],
+    col_points: &[FF],
+    rhs: &[FF],
+) -> Result<Vec<FF>, MatrixError>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+{
+    if row_points.len() != col_points.len() {
+        return Err(MatrixError::NotSquare);
+    }
+    check_dimension(row_points.len(), rhs)?;
+
+    // With Q(X) = Π_j (X - y_j), the rational function Σ_j a_j / (X - y_j) equals R(X) / Q(X)
+    // for R(X) = Σ_j a_j·Π_{k≠j} (X - y_k). The system prescribes R(x_i) = rhs[i]·Q(x_i),
+    // which determines R through interpolation. Finally, R(y_j) = a_j·Q'(y_j).
+    let row_tree = ZerofierTree::new_from_domain(row_points);
+    let col_tree = ZerofierTree::new_from_domain(col_points);
+    let col_zerofier = col_tree.zerofier();
+
+    let col_zerofier_in_row_points = col_zerofier.divide_and_conquer_batch_evaluate(&row_tree);
+    if col_zerofier_in_row_points.iter().any(|v| v.is_zero()) {
+        return Err(MatrixError::OverlappingCauchyPoints);
+    }
+    let values = rhs
+        .iter()
+        .zip(col_zerofier_in_row_points)
+        .map(|(&r, q)| r * q)
+        .collect::<Vec<_>>();
+    let numerator = interpolate(&row_tree, &values)?;
+
+    let numerators = numerator.divide_and_conquer_batch_evaluate(&col_tree);
+    let denominators = col_zerofier
+        .formal_derivative()
+        .divide_and_conquer_batch_evaluate(&col_tree);
+
+    divide_pointwise(numerators, denominators)
+}
+
+/// Like [`solve_vandermonde`], but using Gauss-Jordan elimination.
+pub fn naive_solve_vandermonde<FF: FiniteField>(
+    points: &[FF],
+    rhs: &[FF],
+) -> Result<Vec<FF>, MatrixError> {
+    naive_solve(&Matrix::vandermonde(points), rhs)
+}
+
+/// Like [`solve_transposed_vandermonde`], but using Gauss-Jordan elimination.
+pub fn naive_solve_transposed_vandermonde<FF: FiniteField>(
+    points: &[FF],
+    rhs: &[FF],
+) -> Result<Vec<FF>, MatrixError> {
+    naive_solve(&Matrix::vandermonde(points).transpose(), rhs)
+}
+
+/// Like [`solve_cauchy`], but using Gauss-Jordan elimination.
+pub fn naive_solve_cauchy<FF: FiniteField>(
+    row_points: &[FF],
+    col_points: &[FF],
+    rhs: &[FF],
+) -> Result<Vec<FF>, MatrixError> {
+    naive_solve(&Matrix::cauchy(row_points, col_points)?, rhs)
+}
+
+/// The unique solution of `matrix · x = rhs`.
+fn naive_solve<FF: FiniteField>(matrix: &Matrix<FF>, rhs: &[FF]) -> Result<Vec<FF>, MatrixError> {
+    if !matrix.is_square() {
+        return Err(MatrixError::NotSquare);
+    }
+    check_dimension(matrix.num_rows(), rhs)?;
+
+    Ok(matrix.inverse()?.mul_vector(rhs))
+}
+
+fn check_dimension<FF>(expected: usize, rhs: &[FF]) -> Result<(), MatrixError> {
+    let actual = rhs.len();
+    if actual != expected {
+        return Err(MatrixError::DimensionMismatch { expected, actual });
+    }
+
+    Ok(())
+}
+
+/// Inverts all elements, or fails with [`MatrixError::Singular`] if any of them is zero.
+///
+/// Only used on the derivative of a zerofier in its roots, which has a zero if and only if
+/// some root is repeated.
+fn checked_batch_inversion<FF: FiniteField>(elements: Vec<FF>) -> Result<Vec<FF>, MatrixError> {
+    if elements.iter().any(|e| e.is_zero()) {
+        return Err(MatrixError::Singular);
+    }
+
+    Ok(FF::batch_inversion(elements))
+}
+
+fn divide_pointwise<FF: FiniteField>(
+    numerators: Vec<FF>,
+    denominators: Vec<FF>,
+) -> Result<Vec<FF>, MatrixError> {
+    let inverse_denominators = checked_batch_inversion(denominators)?;
+    let quotients = numerators
+        .into_iter()
+        .zip(inverse_denominators)
+        .map(|(numerator, inverse_denominator)| numerator * inverse_denominator)
+        .collect();
+
+    Ok(quotients)
+}
+
+fn padded_coefficients<FF: FiniteField>(polynomial: &Polynomial<FF>, len: usize) -> Vec<FF> {
+    let mut coefficients = polynomial.coefficients().to_vec();
+    coefficients.resize(len, FF::ZERO);
+    coefficients
+}
+
+/// The polynomial of degree less than the number of points in the tree that evaluates to
+/// `values[i]` in the `i`th point.
+fn interpolate<FF>(
+    tree: &ZerofierTree<FF>,
+    values: &[FF],
+) -> Result<Polynomial<'static, FF>, MatrixError>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+{
+    let denominators = tree
+        .zerofier()
+        .formal_derivative()
+        .divide_and_conquer_batch_evaluate(tree);
+    let weights = checked_batch_inversion(denominators)?
+        .into_iter()
+        .zip(values)
+        .map(|(d, &v)| d * v)
+        .collect::<Vec<_>>();
+
+    Ok(linear_combination(tree, &weights))
+}
+
+/// The polynomial `Σ_i weights[i]·Z(X) / (X - p_i)`, where `p_i` are the points in the tree and
+/// `Z` is the tree's zerofier.
+fn linear_combination<FF>(tree: &ZerofierTree<FF>, weights: &[FF]) -> Polynomial<'static, FF>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+{
+    match tree {
+        ZerofierTree::Leaf(leaf) => {
+            let zerofier = tree.zerofier();
+            let zerofier = zerofier.coefficients();
+            let degree = zerofier.len() - 1;
+
+            let mut coefficients = vec![FF::ZERO; degree];
+            for (&point, &weight) in leaf.points.iter().zip(weights) {
+                // synthetic division of the zerofier by (X - point)
+                let mut quotient_coefficient = FF::ZERO;
+                for (coefficient, &zerofier_coefficient) in
+                    coefficients.iter_mut().zip(&zerofier[1..]).rev()
+                {
+                    quotient_coefficient = zerofier_coefficient + point * quotient_coefficient;
+                    *coefficient += weight * quotient_coefficient;
+                }
+            }
+            Polynomial::new(coefficients)
+        }
+        ZerofierTree::Branch(branch) => {
+            let left_zerofier = branch.left.zerofier();
+            let right_zerofier = branch.right.zerofier();
+            let num_left_points = usize::try_from(left_zerofier.degree()).unwrap_or(0);
+            let (left_weights, right_weights) = weights.split_at(num_left_points);
+
+            let left = linear_combination(&branch.left, left_weights);
+            let right = linear_combination(&branch.right, right_weights);
+            left.multiply(&right_zerofier) + right.multiply(&left_zerofier)
+        }
+        ZerofierTree::Padding => Polynomial::zero(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::ConstOne;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::bfe_array;
+    use crate::bfe_vec;
+    use crate::math::traits::ModPowU32;
+    use crate::math::x_field_element::XFieldElement;
+
+    #[test]
+    fn vandermonde_matrix_has_powers_of_points_as_rows() {
+        let matrix = Matrix::vandermonde(&bfe_array![2, 3, 5]);
+        let expected =
+            Matrix::from_rows(&[bfe_vec![1, 2, 4], bfe_vec![1, 3, 9], bfe_vec![1, 5, 25]]);
+        assert_eq!(expected.unwrap(), matrix);
+    }
+
+    #[test]
+    fn cauchy_matrix_is_defined_for_disjoint_points_only() {
+        let matrix = Matrix::cauchy(&bfe_array![1, 2], &bfe_array![0, 3]).unwrap();
+        assert_eq!(BFieldElement::ONE, matrix[(0, 0)]);
+        assert_eq!(-BFieldElement::ONE, matrix[(1, 1)]);
+
+        let err = Matrix::cauchy(&bfe_array![1, 2], &bfe_array![2, 3]).unwrap_err();
+        assert_eq!(MatrixError::OverlappingCauchyPoints, err);
+    }
+
+    #[proptest(cases = 50)]
+    fn fast_vandermonde_solver_agrees_with_naive_solver(
+        #[strategy(0_usize..70)] _n: usize,
+        #[strategy(vec(arb(), #_n))] points: Vec<BFieldElement>,
+        #[strategy(vec(arb(), #_n))] rhs: Vec<BFieldElement>,
+    ) {
+        let solution = solve_vandermonde(&points, &rhs);
+        prop_assert_eq!(naive_solve_vandermonde(&points, &rhs), solution.clone());
+        prop_assert_eq!(rhs, Matrix::vandermonde(&points).mul_vector(&solution?));
+    }
+
+    #[proptest(cases = 50)]
+    fn fast_transposed_vandermonde_solver_agrees_with_naive_solver(
+        #[strategy(0_usize..70)] _n: usize,
+        #[strategy(vec(arb(), #_n))] points: Vec<BFieldElement>,
+        #[strategy(vec(arb(), #_n))] rhs: Vec<BFieldElement>,
+    ) {
+        let solution = solve_transposed_vandermonde(&points, &rhs);
+        prop_assert_eq!(naive_solve_transposed_vandermonde(&points, &rhs), solution);
+    }
+
+    #[proptest(cases = 50)]
+    fn fast_cauchy_solver_agrees_with_naive_solver(
+        #[strategy(0_usize..70)] _n: usize,
+        #[strategy(vec(arb(), #_n))] row_points: Vec<BFieldElement>,
+        #[strategy(vec(arb(), #_n))] col_points: Vec<BFieldElement>,
+        #[strategy(vec(arb(), #_n))] rhs: Vec<BFieldElement>,
+    ) {
+        let solution = solve_cauchy(&row_points, &col_points, &rhs);
+        prop_assert_eq!(
+            naive_solve_cauchy(&row_points, &col_points, &rhs),
+            solution.clone()
+        );
+
+        let cauchy = Matrix::cauchy(&row_points, &col_points)?;
+        prop_assert_eq!(rhs, cauchy.mul_vector(&solution?));
+    }
+
+    #[proptest(cases = 20)]
+    fn weights_can_be_recovered_from_power_sums(
+        #[strategy(0_usize..100)] _n: usize,
+        #[strategy(vec(arb(), #_n))] points: Vec<XFieldElement>,
+        #[strategy(vec(arb(), #_n))] weights: Vec<XFieldElement>,
+    ) {
+        let power_sums = (0..points.len())
+            .map(|j| {
+                points
+                    .iter()
+                    .zip(&weights)
+                    .map(|(&p, &w)| w * p.mod_pow_u32(j as u32))
+                    .sum()
+            })
+            .collect::<Vec<_>>();
+
+        prop_assert_eq!(weights, solve_transposed_vandermonde(&points, &power_sums)?);
+    }
+
+    #[proptest(cases = 20)]
+    fn solving_vandermonde_system_is_interpolation(
+        #[strategy(1_usize..100)] _n: usize,
+        #[strategy(vec(arb(), #_n))] points: Vec<XFieldElement>,
+        #[strategy(vec(arb(), #_n))] values: Vec<XFieldElement>,
+    ) {
+        let interpolant = Polynomial::interpolate(&points, &values);
+        let solution = solve_vandermonde(&points, &values)?;
+        prop_assert_eq!(interpolant, Polynomial::new(solution));
+    }
+
+    #[test]
+    fn repeated_points_make_structured_matrices_singular() {
+        let points = bfe_vec![1, 2, 1];
+        let col_points = bfe_vec![3, 4, 5];
+        let rhs = bfe_vec![1, 1, 1];
+
+        let singular = Err(MatrixError::Singular);
+        assert_eq!(singular, solve_vandermonde(&points, &rhs));
+        assert_eq!(singular, solve_transposed_vandermonde(&points, &rhs));
+        assert_eq!(singular, solve_cauchy(&points, &col_points, &rhs));
+        assert_eq!(singular, solve_cauchy(&col_points, &points, &rhs));
+        assert_eq!(singular, naive_solve_vandermonde(&points, &rhs));
+        assert_eq!(singular, naive_solve_transposed_vandermonde(&points, &rhs));
+        assert_eq!(singular, naive_solve_cauchy(&points, &col_points, &rhs));
+    }
+
+    #[test]
+    fn malformed_structured_systems_are_rejected() {
+        let points = bfe_vec![1, 2, 3];
+        let short_rhs = bfe_vec![1, 1];
+        let mismatch = Err(MatrixError::DimensionMismatch {
+            expected: 3,
+            actual: 2,
+        });
+        assert_eq!(mismatch, solve_vandermonde(&points, &short_rhs));
+        assert_eq!(mismatch, solve_transposed_vandermonde(&points, &short_rhs));
+
+        let rhs = bfe_vec![1, 1, 1];
+        let too_few_col_points = solve_cauchy(&points, &bfe_array![4, 5], &rhs);
+        assert_eq!(Err(MatrixError::NotSquare), too_few_col_points);
+
+        let overlapping_points = solve_cauchy(&points, &bfe_array![4, 5, 3], &rhs);
+        assert_eq!(
+            Err(MatrixError::OverlappingCauchyPoints),
+            overlapping_points
+        );
+    }
+}
@@ -0,0 +1,620 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+
+use itertools::Itertools;
+use num_traits::Zero;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::polynomial::Polynomial;
+use crate::math::traits::FiniteField;
+
+/// A multivariate polynomial with coefficients in a [finite field](FiniteField), in monomial form.
+///
+/// The polynomial is stored sparsely: only monomials with non-zero coefficients are kept. Every
+/// monomial is identified by its exponents, one per variable.
+///
+/// # Examples
+///
+/// ```
+/// # use twenty_first::prelude::*;
+/// let [x, y] = MPolynomial::<BFieldElement>::variables(2).try_into().unwrap();
+/// let f = x.clone() * x + y.clone() * y; // x² + y²
+///
+/// assert_eq!(2, f.degree());
+/// assert_eq!(bfe!(25), f.evaluate(&bfe_array![3, 4]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MPolynomial<FF: FiniteField> {
+    variable_count: usize,
+
+    /// Maps the exponents of a monomial to its coefficient. Every key has length
+    /// `variable_count`. Never contains a zero coefficient.
+    coefficients: HashMap<Vec<u32>, FF>,
+}
+
+impl<FF: FiniteField> Display for MPolynomial<FF> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let monomials = self
+            .coefficients
+            .iter()
+            .sorted_by(|(l, _), (r, _)| r.cmp(l));
+        for (i, (exponents, &coeff)) in monomials.enumerate() {
+            if i != 0 {
+                write!(f, " + ")?;
+            }
+
+            let variables = exponents
+                .iter()
+                .enumerate()
+                .filter(|&(_, &exponent)| exponent != 0)
+                .map(|(var, &exponent)| match exponent {
+                    1 => format!("x_{var}"),
+                    _ => format!("x_{var}^{exponent}"),
+                })
+                .join("·");
+
+            match (coeff.is_one(), variables.is_empty()) {
+                (_, true) => write!(f, "{coeff}")?,
+                (true, false) => write!(f, "{variables}")?,
+                (false, false) => write!(f, "{coeff}·{variables}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<FF: FiniteField> MPolynomial<FF> {
+    /// Create a new multivariate polynomial in `variable_count` variables from a map of monomial
+    /// exponents to coefficients. Zero coefficients are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the exponents of any monomial are not of length `variable_count`.
+    pub fn new(variable_count: usize, coefficients: HashMap<Vec<u32>, FF>) -> Self {
+        assert!(
+            coefficients.keys().all(|e| e.len() == variable_count),
+            "every monomial must have exactly {variable_count} exponents"
+        );
+
+        let coefficients = coefficients
+            .into_iter()
+            .filter(|(_, c)| !c.is_zero())
+            .collect();
+
+        Self {
+            variable_count,
+            coefficients,
+        }
+    }
+
+    /// The zero polynomial in `variable_count` variables.
+    pub fn zero(variable_count: usize) -> Self {
+        Self::new(variable_count, HashMap::new())
+    }
+
+    /// The constant polynomial `constant` in `variable_count` variables.
+    pub fn from_constant(constant: FF, variable_count: usize) -> Self {
+        let coefficients = HashMap::from([(vec![0; variable_count], constant)]);
+        Self::new(variable_count, coefficients)
+    }
+
+    /// All `variable_count` variables `x_0, …, x_{variable_count - 1}`, each as a polynomial.
+    pub fn variables(variable_count: usize) -> Vec<Self> {
+        (0..variable_count)
+            .map(|var| {
+                let mut exponents = vec![0; variable_count];
+                exponents[var] = 1;
+                Self::new(variable_count, HashMap::from([(exponents, FF::ONE)]))
+            })
+            .collect()
+    }
+
+    /// Lift a univariate polynomial into a multivariate polynomial in `variable_count` variables,
+    /// using the variable with index `variable` as the indeterminate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `variable` is not smaller than `variable_count`.
+    pub fn from_univariate(
+        polynomial: &Polynomial<FF>,
+        variable: usize,
+        variable_count: usize,
+    ) -> Self {
+        assert!(variable < variable_count, "variable index out of bounds");
+
+        let coefficients = (0..)
+            .zip(polynomial.coefficients())
+            .map(|(exponent, &coeff)| {
+                let mut exponents = vec![0; variable_count];
+                exponents[variable] = exponent;
+                (exponents, coeff)
+            })
+            .collect();
+
+        Self::new(variable_count, coefficients)
+    }
+
+    pub fn variable_count(&self) -> usize {
+        self.variable_count
+    }
+
+    /// The polynomial's monomials, mapping exponents to (non-zero) coefficients.
+    pub fn coefficients(&self) -> &HashMap<Vec<u32>, FF> {
+        &self.coefficients
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        *self == Self::from_constant(FF::ONE, self.variable_count)
+    }
+
+    /// The total degree, _i.e._, the largest sum of exponents of any monomial. -1 for the zero
+    /// polynomial.
+    pub fn degree(&self) -> isize {
+        self.coefficients
+            .keys()
+            .map(|exponents| exponents.iter().map(|&e| e as isize).sum())
+            .max()
+            .unwrap_or(-1)
+    }
+
+    /// The degree of `self` when considered a univariate polynomial in the variable with index
+    /// `variable`. -1 for the zero polynomial.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `variable` is not smaller than the [variable count](Self::variable_count).
+    pub fn degree_in(&self, variable: usize) -> isize {
+        assert!(
+            variable < self.variable_count,
+            "variable index out of bounds"
+        );
+        self.coefficients
+            .keys()
+            .map(|exponents| exponents[variable] as isize)
+            .max()
+            .unwrap_or(-1)
+    }
+
+    /// The [degree in each variable](Self::degree_in), in order of the variables.
+    pub fn degree_per_variable(&self) -> Vec<isize> {
+        (0..self.variable_count)
+            .map(|var| self.degree_in(var))
+            .collect()
+    }
+
+    /// An upper bound for the degree of the univariate polynomial resulting from
+    /// [symbolic evaluation](Self::evaluate_symbolic), given upper bounds for the degrees of the
+    /// substituted univariate polynomials. -1 for the zero polynomial.
+    ///
+    /// This bound is exact unless terms cancel, which is useful for computing the degree of
+    /// composed AIR constraints without performing the composition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of degree bounds is not equal to the
+    /// [variable count](Self::variable_count).
+    pub fn symbolic_degree_bound(&self, max_degrees: &[isize]) -> isize {
+        assert_eq!(self.variable_count, max_degrees.len());
+        self.coefficients
+            .keys()
+            .map(|exponents| {
+                let mut degree = 0;
+                for (&exponent, &max_degree) in exponents.iter().zip(max_degrees) {
+                    if exponent == 0 {
+                        continue;
+                    }
+                    if max_degree < 0 {
+                        // substituting the zero polynomial makes the term vanish
+                        return -1;
+                    }
+                    degree += exponent as isize * max_degree;
+                }
+                degree
+            })
+            .max()
+            .unwrap_or(-1)
+    }
+
+    /// Evaluate `self` in the given point, which must have one coordinate per variable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of coordinates is not equal to the
+    /// [variable count](Self::variable_count).
+    pub fn evaluate<Ind>(&self, point: &[Ind]) -> Ind
+    where
+        Ind: FiniteField,
+        FF: Mul<Ind, Output = Ind>,
+    {
+        assert_eq!(self.variable_count, point.len());
+
+        let mut powers = HashMap::new();
+        let mut acc = Ind::ZERO;
+        for (exponents, &coeff) in &self.coefficients {
+            let mut term = Ind::ONE;
+            for (var, &exponent) in exponents.iter().enumerate() {
+                if exponent == 0 {
+                    continue;
+                }
+                term *= *powers
+                    .entry((var, exponent))
+                    .or_insert_with(|| point[var].mod_pow_u32(exponent));
+            }
+            acc += coeff * term;
+        }
+
+        acc
+    }
+
+    /// Compute the partial derivative with respect to the variable with index `variable`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `variable` is not smaller than the [variable count](Self::variable_count).
+    #[must_use]
+    pub fn partial_derivative(&self, variable: usize) -> Self {
+        assert!(
+            variable < self.variable_count,
+            "variable index out of bounds"
+        );
+
+        let mut coefficients = HashMap::new();
+        for (exponents, &coeff) in &self.coefficients {
+            let exponent = exponents[variable];
+            if exponent == 0 {
+                continue;
+            }
+            let mut new_exponents = exponents.clone();
+            new_exponents[variable] -= 1;
+            coefficients.insert(new_exponents, coeff * FF::from(u64::from(exponent)));
+        }
+
+        Self::new(self.variable_count, coefficients)
+    }
+
+    /// Multiply a polynomial with a scalar, _i.e._, compute `scalar · self`.
+    #[must_use]
+    pub fn scalar_mul(&self, scalar: FF) -> Self {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|(exponents, &coeff)| (exponents.clone(), coeff * scalar))
+            .collect();
+
+        Self::new(self.variable_count, coefficients)
+    }
+
+    /// Multiply `self` with itself `pow` times.
+    #[must_use]
+    pub fn pow(&self, pow: u32) -> Self {
+        let mut acc = Self::from_constant(FF::ONE, self.variable_count);
+        let Some(bit_length) = pow.checked_ilog2() else {
+            return acc;
+        };
+
+        // square-and-multiply
+        for i in 0..=bit_length {
+            acc = acc.clone() * acc;
+            let bit_is_set = (pow >> (bit_length - i) & 1) == 1;
+            if bit_is_set {
+                acc = acc * self.clone();
+            }
+        }
+
+        acc
+    }
+}
+
+impl<FF> MPolynomial<FF>
+where
+    FF: FiniteField + MulAssign<BFieldElement>,
+{
+    /// Substitute the variables with univariate polynomials, producing a univariate polynomial.
+    ///
+    /// This is the composition of a multivariate polynomial, like an AIR constraint, with
+    /// univariate polynomials, like interpolants of execution trace columns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of univariate polynomials is not equal to the
+    /// [variable count](Self::variable_count).
+    pub fn evaluate_symbolic(&self, point: &[Polynomial<FF>]) -> Polynomial<'static, FF> {
+        assert_eq!(self.variable_count, point.len());
+
+        let mut powers = HashMap::new();
+        let mut acc = Polynomial::zero();
+        for (exponents, &coeff) in &self.coefficients {
+            let mut term = Polynomial::from_constant(coeff);
+            for (var, &exponent) in exponents.iter().enumerate() {
+                if exponent == 0 {
+                    continue;
+                }
+                let power = powers
+                    .entry((var, exponent))
+                    .or_insert_with(|| point[var].fast_pow(exponent));
+                term = term.multiply(power);
+            }
+            acc += term;
+        }
+
+        acc
+    }
+}
+
+impl<FF: FiniteField> Add for MPolynomial<FF> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<FF: FiniteField> AddAssign for MPolynomial<FF> {
+    /// # Panics
+    ///
+    /// Panics if the variable counts of the summands differ.
+    fn add_assign(&mut self, rhs: Self) {
+        assert_eq!(self.variable_count, rhs.variable_count);
+        for (exponents, coeff) in rhs.coefficients {
+            let sum = *self.coefficients.get(&exponents).unwrap_or(&FF::ZERO) + coeff;
+            if sum.is_zero() {
+                self.coefficients.remove(&exponents);
+            } else {
+                self.coefficients.insert(exponents, sum);
+            }
+        }
+    }
+}
+
+impl<FF: FiniteField> Sub for MPolynomial<FF> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<FF: FiniteField> Neg for MPolynomial<FF> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        for coeff in self.coefficients.values_mut() {
+            *coeff = -*coeff;
+        }
+        self
+    }
+}
+
+impl<FF: FiniteField> Mul for MPolynomial<FF> {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if the variable counts of the factors differ.
+    fn mul(self, other: Self) -> Self {
+        assert_eq!(self.variable_count, other.variable_count);
+
+        let mut product = Self::zero(self.variable_count);
+        for (lhs_exponents, &lhs_coeff) in &self.coefficients {
+            for (rhs_exponents, &rhs_coeff) in &other.coefficients {
+                let exponents = lhs_exponents
+                    .iter()
+                    .zip_eq(rhs_exponents)
+                    .map(|(&l, &r)| l + r)
+                    .collect();
+                let coefficients = HashMap::from([(exponents, lhs_coeff * rhs_coeff)]);
+                product += Self::new(self.variable_count, coefficients);
+            }
+        }
+
+        product
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::hash_map;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::prelude::*;
+
+    const NUM_VARIABLES: usize = 3;
+
+    fn arbitrary_mpolynomial<FF>(variable_count: usize) -> BoxedStrategy<MPolynomial<FF>>
+    where
+        FF: FiniteField + for<'a> arbitrary::Arbitrary<'a> + 'static,
+    {
+        let exponents = vec(0_u32..5, variable_count);
+        hash_map(exponents, arb(), 0..8)
+            .prop_map(move |coefficients| MPolynomial::new(variable_count, coefficients))
+            .boxed()
+    }
+
+    #[test]
+    fn multivariate_polynomial_display_test() {
+        let [x, y] = MPolynomial::<BFieldElement>::variables(2)
+            .try_into()
+            .unwrap();
+        assert_eq!("0", MPolynomial::<BFieldElement>::zero(2).to_string());
+        assert_eq!("x_0", x.to_string());
+
+        let f = x.clone().pow(2) * y.clone().scalar_mul(bfe!(3)) + y + x.pow(0);
+        assert_eq!("3·x_0^2·x_1 + x_1 + 1", f.to_string());
+    }
+
+    #[test]
+    fn zero_coefficients_are_dropped() {
+        let coefficients = HashMap::from([(vec![1, 2], bfe!(0)), (vec![0, 0], bfe!(0))]);
+        let f = MPolynomial::new(2, coefficients);
+        assert!(f.is_zero());
+        assert_eq!(-1, f.degree());
+        assert_eq!(vec![-1, -1], f.degree_per_variable());
+    }
+
+    #[test]
+    #[should_panic(expected = "exactly 2 exponents")]
+    fn exponents_of_wrong_length_are_rejected() {
+        let coefficients = HashMap::from([(vec![1, 2, 3], bfe!(1))]);
+        MPolynomial::new(2, coefficients);
+    }
+
+    #[proptest]
+    fn variables_evaluate_to_coordinates(
+        #[strategy(vec(arb(), NUM_VARIABLES))] point: Vec<BFieldElement>,
+    ) {
+        let variables = MPolynomial::<BFieldElement>::variables(NUM_VARIABLES);
+        let evaluations = variables.iter().map(|v| v.evaluate(&point)).collect_vec();
+        prop_assert_eq!(point, evaluations);
+    }
+
+    #[proptest]
+    fn zero_is_neutral_element_for_addition(
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] f: MPolynomial<BFieldElement>,
+    ) {
+        let zero = MPolynomial::zero(NUM_VARIABLES);
+        prop_assert_eq!(f.clone(), f.clone() + zero.clone());
+        prop_assert_eq!(zero, f.clone() - f);
+    }
+
+    #[proptest]
+    fn one_is_neutral_element_for_multiplication(
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] f: MPolynomial<BFieldElement>,
+    ) {
+        let one = MPolynomial::from_constant(bfe!(1), NUM_VARIABLES);
+        prop_assert!(one.is_one());
+        prop_assert_eq!(f.clone(), f * one);
+    }
+
+    #[proptest]
+    fn multiplication_is_commutative_and_distributive(
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] a: MPolynomial<XFieldElement>,
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] b: MPolynomial<XFieldElement>,
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] c: MPolynomial<XFieldElement>,
+    ) {
+        prop_assert_eq!(a.clone() * b.clone(), b.clone() * a.clone());
+        prop_assert_eq!(a.clone() * (b.clone() + c.clone()), a.clone() * b + a * c);
+    }
+
+    #[proptest]
+    fn evaluation_is_homomorphic(
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] a: MPolynomial<BFieldElement>,
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] b: MPolynomial<BFieldElement>,
+        #[strategy(vec(arb(), NUM_VARIABLES))] point: Vec<XFieldElement>,
+    ) {
+        let a_eval = a.evaluate(&point);
+        let b_eval = b.evaluate(&point);
+        prop_assert_eq!(a_eval + b_eval, (a.clone() + b.clone()).evaluate(&point));
+        prop_assert_eq!(a_eval - b_eval, (a.clone() - b.clone()).evaluate(&point));
+        prop_assert_eq!(a_eval * b_eval, (a * b).evaluate(&point));
+    }
+
+    #[proptest]
+    fn degree_of_product_is_sum_of_degrees(
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] a: MPolynomial<BFieldElement>,
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))]
+        #[filter(!#b.is_zero() && !#a.is_zero())]
+        b: MPolynomial<BFieldElement>,
+    ) {
+        let product = a.clone() * b.clone();
+        prop_assert_eq!(a.degree() + b.degree(), product.degree());
+        for var in 0..NUM_VARIABLES {
+            prop_assert_eq!(a.degree_in(var) + b.degree_in(var), product.degree_in(var));
+        }
+    }
+
+    #[proptest]
+    fn lifted_univariate_polynomial_agrees_with_original(
+        polynomial: Polynomial<'static, XFieldElement>,
+        #[strategy(0usize..NUM_VARIABLES)] variable: usize,
+        #[strategy(vec(arb(), NUM_VARIABLES))] point: Vec<XFieldElement>,
+    ) {
+        let lifted = MPolynomial::from_univariate(&polynomial, variable, NUM_VARIABLES);
+        prop_assert_eq!(polynomial.degree(), lifted.degree());
+        prop_assert_eq!(polynomial.degree(), lifted.degree_in(variable));
+        prop_assert_eq!(
+            polynomial.evaluate_in_same_field(point[variable]),
+            lifted.evaluate(&point)
+        );
+
+        let derivative = polynomial.formal_derivative();
+        let lifted_derivative = MPolynomial::from_univariate(&derivative, variable, NUM_VARIABLES);
+        prop_assert_eq!(lifted_derivative, lifted.partial_derivative(variable));
+    }
+
+    #[proptest]
+    fn partial_derivative_satisfies_product_rule(
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] a: MPolynomial<BFieldElement>,
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] b: MPolynomial<BFieldElement>,
+        #[strategy(0usize..NUM_VARIABLES)] variable: usize,
+    ) {
+        let derivative_of_product = (a.clone() * b.clone()).partial_derivative(variable);
+        let product_rule =
+            a.partial_derivative(variable) * b.clone() + a * b.partial_derivative(variable);
+        prop_assert_eq!(derivative_of_product, product_rule);
+    }
+
+    #[proptest]
+    fn pow_is_repeated_multiplication(
+        #[strategy(arbitrary_mpolynomial(2))] f: MPolynomial<BFieldElement>,
+        #[strategy(0u32..5)] exponent: u32,
+    ) {
+        let mut expected = MPolynomial::from_constant(bfe!(1), 2);
+        for _ in 0..exponent {
+            expected = expected * f.clone();
+        }
+        prop_assert_eq!(expected, f.pow(exponent));
+    }
+
+    #[proptest(cases = 50)]
+    fn symbolic_evaluation_agrees_with_evaluation(
+        #[strategy(arbitrary_mpolynomial(NUM_VARIABLES))] f: MPolynomial<XFieldElement>,
+        #[strategy(vec(arb(), NUM_VARIABLES))] univariates: Vec<Polynomial<'static, XFieldElement>>,
+        #[strategy(arb())] x: XFieldElement,
+    ) {
+        let composition = f.evaluate_symbolic(&univariates);
+        let point = univariates
+            .iter()
+            .map(|u| u.evaluate_in_same_field(x))
+            .collect_vec();
+        prop_assert_eq!(f.evaluate(&point), composition.evaluate_in_same_field(x));
+
+        let max_degrees = univariates.iter().map(|u| u.degree()).collect_vec();
+        prop_assert!(composition.degree() <= f.symbolic_degree_bound(&max_degrees));
+    }
+
+    #[test]
+    fn symbolic_degree_bound_is_exact_without_cancellation() {
+        let [x, y] = MPolynomial::<BFieldElement>::variables(2)
+            .try_into()
+            .unwrap();
+        let f = x.pow(3) * y.clone() + y.pow(2);
+        assert_eq!(3 * 2 + 5, f.symbolic_degree_bound(&[2, 5]));
+
+        let univariates = [
+            Polynomial::new(bfe_vec![1, 2, 3]),
+            Polynomial::new(bfe_vec![4, 5, 6, 7, 8, 9]),
+        ];
+        assert_eq!(11, f.evaluate_symbolic(&univariates).degree());
+    }
+}
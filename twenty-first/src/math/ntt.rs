@@ -1,4 +1,6 @@
+use std::borrow::Cow;
 use std::ops::MulAssign;
+use std::sync::OnceLock;
 
 use num_traits::ConstOne;
 use num_traits::ConstZero;
@@ -46,15 +48,10 @@ pub fn ntt<FF>(x: &mut [FF])
 where
     FF: FiniteField + MulAssign<BFieldElement>,
 {
-    let slice_len = u32::try_from(x.len()).expect("slice should be no longer than u32::MAX");
-
-    assert!(slice_len == 0 || slice_len.is_power_of_two());
-    let log2_slice_len = slice_len.checked_ilog2().unwrap_or(0);
-
-    // `slice_len` is 0 or a power of two smaller than u32::MAX
-    //  => `unwrap()` never panics
-    let omega = BFieldElement::primitive_root_of_unity(u64::from(slice_len)).unwrap();
-    ntt_unchecked(x, omega, log2_slice_len);
+    let Some(plan) = cached_plan_for_slice(x) else {
+        return;
+    };
+    plan.forward(x);
 }
 
 /// ## Perform INTT on slices of prime-field elements
@@ -86,65 +83,237 @@ pub fn intt<FF>(x: &mut [FF])
 where
     FF: FiniteField + MulAssign<BFieldElement>,
 {
+    let Some(plan) = cached_plan_for_slice(x) else {
+        return;
+    };
+    plan.inverse(x);
+}
+
+/// The [cached](NttPlan::cached) plan matching the length of the slice, or [`None`] if the slice
+/// is empty. Plans for slices longer than 2^[`MAX_LOG2_CACHED_NTT_LEN`] are computed per call.
+///
+/// # Panics
+///
+/// Panics if the length of the input slice is
+/// - not a power of two
+/// - larger than [`u32::MAX`]
+fn cached_plan_for_slice<FF>(x: &[FF]) -> Option<Cow<'static, NttPlan>> {
     let slice_len = u32::try_from(x.len()).expect("slice should be no longer than u32::MAX");
+    if slice_len == 0 {
+        return None;
+    }
 
-    assert!(slice_len == 0 || slice_len.is_power_of_two());
-    let log2_slice_len = slice_len.checked_ilog2().unwrap_or(0);
+    assert!(slice_len.is_power_of_two());
+    Some(NttPlan::cached(slice_len.ilog2()))
+}
 
-    // `slice_len` is 0 or a power of two smaller than u32::MAX
-    //  => `unwrap()` never panics
-    let omega = BFieldElement::primitive_root_of_unity(u64::from(slice_len)).unwrap();
-    ntt_unchecked(x, omega.inverse(), log2_slice_len);
+/// The largest log₂ of a domain length for which a primitive root of unity exists in the
+/// [`BFieldElement`]s.
+pub const MAX_LOG2_NTT_LEN: u32 = 32;
 
-    let n_inv_or_zero = BFieldElement::from(x.len()).inverse_or_zero();
-    for elem in x.iter_mut() {
-        *elem *= n_inv_or_zero
-    }
-}
+/// The largest log₂ of a transform length for which [`NttPlan`]s are [cached](NttPlan::cached)
+/// globally.
+///
+/// A plan holds one twiddle factor per element of the transform, and cached plans are never
+/// freed. Bounding the cached lengths bounds the memory of all cached plans to 16 MiB.
+pub const MAX_LOG2_CACHED_NTT_LEN: u32 = 20;
 
-/// Like [NTT][self::ntt], but with greater control over the root of unity that
-/// is to be used.
+/// Global cache of [`NttPlan`]s, indexed by the log₂ of the transform length.
+static NTT_PLANS: [OnceLock<NttPlan>; MAX_LOG2_CACHED_NTT_LEN as usize + 1] =
+    [const { OnceLock::new() }; MAX_LOG2_CACHED_NTT_LEN as usize + 1];
+
+/// A precomputed plan for [NTT][self::ntt]s and [INTT][self::intt]s of one fixed length.
 ///
-/// Does _not_ check whether
-/// - the passed-in root of unity is indeed a primitive root of unity of the
-///   appropriate order, or whether
-/// - the passed-in log₂ of the slice length matches.
+/// Holds the twiddle factors, i.e., the powers of the primitive root of unity of the plan's
+/// length, as well as their inverses and the inverse of the length. Use a plan if the same
+/// transform length is used repeatedly. The free functions [`ntt`] and [`intt`] use
+/// [cached](Self::cached) plans.
 ///
-/// Use [NTT][self:ntt] if you want a nicer interface.
-#[expect(clippy::many_single_char_names)]
-#[inline]
-fn ntt_unchecked<FF>(x: &mut [FF], omega: BFieldElement, log2_slice_len: u32)
-where
-    FF: FiniteField + MulAssign<BFieldElement>,
-{
-    let slice_len = x.len() as u32;
+/// # Example
+///
+/// ```
+/// # use twenty_first::prelude::*;
+/// # use twenty_first::math::ntt::NttPlan;
+/// let plan = NttPlan::new(3);
+/// let original_values = bfe_vec![0, 1, 1, 2, 3, 5, 8, 13];
+/// let mut transformed_values = original_values.clone();
+/// plan.forward(&mut transformed_values);
+/// plan.inverse(&mut transformed_values);
+/// assert_eq!(original_values, transformed_values);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NttPlan {
+    log2_len: u32,
 
-    for k in 0..slice_len {
-        let rk = bitreverse(k, log2_slice_len);
-        if k < rk {
-            x.swap(rk as usize, k as usize);
+    /// ω^i for i in `0..len/2`, where ω is the primitive `len`th root of unity.
+    twiddles: Vec<BFieldElement>,
+
+    /// ω^(-i) for i in `0..len/2`.
+    inverse_twiddles: Vec<BFieldElement>,
+
+    len_inverse: BFieldElement,
+}
+
+impl NttPlan {
+    /// Precompute the plan for transforms of length 2^`log2_len`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `log2_len` exceeds [`MAX_LOG2_NTT_LEN`].
+    pub fn new(log2_len: u32) -> Self {
+        assert!(
+            log2_len <= MAX_LOG2_NTT_LEN,
+            "log₂ of NTT length must not exceed {MAX_LOG2_NTT_LEN}"
+        );
+        let len = 1_u64 << log2_len;
+        let omega = BFieldElement::primitive_root_of_unity(len).unwrap();
+
+        Self {
+            log2_len,
+            twiddles: Self::powers(omega, len / 2),
+            inverse_twiddles: Self::powers(omega.inverse(), len / 2),
+            len_inverse: BFieldElement::new(len).inverse(),
         }
     }
 
-    let mut m = 1;
-    for _ in 0..log2_slice_len {
-        let w_m = omega.mod_pow_u32(slice_len / (2 * m));
-        let mut k = 0;
-        while k < slice_len {
-            let mut w = BFieldElement::ONE;
-            for j in 0..m {
-                let u = x[(k + j) as usize];
-                let mut v = x[(k + j + m) as usize];
-                v *= w;
-                x[(k + j) as usize] = u + v;
-                x[(k + j + m) as usize] = u - v;
-                w *= w_m;
-            }
+    /// The globally cached plan for transforms of length 2^`log2_len`. The plan is computed on
+    /// first use.
+    ///
+    /// Only plans up to length 2^[`MAX_LOG2_CACHED_NTT_LEN`] are cached. For longer transforms,
+    /// a fresh plan is [computed](Self::new) on every call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `log2_len` exceeds [`MAX_LOG2_NTT_LEN`].
+    pub fn cached(log2_len: u32) -> Cow<'static, Self> {
+        match NTT_PLANS.get(log2_len as usize) {
+            Some(plan) => Cow::Borrowed(plan.get_or_init(|| Self::new(log2_len))),
+            None => Cow::Owned(Self::new(log2_len)),
+        }
+    }
 
-            k += 2 * m;
+    fn powers(base: BFieldElement, num_powers: u64) -> Vec<BFieldElement> {
+        let mut powers = Vec::with_capacity(num_powers.try_into().unwrap());
+        let mut power = BFieldElement::ONE;
+        for _ in 0..num_powers {
+            powers.push(power);
+            power *= base;
         }
+        powers
+    }
 
-        m *= 2;
+    pub fn log2_len(&self) -> u32 {
+        self.log2_len
+    }
+
+    pub fn len(&self) -> usize {
+        1 << self.log2_len
+    }
+
+    /// Always `false`: a plan's length is a power of two.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Perform the [NTT][self::ntt] in-place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the slice does not match the plan's [length](Self::len).
+    pub fn forward<FF>(&self, x: &mut [FF])
+    where
+        FF: FiniteField + MulAssign<BFieldElement>,
+    {
+        self.transform(x, &self.twiddles);
+    }
+
+    /// Perform the [INTT][self::intt] in-place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the slice does not match the plan's [length](Self::len).
+    pub fn inverse<FF>(&self, x: &mut [FF])
+    where
+        FF: FiniteField + MulAssign<BFieldElement>,
+    {
+        self.transform(x, &self.inverse_twiddles);
+        for elem in x.iter_mut() {
+            *elem *= self.len_inverse;
+        }
+    }
+
+    /// Evaluate the polynomial with coefficients `x` on the coset `offset·⟨ω⟩`, where ω is the
+    /// primitive root of unity of the plan's [length](Self::len). The result is written to `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the slice does not match the plan's [length](Self::len).
+    pub fn coset_forward<FF>(&self, x: &mut [FF], offset: BFieldElement)
+    where
+        FF: FiniteField + MulAssign<BFieldElement>,
+    {
+        Self::scale_by_powers(x, offset);
+        self.forward(x);
+    }
+
+    /// The inverse of [`coset_forward`](Self::coset_forward): interpolate the values `x`, given
+    /// on the coset `offset·⟨ω⟩`, writing the coefficients to `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if
+    /// - the length of the slice does not match the plan's [length](Self::len), or
+    /// - the `offset` is zero.
+    pub fn coset_inverse<FF>(&self, x: &mut [FF], offset: BFieldElement)
+    where
+        FF: FiniteField + MulAssign<BFieldElement>,
+    {
+        self.inverse(x);
+        Self::scale_by_powers(x, offset.inverse());
+    }
+
+    fn scale_by_powers<FF>(x: &mut [FF], base: BFieldElement)
+    where
+        FF: MulAssign<BFieldElement>,
+    {
+        let mut power = BFieldElement::ONE;
+        for elem in x.iter_mut() {
+            *elem *= power;
+            power *= base;
+        }
+    }
+
+    #[expect(clippy::many_single_char_names)]
+    fn transform<FF>(&self, x: &mut [FF], twiddles: &[BFieldElement])
+    where
+        FF: FiniteField + MulAssign<BFieldElement>,
+    {
+        let n = self.len();
+        assert_eq!(n, x.len(), "slice length must match NTT plan length");
+
+        let log2_len = self.log2_len as usize;
+        for k in 0..n {
+            let rk = bitreverse_usize(k, log2_len);
+            if k < rk {
+                x.swap(rk, k);
+            }
+        }
+
+        let mut m = 1;
+        while m < n {
+            let twiddle_stride = n / (2 * m);
+            for k in (0..n).step_by(2 * m) {
+                for j in 0..m {
+                    let u = x[k + j];
+                    let mut v = x[k + j + m];
+                    v *= twiddles[j * twiddle_stride];
+                    x[k + j] = u + v;
+                    x[k + j + m] = u - v;
+                }
+            }
+
+            m *= 2;
+        }
     }
 }
 
@@ -276,16 +445,6 @@ pub fn unscale(array: &mut [BFieldElement]) {
     }
 }
 
-#[inline]
-fn bitreverse(mut n: u32, l: u32) -> u32 {
-    let mut r = 0;
-    for _ in 0..l {
-        r = (r << 1) | (n & 1);
-        n >>= 1;
-    }
-    r
-}
-
 #[cfg(test)]
 mod fast_ntt_attempt_tests {
     use itertools::Itertools;
@@ -551,4 +710,82 @@ mod fast_ntt_attempt_tests {
             assert_eq!(a1, a2);
         }
     }
+
+    #[proptest(cases = 20)]
+    fn plan_agrees_with_free_functions(
+        #[strategy(0_u32..12)] log2_len: u32,
+        #[strategy(vec(arb(), 1 << #log2_len))] input: Vec<XFieldElement>,
+    ) {
+        let plan = NttPlan::new(log2_len);
+
+        let mut plan_output = input.clone();
+        plan.forward(&mut plan_output);
+        let mut free_output = input.clone();
+        ntt(&mut free_output);
+        prop_assert_eq!(&free_output, &plan_output);
+
+        plan.inverse(&mut plan_output);
+        intt(&mut free_output);
+        prop_assert_eq!(&input, &plan_output);
+        prop_assert_eq!(input, free_output);
+    }
+
+    #[proptest(cases = 20)]
+    fn coset_forward_evaluates_polynomial_on_coset(
+        #[strategy(0_u32..10)] log2_len: u32,
+        #[strategy(vec(arb(), 1 << #log2_len))] coefficients: Vec<BFieldElement>,
+        #[strategy(arb())]
+        #[filter(!#offset.is_zero())]
+        offset: BFieldElement,
+    ) {
+        let plan = NttPlan::new(log2_len);
+        let mut values = coefficients.clone();
+        plan.coset_forward(&mut values, offset);
+
+        let polynomial = Polynomial::new(coefficients.clone());
+        let omega = BFieldElement::primitive_root_of_unity(1 << log2_len).unwrap();
+        let expected = (0..plan.len())
+            .map(|i| offset * omega.mod_pow(i.try_into().unwrap()))
+            .map(|x| polynomial.evaluate_in_same_field(x))
+            .collect_vec();
+        prop_assert_eq!(expected, values.clone());
+
+        plan.coset_inverse(&mut values, offset);
+        prop_assert_eq!(coefficients, values);
+    }
+
+    #[test]
+    fn cached_plan_is_computed_only_once() {
+        let plan = NttPlan::cached(5);
+        assert_eq!(32, plan.len());
+        assert!(matches!(plan, Cow::Borrowed(_)));
+        assert!(std::ptr::eq(&*plan, &*NttPlan::cached(5)));
+        assert_eq!(NttPlan::new(5), *plan);
+    }
+
+    #[test]
+    fn plans_for_long_transforms_are_not_cached() {
+        let plan = NttPlan::cached(MAX_LOG2_CACHED_NTT_LEN + 1);
+        assert_eq!(1 << (MAX_LOG2_CACHED_NTT_LEN + 1), plan.len());
+        assert!(matches!(plan, Cow::Owned(_)));
+    }
+
+    #[test]
+    #[should_panic(expected = "must not exceed")]
+    fn cached_plan_for_too_large_length_cannot_be_created() {
+        NttPlan::cached(MAX_LOG2_NTT_LEN + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "must match")]
+    fn plan_panics_on_slice_of_wrong_length() {
+        let mut values = bfe_vec![1, 2, 3, 4];
+        NttPlan::new(3).forward(&mut values);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not exceed")]
+    fn plan_for_too_large_length_cannot_be_created() {
+        NttPlan::new(MAX_LOG2_NTT_LEN + 1);
+    }
 }
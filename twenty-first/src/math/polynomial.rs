@@ -22,6 +22,9 @@ use num_traits::ConstOne;
 use num_traits::ConstZero;
 use num_traits::One;
 use num_traits::Zero;
+use rand::distributions::Distribution;
+use rand::distributions::Standard;
+use rand::Rng;
 use rayon::prelude::*;
 
 use super::traits::PrimitiveRootOfUnity;
@@ -30,6 +33,7 @@ use crate::math::ntt::intt;
 use crate::math::ntt::ntt;
 use crate::math::traits::FiniteField;
 use crate::math::traits::ModPowU32;
+use crate::math::x_field_element::EXTENSION_DEGREE;
 use crate::prelude::BFieldElement;
 use crate::prelude::Inverse;
 use crate::prelude::XFieldElement;
@@ -301,7 +305,7 @@ where
             return Polynomial::zero();
         }
 
-        let squared_coefficient_len = self.degree() as usize * 2 + 1;
+        let squared_coefficient_len = degree as usize * 2 + 1;
         let zero = FF::ZERO;
         let one = FF::ONE;
         let two = one + one;
@@ -603,6 +607,12 @@ where
     /// Extracted from `cargo bench --bench poly_mul` on mjolnir.
     const FAST_MULTIPLY_CUTOFF_THRESHOLD: isize = 1 << 8;
 
+    /// [Fast xgcd](Self::fast_xgcd) switches to the Euclidean algorithm for polynomials of degree
+    /// less than this threshold.
+    ///
+    /// Extracted from `cargo bench --bench poly_xgcd`.
+    const FAST_XGCD_CUTOFF_THRESHOLD: isize = 1 << 9;
+
     /// [Fast interpolation](Self::fast_interpolate) is slower than
     /// [Lagrange interpolation](Self::lagrange_interpolate) below this threshold.
     ///
@@ -708,7 +718,7 @@ where
 
         // A benchmark run on sword_smith's PC revealed that `fast_square` was faster when the input
         // size exceeds a length of 64.
-        let squared_coefficient_len = self.degree() as usize * 2 + 1;
+        let squared_coefficient_len = degree as usize * 2 + 1;
         if squared_coefficient_len > 64 {
             return self.fast_square();
         }
@@ -719,11 +729,12 @@ where
         let mut squared_coefficients = vec![zero; squared_coefficient_len];
 
         // TODO: Review.
-        for i in 0..self.coefficients.len() {
+        let num_coefficients = degree as usize + 1;
+        for i in 0..num_coefficients {
             let ci = self.coefficients[i];
             squared_coefficients[2 * i] += ci * ci;
 
-            for j in i + 1..self.coefficients.len() {
+            for j in i + 1..num_coefficients {
                 let cj = self.coefficients[j];
                 squared_coefficients[i + j] += two * ci * cj;
             }
@@ -1288,6 +1299,132 @@ where
     }
 }
 
+impl<FF> Polynomial<'_, FF>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+{
+    /// Extended Euclidean algorithm with polynomials. Computes the same greatest
+    /// common divisor and Bézout coefficients as [`Self::xgcd`], but is
+    /// asymptotically faster.
+    ///
+    /// Uses the half-GCD algorithm, which jumps over intermediate remainders of the
+    /// Euclidean algorithm by recursing on the most significant coefficients of the
+    /// operands. The time complexity is in O(n·log²(n)), where `n` is the larger of
+    /// the degrees of the operands. The time complexity of [`Self::xgcd`] is in O(n²).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use twenty_first::prelude::Polynomial;
+    /// # use twenty_first::prelude::BFieldElement;
+    /// let x = Polynomial::<BFieldElement>::from([1, 0, 1]);
+    /// let y = Polynomial::<BFieldElement>::from([1, 1]);
+    /// let (gcd, a, b) = Polynomial::fast_xgcd(x.clone(), y.clone());
+    /// assert_eq!(gcd, a.clone() * x.clone() + b.clone() * y.clone());
+    /// assert_eq!((gcd, a, b), Polynomial::xgcd(x, y));
+    /// ```
+    pub fn fast_xgcd(
+        x: Self,
+        y: Polynomial<'_, FF>,
+    ) -> (
+        Polynomial<'static, FF>,
+        Polynomial<'static, FF>,
+        Polynomial<'static, FF>,
+    ) {
+        let mut remainders = [x.into_owned(), y.into_owned()];
+        let mut cofactors = EuclideanMatrix::identity();
+        let is_large = |[r0, r1]: &[Polynomial<'static, FF>; 2]| {
+            r0.degree().max(r1.degree()) >= Self::FAST_XGCD_CUTOFF_THRESHOLD
+        };
+
+        // Alternate between a single step of the Euclidean algorithm, guaranteeing
+        // that the degrees of the remainders differ, and the half-GCD, which requires
+        // exactly that.
+        while is_large(&remainders) && !remainders[1].is_zero() {
+            let [dividend, divisor] = remainders;
+            let (quotient, remainder) = dividend.divide(&divisor);
+            cofactors = cofactors.euclidean_step(&quotient);
+            remainders = [divisor, remainder];
+            if remainders[1].is_zero() {
+                break;
+            }
+
+            let half_gcd = Self::half_gcd(&remainders[0], &remainders[1]);
+            remainders = half_gcd.apply(&remainders);
+            cofactors = half_gcd.multiply(&cofactors);
+        }
+
+        // The Euclidean algorithm finishes the job for small remainders. Since it
+        // normalizes the gcd, so are the combined Bézout coefficients.
+        let [r0, r1] = remainders;
+        let (gcd, a, b) = Self::xgcd(r0, r1);
+        let [[m00, m01], [m10, m11]] = cofactors.0;
+        let a_combined = a.multiply(&m00) + b.multiply(&m10);
+        let b_combined = a.multiply(&m01) + b.multiply(&m11);
+        (gcd, a_combined, b_combined)
+    }
+
+    /// The matrix of the steps of the Euclidean algorithm that reduce `(x, y)` to
+    /// consecutive remainders `(r_i, r_{i+1})` with deg(r_i) ≥ m > deg(r_{i+1}),
+    /// where m = ⌈deg(x) / 2⌉.
+    ///
+    /// Requires deg(x) > deg(y).
+    fn half_gcd(x: &Self, y: &Polynomial<'_, FF>) -> EuclideanMatrix<FF> {
+        debug_assert!(x.degree() > y.degree());
+
+        let degree = x.degree();
+        let half_degree = (degree + 1) / 2;
+        if y.degree() < half_degree {
+            return EuclideanMatrix::identity();
+        }
+        if degree < Self::FAST_XGCD_CUTOFF_THRESHOLD {
+            return Self::naive_half_gcd(x, y, half_degree);
+        }
+
+        // The quotients of the high halves agree with those of the full polynomials
+        // for the first half of the Euclidean algorithm.
+        let shift = half_degree as usize;
+        let [x_high, y_high] = [x.div_x_to_the_n(shift), y.div_x_to_the_n(shift)];
+        let mut matrix = Self::half_gcd(&x_high, &y_high);
+        let [r0, r1] = matrix.apply(&[x.clone().into_owned(), y.clone().into_owned()]);
+        if r1.degree() < half_degree {
+            return matrix;
+        }
+
+        let (quotient, r2) = r0.divide(&r1);
+        matrix = matrix.euclidean_step(&quotient);
+
+        // Reduce the remaining degree gap of size deg(r1) - m by recursing on the
+        // leading 2·(deg(r1) - m) + 1 coefficients.
+        let r1_shift = (2 * half_degree - r1.degree()) as usize;
+        let [r1_high, r2_high] = [r1.div_x_to_the_n(r1_shift), r2.div_x_to_the_n(r1_shift)];
+        let second_matrix = Self::half_gcd(&r1_high, &r2_high);
+
+        second_matrix.multiply(&matrix)
+    }
+
+    /// Like [`Self::half_gcd`], but using the Euclidean algorithm.
+    fn naive_half_gcd(x: &Self, y: &Polynomial<'_, FF>, half_degree: isize) -> EuclideanMatrix<FF> {
+        let mut matrix = EuclideanMatrix::identity();
+        let mut x = x.clone().into_owned();
+        let mut y = y.clone().into_owned();
+        while y.degree() >= half_degree {
+            let (quotient, remainder) = x.naive_divide(&y);
+            matrix = matrix.euclidean_step(&quotient);
+            x = y;
+            y = remainder;
+        }
+
+        matrix
+    }
+
+    /// `self / x^n`, discarding the remainder.
+    fn div_x_to_the_n(&self, n: usize) -> Polynomial<'static, FF> {
+        let coefficients = self.coefficients.get(n..).unwrap_or_default();
+        Polynomial::new(coefficients.to_vec())
+    }
+}
+
 impl<FF> Polynomial<'static, FF>
 where
     FF: FiniteField + MulAssign<BFieldElement>,
@@ -2298,6 +2435,374 @@ impl Polynomial<'_, BFieldElement> {
 
         Polynomial::new(coeffs.into_iter().map(|c| c.unlift().unwrap()).collect())
     }
+
+    /// All distinct roots of the polynomial in the [base field](BFieldElement), in no particular
+    /// order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is zero, since every field element is a root of the zero
+    /// polynomial.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use twenty_first::prelude::*;
+    /// // x² + 7 has no roots in the base field
+    /// let no_roots = Polynomial::new(bfe_vec![7, 0, 1]);
+    /// let polynomial: Polynomial<_> = Polynomial::zerofier(&bfe_vec![3, 1, 4]) * no_roots;
+    /// let mut roots = polynomial.roots();
+    /// roots.sort_by_key(|root| root.value());
+    /// assert_eq!(bfe_vec![1, 3, 4], roots);
+    /// ```
+    pub fn roots(&self) -> Vec<BFieldElement> {
+        self.roots_in_field_of_order_p_to_the(1)
+    }
+
+    /// Whether the polynomial is irreducible over the [base field](BFieldElement), _i.e._, has
+    /// positive degree and is not the product of two polynomials of lower degree.
+    ///
+    /// Uses [Rabin's test][rabin]: a polynomial `f` of degree `n` is irreducible if and only if
+    /// `f` divides `x^(p^n) - x`, and `gcd(f, x^(p^(n/q)) - x) = 1` for every prime divisor `q`
+    /// of `n`.
+    ///
+    /// [rabin]: https://en.wikipedia.org/wiki/Factorization_of_polynomials_over_finite_fields#Rabin's_test_of_irreducibility
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use twenty_first::prelude::*;
+    /// assert!(XFieldElement::shah_polynomial().is_irreducible());
+    /// assert!(!Polynomial::new(bfe_vec![-1, 0, 1]).is_irreducible()); // x² - 1
+    /// ```
+    pub fn is_irreducible(&self) -> bool {
+        let degree = match usize::try_from(self.degree()) {
+            Ok(0) | Err(_) => return false,
+            Ok(1) => return true,
+            Ok(degree) => degree,
+        };
+
+        let maximal_proper_divisors = Self::prime_divisors(degree)
+            .into_iter()
+            .map(|prime| degree / prime)
+            .collect_vec();
+
+        let x = Polynomial::x_to_the(1);
+        let mut x_to_the_p_to_the_k = x.clone();
+        for k in 1..degree {
+            x_to_the_p_to_the_k = x_to_the_p_to_the_k.mod_pow_u64(BFieldElement::P, self);
+            if maximal_proper_divisors.contains(&k) {
+                let (gcd, _, _) =
+                    Polynomial::xgcd(self.clone(), x_to_the_p_to_the_k.clone() - x.clone());
+                if gcd.degree() > 0 {
+                    return false;
+                }
+            }
+        }
+
+        x_to_the_p_to_the_k.mod_pow_u64(BFieldElement::P, self) == x
+    }
+
+    /// The complete factorization into monic irreducible polynomials over the [base
+    /// field](BFieldElement), together with their multiplicities.
+    ///
+    /// The factors are pairwise distinct and sorted by degree, then by coefficients. Their
+    /// product, taking multiplicities into account, is `self` divided by its leading coefficient.
+    /// The factorization of a constant polynomial is empty.
+    ///
+    /// Combines [square-free](Self::square_free_factorization),
+    /// [distinct-degree](Self::distinct_degree_factorization), and
+    /// [equal-degree](Self::equal_degree_factorization) factorization.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use twenty_first::prelude::*;
+    /// let x_squared_plus_7 = Polynomial::new(bfe_vec![7, 0, 1]);
+    /// let x_plus_1 = Polynomial::new(bfe_vec![1, 1]);
+    /// let poly = x_squared_plus_7.clone() * x_plus_1.clone() * x_plus_1.clone() * bfe!(5);
+    /// assert_eq!(vec![(x_plus_1, 2), (x_squared_plus_7, 1)], poly.factor());
+    /// ```
+    pub fn factor(&self) -> Vec<(Polynomial<'static, BFieldElement>, usize)> {
+        let mut factors = vec![];
+        for (square_free_factor, multiplicity) in self.square_free_factorization() {
+            for (equal_degree_factors, degree) in square_free_factor.distinct_degree_factorization()
+            {
+                let irreducible_factors = equal_degree_factors.equal_degree_factorization(degree);
+                factors.extend(irreducible_factors.into_iter().map(|f| (f, multiplicity)));
+            }
+        }
+
+        factors.sort_by_key(|(factor, _)| {
+            let coefficients = factor
+                .coefficients()
+                .iter()
+                .map(|c| c.value())
+                .collect_vec();
+            (factor.degree(), coefficients)
+        });
+        factors
+    }
+
+    /// Pairwise coprime, monic, square-free polynomials `f_i` with multiplicities `i` such that
+    /// `self` is the product of all `f_i^i`, up to its leading coefficient. Only factors of
+    /// positive degree are returned.
+    ///
+    /// Uses [Yun's algorithm][yun], which relies on the [formal
+    /// derivative](Self::formal_derivative). Since the degree of any polynomial is smaller than
+    /// the field's characteristic, the formal derivative of a non-constant polynomial is never
+    /// zero, and the algorithm works as it does over the rationals.
+    ///
+    /// [yun]: https://en.wikipedia.org/wiki/Square-free_polynomial#Yun's_algorithm
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is zero.
+    pub fn square_free_factorization(&self) -> Vec<(Polynomial<'static, BFieldElement>, usize)> {
+        assert!(self.degree() >= 0, "cannot factor the zero polynomial");
+        if self.degree() == 0 {
+            return vec![];
+        }
+
+        let derivative = self.formal_derivative();
+        let (common_part, _, _) = Polynomial::xgcd(self.clone(), derivative.clone());
+        let mut remaining = self.divide(&common_part).0;
+        let mut cofactor = derivative.divide(&common_part).0;
+
+        let mut factors = vec![];
+        for multiplicity in 1.. {
+            let difference = cofactor - remaining.formal_derivative();
+            let (factor, _, _) = Polynomial::xgcd(remaining.clone(), difference.clone());
+            remaining = remaining.divide(&factor).0;
+            cofactor = difference.divide(&factor).0;
+            if factor.degree() > 0 {
+                factors.push((factor, multiplicity));
+            }
+            if remaining.degree() < 1 {
+                break;
+            }
+        }
+
+        factors
+    }
+
+    /// For every `d`, the product of all monic irreducible factors of degree `d`, as pairs of
+    /// that product and `d`. Only products of positive degree are returned, in order of
+    /// increasing `d`.
+    ///
+    /// The polynomial must be [square-free](Self::square_free_factorization). For every `d`,
+    /// the product of all irreducible factors whose degree divides `d` is `gcd(f, x^(p^d) - x)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is zero. May panic, or return a wrong result, if the
+    /// polynomial is not square-free.
+    pub fn distinct_degree_factorization(
+        &self,
+    ) -> Vec<(Polynomial<'static, BFieldElement>, usize)> {
+        assert!(self.degree() >= 0, "cannot factor the zero polynomial");
+
+        let x = Polynomial::x_to_the(1);
+        let mut remaining = self.scalar_mul(self.leading_coefficient().unwrap().inverse());
+        let mut x_to_the_p_to_the_d = x.clone();
+        let mut factors = vec![];
+        for degree in 1.. {
+            if remaining.degree() < 2 * degree as isize {
+                break;
+            }
+
+            x_to_the_p_to_the_d = x_to_the_p_to_the_d.mod_pow_u64(BFieldElement::P, &remaining);
+            let difference = x_to_the_p_to_the_d.clone() - x.clone();
+            let (factor, _, _) = Polynomial::xgcd(remaining.clone(), difference);
+            if factor.degree() > 0 {
+                remaining = remaining.divide(&factor).0;
+                x_to_the_p_to_the_d = x_to_the_p_to_the_d.reduce(&remaining);
+                factors.push((factor, degree));
+            }
+        }
+
+        if let Ok(degree @ 1..) = usize::try_from(remaining.degree()) {
+            factors.push((remaining, degree));
+        }
+        factors
+    }
+
+    /// Split a product of distinct monic irreducible polynomials, all of the given degree, into
+    /// its factors, in no particular order.
+    ///
+    /// Uses the [Cantor–Zassenhaus][cz] algorithm. For a random polynomial `a`, the polynomial
+    /// `a^((p^d - 1)/2) - 1` is divisible by roughly half of the irreducible factors of degree
+    /// `d`, and taking its gcd with `self` usually gives a non-trivial factor.
+    ///
+    /// [cz]: https://en.wikipedia.org/wiki/Cantor%E2%80%93Zassenhaus_algorithm
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is zero, or if the given degree is zero. May loop indefinitely,
+    /// or return a wrong result, if the polynomial is not a product of distinct irreducible
+    /// polynomials of the given degree.
+    pub fn equal_degree_factorization(
+        &self,
+        degree: usize,
+    ) -> Vec<Polynomial<'static, BFieldElement>> {
+        assert!(self.degree() >= 0, "cannot factor the zero polynomial");
+        assert_ne!(0, degree, "irreducible factors have positive degree");
+
+        let monic = self.scalar_mul(self.leading_coefficient().unwrap().inverse());
+        let mut factors = vec![];
+        monic.split_into_equal_degree_factors(degree, 1, &mut factors);
+        factors
+    }
+
+    /// The distinct prime divisors of `n`, in increasing order.
+    fn prime_divisors(mut n: usize) -> Vec<usize> {
+        let mut prime_divisors = vec![];
+        let mut candidate = 2;
+        while candidate * candidate <= n {
+            if n.is_multiple_of(candidate) {
+                prime_divisors.push(candidate);
+                while n.is_multiple_of(candidate) {
+                    n /= candidate;
+                }
+            }
+            candidate += 1;
+        }
+        if n > 1 {
+            prime_divisors.push(n);
+        }
+
+        prime_divisors
+    }
+}
+
+impl Polynomial<'_, XFieldElement> {
+    /// All distinct roots of the polynomial in the [extension field](XFieldElement), in no
+    /// particular order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polynomial is zero, since every field element is a root of the zero
+    /// polynomial.
+    pub fn roots(&self) -> Vec<XFieldElement> {
+        self.roots_in_field_of_order_p_to_the(EXTENSION_DEGREE)
+    }
+}
+
+impl<FF> Polynomial<'_, FF>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+    Standard: Distribution<FF>,
+{
+    /// All distinct roots of the polynomial in the field `FF`, which must have order q = p^k,
+    /// where p is [`BFieldElement::P`] and k is the `extension_degree`.
+    ///
+    /// The product of all linear factors is gcd(f, x^q - x), which is then split into the linear
+    /// factors using [Cantor–Zassenhaus][cz] equal-degree factorization.
+    ///
+    /// [cz]: https://en.wikipedia.org/wiki/Cantor%E2%80%93Zassenhaus_algorithm
+    fn roots_in_field_of_order_p_to_the(&self, extension_degree: usize) -> Vec<FF> {
+        assert!(
+            self.degree() >= 0,
+            "every field element is a root of the zero polynomial"
+        );
+        if self.degree() < 1 {
+            return vec![];
+        }
+
+        let x = Polynomial::new(vec![FF::ZERO, FF::ONE]);
+        let x_to_the_q = x.iterated_frobenius(extension_degree, self);
+        let (product_of_linear_factors, _, _) = Polynomial::xgcd(self.clone(), x_to_the_q - x);
+
+        let mut linear_factors = vec![];
+        product_of_linear_factors.split_into_equal_degree_factors(
+            1,
+            extension_degree,
+            &mut linear_factors,
+        );
+        linear_factors
+            .into_iter()
+            .map(|factor| -factor.coefficients[0] / factor.coefficients[1])
+            .collect()
+    }
+
+    /// Compute `self^(p^k) mod modulus` by raising to the p-th power `k` times.
+    fn iterated_frobenius(
+        &self,
+        k: usize,
+        modulus: &Polynomial<'_, FF>,
+    ) -> Polynomial<'static, FF> {
+        let mut power = self.reduce(modulus);
+        for _ in 0..k {
+            power = power.mod_pow_u64(BFieldElement::P, modulus);
+        }
+        power
+    }
+
+    /// Compute `self^exponent mod modulus` using square-and-multiply.
+    fn mod_pow_u64(&self, exponent: u64, modulus: &Polynomial<'_, FF>) -> Polynomial<'static, FF> {
+        let base = self.reduce(modulus);
+        let mut acc = Polynomial::one();
+        for bit_index in (0..u64::BITS - exponent.leading_zeros()).rev() {
+            acc = acc.square().reduce(modulus);
+            if (exponent >> bit_index) & 1 == 1 {
+                acc = acc.multiply(&base).reduce(modulus);
+            }
+        }
+        acc
+    }
+
+    /// Split a product of distinct irreducible polynomials over the field `FF` of order
+    /// `q = p^k`, all of degree `factor_degree`, into its factors, where `k` is the
+    /// `extension_degree`. This is the recursive part of [Cantor–Zassenhaus][cz]
+    /// equal-degree factorization.
+    ///
+    /// For a random polynomial `a`, the polynomial `a^((q^d - 1)/2) - 1` is divisible by
+    /// roughly half of the irreducible factors of degree `d`. Its gcd with `self` is a
+    /// non-trivial factor with probability about 1/2.
+    ///
+    /// [cz]: https://en.wikipedia.org/wiki/Cantor%E2%80%93Zassenhaus_algorithm
+    fn split_into_equal_degree_factors(
+        &self,
+        factor_degree: usize,
+        extension_degree: usize,
+        factors: &mut Vec<Polynomial<'static, FF>>,
+    ) {
+        let Ok(self_degree) = usize::try_from(self.degree()) else {
+            return;
+        };
+        if self_degree < factor_degree || factor_degree == 0 {
+            return;
+        }
+        if self_degree == factor_degree {
+            return factors.push(self.clone().into_owned());
+        }
+
+        // (q^d-1)/2 = (p-1)/2 · (1 + p + … + p^(k·d-1)), avoiding exponents exceeding 64 bits
+        let mut rng = rand::thread_rng();
+        let one = Polynomial::one();
+        loop {
+            let random_polynomial = Polynomial::new((0..self_degree).map(|_| rng.gen()).collect());
+            let mut frobenius_power = random_polynomial.reduce(self);
+            let mut norm = frobenius_power.clone();
+            for _ in 1..extension_degree * factor_degree {
+                frobenius_power = frobenius_power.mod_pow_u64(BFieldElement::P, self);
+                norm = norm.multiply(&frobenius_power).reduce(self);
+            }
+            let legendre_symbols = norm.mod_pow_u64((BFieldElement::P - 1) / 2, self);
+
+            let (factor, _, _) = Polynomial::xgcd(self.clone(), legendre_symbols - one.clone());
+            if (1..self.degree()).contains(&factor.degree()) {
+                let (cofactor, _) = self.divide(&factor);
+                factor.split_into_equal_degree_factors(factor_degree, extension_degree, factors);
+                cofactor.split_into_equal_degree_factors(factor_degree, extension_degree, factors);
+                return;
+            }
+        }
+    }
 }
 
 impl<const N: usize, FF, E> From<[E; N]> for Polynomial<'static, FF>
@@ -2593,8 +3098,62 @@ where
     }
 }
 
+/// A 2×2 matrix of polynomials, accumulating steps of the Euclidean algorithm. If the
+/// matrix was built from the steps that reduce `(x, y)` to `(r_i, r_{i+1})`, then
+/// `matrix · (x, y)ᵀ = (r_i, r_{i+1})ᵀ`. See [`Polynomial::fast_xgcd`].
+#[derive(Debug, Clone)]
+struct EuclideanMatrix<FF: FiniteField + 'static>([[Polynomial<'static, FF>; 2]; 2]);
+
+impl<FF> EuclideanMatrix<FF>
+where
+    FF: FiniteField + MulAssign<BFieldElement> + 'static,
+{
+    fn identity() -> Self {
+        Self([
+            [Polynomial::one(), Polynomial::zero()],
+            [Polynomial::zero(), Polynomial::one()],
+        ])
+    }
+
+    /// Left-multiply `self` with the matrix `[[0, 1], [1, -quotient]]`, corresponding
+    /// to one step `(x, y) ↦ (y, x - quotient·y)` of the Euclidean algorithm.
+    fn euclidean_step(self, quotient: &Polynomial<'_, FF>) -> Self {
+        let [[m00, m01], [m10, m11]] = self.0;
+        let new_m10 = m00 - quotient.multiply(&m10);
+        let new_m11 = m01 - quotient.multiply(&m11);
+        Self([[m10, m11], [new_m10, new_m11]])
+    }
+
+    /// Compute `self · rhs`.
+    fn multiply(&self, rhs: &Self) -> Self {
+        let [[a, b], [c, d]] = &self.0;
+        let [[e, f], [g, h]] = &rhs.0;
+        Self([
+            [
+                a.fast_multiply(e) + b.fast_multiply(g),
+                a.fast_multiply(f) + b.fast_multiply(h),
+            ],
+            [
+                c.fast_multiply(e) + d.fast_multiply(g),
+                c.fast_multiply(f) + d.fast_multiply(h),
+            ],
+        ])
+    }
+
+    /// Compute `self · (x, y)ᵀ`.
+    fn apply(&self, [x, y]: &[Polynomial<'static, FF>; 2]) -> [Polynomial<'static, FF>; 2] {
+        let [[a, b], [c, d]] = &self.0;
+        [
+            a.fast_multiply(x) + b.fast_multiply(y),
+            c.fast_multiply(x) + d.fast_multiply(y),
+        ]
+    }
+}
+
 #[cfg(test)]
 mod test_polynomials {
+    use std::collections::HashSet;
+
     use num_traits::ConstZero;
     use proptest::collection::size_range;
     use proptest::collection::vec;
@@ -3373,6 +3932,239 @@ mod test_polynomials {
         );
     }
 
+    #[proptest(cases = 50)]
+    fn roots_of_zerofier_are_its_domain(
+        #[any(size_range(..20).lift())] domain: HashSet<BFieldElement>,
+    ) {
+        let zerofier = Polynomial::zerofier(&domain.iter().copied().collect_vec());
+        let roots = zerofier.roots();
+        prop_assert_eq!(domain.len(), roots.len());
+        prop_assert_eq!(domain, roots.into_iter().collect());
+    }
+
+    #[proptest(cases = 20)]
+    fn roots_of_zerofier_over_extension_field_are_its_domain(
+        #[any(size_range(..10).lift())] domain: HashSet<XFieldElement>,
+    ) {
+        let domain = domain.into_iter().collect_vec();
+        let roots = Polynomial::zerofier(&domain).roots();
+        prop_assert_eq!(domain.len(), roots.len());
+        for root in roots {
+            prop_assert!(domain.contains(&root));
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn repeated_roots_are_reported_once(
+        #[strategy(vec(arb(), 1..10))] domain: Vec<BFieldElement>,
+        #[strategy(1_usize..4)] multiplicity: usize,
+    ) {
+        let repeated_domain = domain.repeat(multiplicity);
+        let roots = Polynomial::zerofier(&repeated_domain).roots();
+        prop_assert_eq!(domain.iter().unique().count(), roots.len());
+        for root in roots {
+            prop_assert!(domain.contains(&root));
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn roots_of_polynomial_without_roots_in_base_field_are_empty(
+        #[strategy(vec(arb(), 0..10))] domain: Vec<BFieldElement>,
+    ) {
+        // x^2 - 7 is irreducible over the base field since 7 is a quadratic non-residue
+        let irreducible = Polynomial::new(bfe_vec![-7, 0, 1]);
+        let poly = Polynomial::zerofier(&domain) * irreducible.clone() * irreducible;
+        let roots = poly.roots();
+        prop_assert_eq!(domain.iter().unique().count(), roots.len());
+    }
+
+    #[proptest(cases = 50)]
+    fn roots_of_arbitrary_polynomial_are_distinct_roots(poly: BfePoly) {
+        prop_assume!(!poly.is_zero());
+        let roots = poly.roots();
+        prop_assert!(roots.len() as isize <= poly.degree().max(0));
+        prop_assert!(roots.iter().all_unique());
+        for root in roots {
+            prop_assert_eq!(BFieldElement::ZERO, poly.evaluate_in_same_field(root));
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn roots_of_arbitrary_extension_field_polynomial_are_distinct_roots(
+        #[strategy(vec(arb(), 1..10))] coefficients: Vec<XFieldElement>,
+    ) {
+        let poly = Polynomial::new(coefficients);
+        prop_assume!(!poly.is_zero());
+        let roots = poly.roots();
+        prop_assert!(roots.len() as isize <= poly.degree().max(0));
+        prop_assert!(roots.iter().all_unique());
+        for root in roots {
+            prop_assert_eq!(XFieldElement::ZERO, poly.evaluate_in_same_field(root));
+        }
+    }
+
+    #[proptest]
+    fn constant_polynomial_has_no_roots(#[filter(!#c.is_zero())] c: BFieldElement) {
+        prop_assert!(Polynomial::from_constant(c).roots().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "every field element is a root of the zero polynomial")]
+    fn finding_roots_of_zero_polynomial_panics() {
+        let _ = BfePoly::zero().roots();
+    }
+
+    /// A strategy for non-zero polynomials of the given maximal degree.
+    fn non_zero_polynomial(max_degree: usize) -> impl Strategy<Value = BfePoly> {
+        vec(arb(), 1..=max_degree + 1)
+            .prop_map(Polynomial::new)
+            .prop_filter("polynomial must be non-zero", |poly| !poly.is_zero())
+    }
+
+    #[test]
+    fn constant_polynomials_are_not_irreducible_and_have_no_factors() {
+        let constant = Polynomial::from_constant(bfe!(42));
+        assert!(!constant.is_irreducible());
+        assert!(constant.factor().is_empty());
+        assert!(constant.square_free_factorization().is_empty());
+        assert!(!BfePoly::zero().is_irreducible());
+    }
+
+    #[proptest]
+    fn linear_polynomials_are_irreducible(
+        #[filter(!#leading_coefficient.is_zero())] leading_coefficient: BFieldElement,
+        constant_term: BFieldElement,
+    ) {
+        let linear = Polynomial::new(vec![constant_term, leading_coefficient]);
+        prop_assert!(linear.is_irreducible());
+    }
+
+    #[proptest(cases = 50)]
+    fn product_of_non_constant_polynomials_is_reducible(
+        #[strategy(non_zero_polynomial(4))]
+        #[filter(#f.degree() > 0)]
+        f: BfePoly,
+        #[strategy(non_zero_polynomial(4))]
+        #[filter(#g.degree() > 0)]
+        g: BfePoly,
+    ) {
+        prop_assert!(!(f * g).is_irreducible());
+    }
+
+    #[test]
+    fn factorization_of_polynomial_with_known_factors_is_found() {
+        let x_minus_3 = Polynomial::new(bfe_vec![-3, 1]);
+        let x_plus_1 = Polynomial::new(bfe_vec![1, 1]);
+        let x_squared_minus_7 = Polynomial::new(bfe_vec![-7, 0, 1]);
+        let shah = XFieldElement::shah_polynomial();
+        assert!(x_squared_minus_7.is_irreducible());
+
+        let poly = x_minus_3.pow(3)
+            * x_plus_1.clone()
+            * x_squared_minus_7.pow(2)
+            * shah.clone()
+            * bfe!(11);
+        let expected = vec![
+            (x_plus_1, 1),
+            (x_minus_3, 3),
+            (x_squared_minus_7, 2),
+            (shah, 1),
+        ];
+        assert_eq!(expected, poly.factor());
+    }
+
+    #[test]
+    fn polynomial_with_all_roots_in_base_field_splits_into_linear_factors() {
+        // the 8th roots of unity are in the base field
+        let x_to_the_8_minus_1 = Polynomial::x_to_the(8) - Polynomial::one();
+        let distinct_degree_factors = x_to_the_8_minus_1.distinct_degree_factorization();
+        assert_eq!(
+            vec![(x_to_the_8_minus_1.clone(), 1)],
+            distinct_degree_factors
+        );
+
+        let factors = x_to_the_8_minus_1.factor();
+        assert_eq!(8, factors.len());
+        for (factor, multiplicity) in factors {
+            assert_eq!(1, factor.degree());
+            assert_eq!(1, multiplicity);
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn factorization_multiplies_back_to_polynomial(
+        #[strategy(non_zero_polynomial(10))] poly: BfePoly,
+    ) {
+        let factors = poly.factor();
+        prop_assert!(factors.iter().map(|(factor, _)| factor).all_unique());
+
+        let mut product = Polynomial::from_constant(poly.leading_coefficient().unwrap());
+        for (factor, multiplicity) in factors {
+            prop_assert_eq!(Some(bfe!(1)), factor.leading_coefficient());
+            prop_assert!(factor.is_irreducible());
+            product = product * factor.pow(multiplicity as u32);
+        }
+        prop_assert_eq!(poly, product);
+    }
+
+    #[proptest(cases = 50)]
+    fn square_free_factors_are_square_free_and_pairwise_coprime(
+        #[strategy(non_zero_polynomial(4))] f: BfePoly,
+        #[strategy(non_zero_polynomial(4))] g: BfePoly,
+    ) {
+        let poly = f.clone() * f * g;
+        let factors = poly.square_free_factorization();
+
+        let mut product = Polynomial::from_constant(poly.leading_coefficient().unwrap());
+        for (factor, multiplicity) in &factors {
+            let (gcd, _, _) = Polynomial::xgcd(factor.clone(), factor.formal_derivative());
+            prop_assert!(gcd.is_one());
+            product = product * factor.pow(*multiplicity as u32);
+        }
+        prop_assert_eq!(poly, product);
+
+        for ((factor, _), (other_factor, _)) in factors.iter().tuple_combinations() {
+            let (gcd, _, _) = Polynomial::xgcd(factor.clone(), other_factor.clone());
+            prop_assert!(gcd.is_one());
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn distinct_degree_factors_consist_of_irreducibles_of_that_degree(
+        #[strategy(non_zero_polynomial(10))] poly: BfePoly,
+    ) {
+        let (square_free_part, _) = poly
+            .square_free_factorization()
+            .into_iter()
+            .max_by_key(|(factor, _)| factor.degree())
+            .unwrap_or((Polynomial::one(), 1));
+
+        for (product, degree) in square_free_part.distinct_degree_factorization() {
+            let irreducible_factors = product.equal_degree_factorization(degree);
+            prop_assert_eq!(
+                product.degree() as usize,
+                degree * irreducible_factors.len()
+            );
+            for factor in irreducible_factors {
+                prop_assert_eq!(degree as isize, factor.degree());
+                prop_assert!(factor.is_irreducible());
+            }
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn is_irreducible_agrees_with_factorization(#[strategy(non_zero_polynomial(6))] poly: BfePoly) {
+        let factors = poly.factor();
+        let is_single_simple_factor = factors.len() == 1 && factors[0].1 == 1;
+        prop_assert_eq!(is_single_simple_factor, poly.is_irreducible());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot factor the zero polynomial")]
+    fn factoring_zero_polynomial_panics() {
+        let _ = BfePoly::zero().factor();
+    }
+
     #[test]
     fn fast_evaluate_on_hardcoded_domain_and_polynomial() {
         let domain = bfe_array![6, 12];
@@ -3746,6 +4538,83 @@ mod test_polynomials {
         prop_assert_eq!(gcd, a * x + b * y);
     }
 
+    #[test]
+    fn fast_xgcd_agrees_with_xgcd_on_input_zero() {
+        let zero = Polynomial::<BFieldElement>::zero;
+        let one = Polynomial::<BFieldElement>::one;
+        let x_plus_1 = || Polynomial::from([1, 1]);
+        for (x, y) in [(zero(), zero()), (x_plus_1(), zero()), (zero(), x_plus_1())] {
+            let fast = Polynomial::fast_xgcd(x.clone(), y.clone());
+            assert_eq!(Polynomial::xgcd(x, y), fast);
+        }
+        assert_eq!(
+            (zero(), one(), zero()),
+            Polynomial::fast_xgcd(zero(), zero())
+        );
+    }
+
+    #[proptest]
+    fn fast_xgcd_agrees_with_xgcd(x: BfePoly, y: BfePoly) {
+        let fast = Polynomial::fast_xgcd(x.clone(), y.clone());
+        prop_assert_eq!(Polynomial::xgcd(x, y), fast);
+    }
+
+    #[proptest]
+    fn fast_xgcd_agrees_with_xgcd_over_extension_field(x: XfePoly, y: XfePoly) {
+        let fast = Polynomial::fast_xgcd(x.clone(), y.clone());
+        prop_assert_eq!(Polynomial::xgcd(x, y), fast);
+    }
+
+    #[proptest(cases = 10)]
+    fn fast_xgcd_agrees_with_xgcd_for_high_degrees(
+        #[strategy(vec(arb(), 500..2000))] x_coefficients: Vec<BFieldElement>,
+        #[strategy(vec(arb(), 500..2000))] y_coefficients: Vec<BFieldElement>,
+    ) {
+        let x = Polynomial::new(x_coefficients);
+        let y = Polynomial::new(y_coefficients);
+        let fast = Polynomial::fast_xgcd(x.clone(), y.clone());
+        prop_assert_eq!(Polynomial::xgcd(x, y), fast);
+    }
+
+    /// Random polynomials almost surely have a normal remainder sequence, _i.e._, every
+    /// step of the Euclidean algorithm decreases the degree by exactly one. Construct
+    /// the remainder sequence from its last element upwards to also cover quotients of
+    /// higher degree and non-trivial gcds.
+    #[proptest(cases = 20)]
+    fn fast_xgcd_agrees_with_xgcd_for_abnormal_remainder_sequences(
+        #[strategy(vec(arb(), 1..8))]
+        #[filter(!Polynomial::new(#gcd_coefficients.clone()).is_zero())]
+        gcd_coefficients: Vec<BFieldElement>,
+        #[strategy(vec(vec(arb(), 2..60), 1..50))]
+        #[filter(#quotients_coefficients.iter().all(|q| !q.last().unwrap().is_zero()))]
+        quotients_coefficients: Vec<Vec<BFieldElement>>,
+    ) {
+        let mut remainders = [Polynomial::zero(), Polynomial::new(gcd_coefficients)];
+        for quotient in quotients_coefficients.into_iter().map(Polynomial::new) {
+            let [rem, prev] = remainders;
+            remainders = [prev.clone(), quotient.multiply(&prev) + rem];
+        }
+
+        let [y, x] = remainders;
+        let fast = Polynomial::fast_xgcd(x.clone(), y.clone());
+        prop_assert_eq!(Polynomial::xgcd(x, y), fast);
+    }
+
+    #[proptest(cases = 10)]
+    fn half_gcd_agrees_with_naive_half_gcd(
+        #[strategy(vec(arb(), 1000..2000))] x_coefficients: Vec<BFieldElement>,
+        #[strategy(vec(arb(), 1..#x_coefficients.len()))] y_coefficients: Vec<BFieldElement>,
+    ) {
+        let x = Polynomial::new(x_coefficients);
+        let y = Polynomial::new(y_coefficients);
+        prop_assume!(x.degree() > y.degree());
+
+        let half_degree = (x.degree() + 1) / 2;
+        let naive = Polynomial::naive_half_gcd(&x, &y, half_degree);
+        let fast = Polynomial::half_gcd(&x, &y);
+        prop_assert_eq!(naive.0, fast.0);
+    }
+
     #[proptest]
     fn add_assign_is_equivalent_to_adding_and_assigning(a: BfePoly, b: BfePoly) {
         let mut c = a.clone();
@@ -3808,6 +4677,17 @@ mod test_polynomials {
         prop_assert_eq!(poly.square(), poly.fast_square());
     }
 
+    #[proptest]
+    fn squaring_ignores_leading_zero_coefficients(
+        poly: BfePoly,
+        #[strategy(0_usize..5)] num_leading_zeros: usize,
+    ) {
+        let mut coefficients = poly.clone().into_coefficients();
+        coefficients.resize(coefficients.len() + num_leading_zeros, BFieldElement::ZERO);
+        let poly_with_leading_zeros = Polynomial::new(coefficients);
+        prop_assert_eq!(poly.square(), poly_with_leading_zeros.square());
+    }
+
     #[test]
     fn constant_zero_eq_constant_zero() {
         let zero_polynomial1 = Polynomial::<BFieldElement>::zero();
@@ -0,0 +1,533 @@
+use std::fmt::Display;
+use std::iter::Sum;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Div;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+use arbitrary::Arbitrary;
+use bfieldcodec_derive::BFieldCodec;
+use num_traits::ConstOne;
+use num_traits::ConstZero;
+use num_traits::One;
+use num_traits::Zero;
+use rand::Rng;
+use rand_distr::Distribution;
+use rand_distr::Standard;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::traits::CyclicGroupGenerator;
+use crate::math::traits::FiniteField;
+use crate::math::traits::Inverse;
+use crate::math::traits::ModPowU32;
+use crate::math::traits::ModPowU64;
+use crate::math::traits::PrimitiveRootOfUnity;
+use crate::math::traits::SquareRoot;
+
+pub const QUINTIC_EXTENSION_DEGREE: usize = 5;
+
+/// The constant `c` in the modulus `z^5 - c` defining the [quintic
+/// extension](QuinticFieldElement) over the [base field](BFieldElement).
+pub const MODULUS_CONSTANT: BFieldElement = BFieldElement::new(3);
+
+/// The powers `ω^i` of the fifth root of unity `ω = 3^((p - 1) / 5)`. Because `z^p = ω·z`, the
+/// Frobenius endomorphism multiplies the `i`th coefficient by `ω^i`.
+const FROBENIUS_TWIST: [BFieldElement; QUINTIC_EXTENSION_DEGREE] = [
+    BFieldElement::new(1),
+    BFieldElement::new(1041288259238279555),
+    BFieldElement::new(15820824984080659046),
+    BFieldElement::new(211587555138949697),
+    BFieldElement::new(1373043270956696022),
+];
+
+/// The powers `ω^(2i)`, defining the square of the Frobenius endomorphism. See also
+/// [`FROBENIUS_TWIST`].
+const FROBENIUS_SQUARED_TWIST: [BFieldElement; QUINTIC_EXTENSION_DEGREE] = [
+    FROBENIUS_TWIST[0],
+    FROBENIUS_TWIST[2],
+    FROBENIUS_TWIST[4],
+    FROBENIUS_TWIST[1],
+    FROBENIUS_TWIST[3],
+];
+
+/// An element of the field GF(p^5) = GF(p)\[z\] / (z^5 - 3), the field underlying the
+/// [ecGFp5](crate::math::ecgfp5) curve.
+#[derive(
+    Debug, PartialEq, Eq, Copy, Clone, Hash, Serialize, Deserialize, BFieldCodec, Arbitrary,
+)]
+pub struct QuinticFieldElement {
+    pub coefficients: [BFieldElement; QUINTIC_EXTENSION_DEGREE],
+}
+
+impl Sum for QuinticFieldElement {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|a, b| a + b).unwrap_or(Self::ZERO)
+    }
+}
+
+impl<T> From<T> for QuinticFieldElement
+where
+    T: Into<BFieldElement>,
+{
+    fn from(value: T) -> Self {
+        Self::new_const(value.into())
+    }
+}
+
+impl<T> From<[T; QUINTIC_EXTENSION_DEGREE]> for QuinticFieldElement
+where
+    T: Into<BFieldElement>,
+{
+    fn from(value: [T; QUINTIC_EXTENSION_DEGREE]) -> Self {
+        Self::new(value.map(Into::into))
+    }
+}
+
+impl QuinticFieldElement {
+    #[inline]
+    pub const fn new(coefficients: [BFieldElement; QUINTIC_EXTENSION_DEGREE]) -> Self {
+        Self { coefficients }
+    }
+
+    #[inline]
+    pub const fn new_const(element: BFieldElement) -> Self {
+        let zero = BFieldElement::ZERO;
+        Self::new([element, zero, zero, zero, zero])
+    }
+
+    pub fn unlift(&self) -> Option<BFieldElement> {
+        let [c0, rest @ ..] = self.coefficients;
+        rest.iter().all(Zero::is_zero).then_some(c0)
+    }
+
+    /// The Frobenius endomorphism `x ↦ x^p`.
+    fn frobenius(self) -> Self {
+        Self::twist(self, FROBENIUS_TWIST)
+    }
+
+    /// The Frobenius endomorphism applied twice, _i.e._, `x ↦ x^(p^2)`.
+    fn frobenius_squared(self) -> Self {
+        Self::twist(self, FROBENIUS_SQUARED_TWIST)
+    }
+
+    fn twist(self, twist: [BFieldElement; QUINTIC_EXTENSION_DEGREE]) -> Self {
+        let mut coefficients = self.coefficients;
+        for (c, t) in coefficients.iter_mut().zip(twist) {
+            *c *= t;
+        }
+        Self { coefficients }
+    }
+
+    /// The product of all conjugates of `self` except `self` itself, _i.e._,
+    /// `x^(p + p^2 + p^3 + p^4)`.
+    fn conjugates_product(self) -> Self {
+        let first_two = self.frobenius() * self.frobenius_squared();
+        first_two * first_two.frobenius_squared()
+    }
+
+    /// The norm `x^(1 + p + p^2 + p^3 + p^4)`, which lies in the base field, given the
+    /// [product of the other conjugates](Self::conjugates_product).
+    fn norm_from_conjugates_product(self, conjugates_product: Self) -> BFieldElement {
+        let [a0, a1, a2, a3, a4] = self.coefficients;
+        let [b0, b1, b2, b3, b4] = conjugates_product.coefficients;
+        a0 * b0 + MODULUS_CONSTANT * (a1 * b4 + a2 * b3 + a3 * b2 + a4 * b1)
+    }
+
+    fn norm(self) -> BFieldElement {
+        self.norm_from_conjugates_product(self.conjugates_product())
+    }
+}
+
+impl Inverse for QuinticFieldElement {
+    fn inverse(&self) -> Self {
+        assert!(
+            !self.is_zero(),
+            "Cannot invert the zero element in the quintic extension field."
+        );
+        let conjugates_product = self.conjugates_product();
+        let norm = self.norm_from_conjugates_product(conjugates_product);
+        conjugates_product * norm.inverse()
+    }
+}
+
+// Like for the cubic extension, the extension degree is odd. With `s = 1 + p + p^2 + p^3 + p^4`,
+// the exponent `(s + 1) / 2 = 1 + p · ((p + 1) / 2) · (1 + p^2)` is evaluated using one
+// exponentiation with a 64-bit exponent and Frobenius endomorphisms.
+impl SquareRoot for QuinticFieldElement {
+    fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(Self::ZERO);
+        }
+
+        let norm_sqrt = self.norm().sqrt()?;
+        let half_power = self.mod_pow_u64(BFieldElement::P.div_ceil(2));
+        let power = (half_power * half_power.frobenius_squared()).frobenius() * *self;
+        Some(power * norm_sqrt.inverse())
+    }
+
+    fn legendre_symbol(&self) -> i8 {
+        if self.is_zero() {
+            return 0;
+        }
+        self.norm().legendre_symbol()
+    }
+}
+
+impl PrimitiveRootOfUnity for QuinticFieldElement {
+    fn primitive_root_of_unity(n: u64) -> Option<Self> {
+        let b_root = BFieldElement::primitive_root_of_unity(n);
+        b_root.map(Self::new_const)
+    }
+}
+
+impl Distribution<QuinticFieldElement> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> QuinticFieldElement {
+        QuinticFieldElement::new(rng.gen())
+    }
+}
+
+impl CyclicGroupGenerator for QuinticFieldElement {
+    fn get_cyclic_group_elements(&self, max: Option<usize>) -> Vec<Self> {
+        let mut val = *self;
+        let mut ret: Vec<Self> = vec![Self::one()];
+
+        loop {
+            ret.push(val);
+            val *= *self;
+            if val.is_one() || max.is_some() && ret.len() >= max.unwrap() {
+                break;
+            }
+        }
+        ret
+    }
+}
+
+impl Display for QuinticFieldElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(bfe) = self.unlift() {
+            return write!(f, "{bfe}_qfe");
+        }
+
+        let [c0, c1, c2, c3, c4] = self.coefficients;
+        write!(
+            f,
+            "({c4:>020}·z⁴ + {c3:>020}·z³ + {c2:>020}·z² + {c1:>020}·z + {c0:>020})"
+        )
+    }
+}
+
+impl Zero for QuinticFieldElement {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        self == &Self::ZERO
+    }
+}
+
+impl ConstZero for QuinticFieldElement {
+    const ZERO: Self = Self::new_const(BFieldElement::ZERO);
+}
+
+impl One for QuinticFieldElement {
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn is_one(&self) -> bool {
+        self == &Self::ONE
+    }
+}
+
+impl ConstOne for QuinticFieldElement {
+    const ONE: Self = Self::new_const(BFieldElement::ONE);
+}
+
+impl FiniteField for QuinticFieldElement {}
+
+impl Add<QuinticFieldElement> for QuinticFieldElement {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl Add<BFieldElement> for QuinticFieldElement {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, other: BFieldElement) -> Self {
+        self.coefficients[0] += other;
+        self
+    }
+}
+
+impl Mul<QuinticFieldElement> for QuinticFieldElement {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        // Schoolbook multiplication, followed by reduction using z^5 = 3.
+        let [a0, a1, a2, a3, a4] = self.coefficients;
+        let [b0, b1, b2, b3, b4] = other.coefficients;
+        let c = MODULUS_CONSTANT;
+
+        let r0 = a0 * b0 + c * (a1 * b4 + a2 * b3 + a3 * b2 + a4 * b1);
+        let r1 = a0 * b1 + a1 * b0 + c * (a2 * b4 + a3 * b3 + a4 * b2);
+        let r2 = a0 * b2 + a1 * b1 + a2 * b0 + c * (a3 * b4 + a4 * b3);
+        let r3 = a0 * b3 + a1 * b2 + a2 * b1 + a3 * b0 + c * (a4 * b4);
+        let r4 = a0 * b4 + a1 * b3 + a2 * b2 + a3 * b1 + a4 * b0;
+
+        Self::new([r0, r1, r2, r3, r4])
+    }
+}
+
+impl Mul<BFieldElement> for QuinticFieldElement {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: BFieldElement) -> Self {
+        let coefficients = self.coefficients.map(|c| c * other);
+        Self { coefficients }
+    }
+}
+
+impl Neg for QuinticFieldElement {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        let coefficients = self.coefficients.map(Neg::neg);
+        Self { coefficients }
+    }
+}
+
+impl Sub<QuinticFieldElement> for QuinticFieldElement {
+    type Output = Self;
+
+    #[inline]
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl Sub<BFieldElement> for QuinticFieldElement {
+    type Output = Self;
+
+    #[inline]
+    fn sub(mut self, other: BFieldElement) -> Self {
+        self.coefficients[0] -= other;
+        self
+    }
+}
+
+impl AddAssign<QuinticFieldElement> for QuinticFieldElement {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        for (c, r) in self.coefficients.iter_mut().zip(rhs.coefficients) {
+            *c += r;
+        }
+    }
+}
+
+impl MulAssign<QuinticFieldElement> for QuinticFieldElement {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl SubAssign<QuinticFieldElement> for QuinticFieldElement {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        for (c, r) in self.coefficients.iter_mut().zip(rhs.coefficients) {
+            *c -= r;
+        }
+    }
+}
+
+impl Div for QuinticFieldElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inverse()
+    }
+}
+
+impl ModPowU64 for QuinticFieldElement {
+    #[inline]
+    fn mod_pow_u64(&self, exponent: u64) -> Self {
+        let mut x = *self;
+        let mut result = Self::one();
+        let mut i = exponent;
+
+        while i > 0 {
+            if i & 1 == 1 {
+                result *= x;
+            }
+
+            x *= x;
+            i >>= 1;
+        }
+
+        result
+    }
+}
+
+impl ModPowU32 for QuinticFieldElement {
+    #[inline]
+    fn mod_pow_u32(&self, exp: u32) -> Self {
+        self.mod_pow_u64(exp.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::math::bfield_codec::BFieldCodec;
+
+    impl proptest::arbitrary::Arbitrary for QuinticFieldElement {
+        type Parameters = ();
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            arb().boxed()
+        }
+
+        type Strategy = BoxedStrategy<Self>;
+    }
+
+    #[test]
+    fn frobenius_twist_is_made_of_powers_of_a_fifth_root_of_unity() {
+        let omega = MODULUS_CONSTANT.mod_pow((BFieldElement::P - 1) / 5);
+        assert_eq!(BFieldElement::ONE, omega.mod_pow(5));
+        assert_ne!(BFieldElement::ONE, omega);
+        for (i, twist) in FROBENIUS_TWIST.into_iter().enumerate() {
+            assert_eq!(omega.mod_pow(i as u64), twist);
+        }
+    }
+
+    #[proptest]
+    fn frobenius_is_exponentiation_by_p(qfe: QuinticFieldElement) {
+        prop_assert_eq!(qfe.mod_pow_u64(BFieldElement::P), qfe.frobenius());
+        prop_assert_eq!(qfe.frobenius().frobenius(), qfe.frobenius_squared());
+    }
+
+    #[proptest]
+    fn multiplication_by_z_shifts_and_reduces(qfe: QuinticFieldElement) {
+        let z = QuinticFieldElement::from([0, 1, 0, 0, 0]);
+        let [c0, c1, c2, c3, c4] = qfe.coefficients;
+        let expected = QuinticFieldElement::new([MODULUS_CONSTANT * c4, c0, c1, c2, c3]);
+        prop_assert_eq!(expected, qfe * z);
+    }
+
+    #[proptest]
+    fn multiplication_distributes_over_addition(
+        a: QuinticFieldElement,
+        b: QuinticFieldElement,
+        c: QuinticFieldElement,
+    ) {
+        prop_assert_eq!(a * (b + c), a * b + a * c);
+        prop_assert_eq!((a * b) * c, a * (b * c));
+        prop_assert_eq!(a * b, b * a);
+    }
+
+    #[proptest]
+    fn subtraction_is_addition_of_negation(a: QuinticFieldElement, b: QuinticFieldElement) {
+        prop_assert_eq!(a - b, a + (-b));
+        prop_assert_eq!(QuinticFieldElement::ZERO, a - a);
+    }
+
+    #[proptest]
+    fn multiplication_with_base_field_element_agrees_with_lifted_multiplication(
+        qfe: QuinticFieldElement,
+        bfe: BFieldElement,
+    ) {
+        prop_assert_eq!(qfe * QuinticFieldElement::from(bfe), qfe * bfe);
+        prop_assert_eq!(qfe + QuinticFieldElement::from(bfe), qfe + bfe);
+        prop_assert_eq!(qfe - QuinticFieldElement::from(bfe), qfe - bfe);
+    }
+
+    #[proptest]
+    fn inverse_is_multiplicative_inverse(#[filter(!#qfe.is_zero())] qfe: QuinticFieldElement) {
+        prop_assert_eq!(QuinticFieldElement::ONE, qfe * qfe.inverse());
+        prop_assert_eq!(QuinticFieldElement::ONE, qfe / qfe);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot invert the zero element")]
+    fn inverting_zero_panics() {
+        let _ = QuinticFieldElement::ZERO.inverse();
+    }
+
+    #[proptest]
+    fn norm_is_product_of_all_conjugates(qfe: QuinticFieldElement) {
+        let mut product = QuinticFieldElement::ONE;
+        let mut conjugate = qfe;
+        for _ in 0..QUINTIC_EXTENSION_DEGREE {
+            product *= conjugate;
+            conjugate = conjugate.frobenius();
+        }
+        prop_assert_eq!(Some(qfe.norm()), product.unlift());
+    }
+
+    #[proptest]
+    fn mod_pow_agrees_with_repeated_multiplication(
+        qfe: QuinticFieldElement,
+        #[strategy(0_u32..100)] exponent: u32,
+    ) {
+        let expected = (0..exponent).fold(QuinticFieldElement::ONE, |acc, _| acc * qfe);
+        prop_assert_eq!(expected, qfe.mod_pow_u32(exponent));
+    }
+
+    #[proptest]
+    fn square_root_squares_to_original_element(qfe: QuinticFieldElement) {
+        let square = qfe * qfe;
+        let root = square.sqrt().unwrap();
+        prop_assert_eq!(square, root * root);
+        prop_assert!(root == qfe || root == -qfe);
+    }
+
+    #[proptest]
+    fn elements_without_square_root_are_not_squares(qfe: QuinticFieldElement) {
+        let legendre_symbol = qfe.legendre_symbol();
+        prop_assert_eq!(legendre_symbol >= 0, qfe.sqrt().is_some());
+        prop_assert_eq!(legendre_symbol >= 0, qfe.is_square());
+    }
+
+    #[proptest]
+    fn legendre_symbol_is_multiplicative(a: QuinticFieldElement, b: QuinticFieldElement) {
+        prop_assert_eq!(
+            a.legendre_symbol() * b.legendre_symbol(),
+            (a * b).legendre_symbol()
+        );
+    }
+
+    #[test]
+    fn norm_of_z_is_modulus_constant() {
+        let z = QuinticFieldElement::from([0, 1, 0, 0, 0]);
+        assert_eq!(MODULUS_CONSTANT, z.norm());
+    }
+
+    #[proptest]
+    fn bfield_codec_round_trip(qfe: QuinticFieldElement) {
+        let encoding = qfe.encode();
+        prop_assert_eq!(QUINTIC_EXTENSION_DEGREE, encoding.len());
+        prop_assert_eq!(qfe, *QuinticFieldElement::decode(&encoding).unwrap());
+    }
+}
@@ -0,0 +1,416 @@
+//! [Reed–Solomon codes][rs] over the [base field](BFieldElement) and the
+//! [extension field](crate::math::x_field_element::XFieldElement).
+//!
+//! [rs]: https://en.wikipedia.org/wiki/Reed%E2%80%93Solomon_error_correction
+
+use std::ops::Mul;
+use std::ops::MulAssign;
+
+use itertools::Itertools;
+use num_traits::One;
+use num_traits::Zero;
+use thiserror::Error;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::polynomial::Polynomial;
+use crate::math::traits::FiniteField;
+use crate::math::traits::ModPowU32;
+use crate::math::traits::PrimitiveRootOfUnity;
+
+type Result<T> = std::result::Result<T, ReedSolomonError>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Error)]
+#[non_exhaustive]
+pub enum ReedSolomonError {
+    #[error("expected a received word of length {expected}, but got {actual}")]
+    InvalidReceivedWordLength { expected: usize, actual: usize },
+
+    #[error("decoding requires {required} symbols, but only {available} are available")]
+    TooManyErasures { available: usize, required: usize },
+
+    #[error("received word is not within the unique-decoding radius of any codeword")]
+    TooManyErrors,
+}
+
+/// A Reed–Solomon code with messages of length `k` and codewords of length `n`.
+///
+/// A message `(m_0, …, m_{k-1})` is interpreted as the coefficients of the polynomial
+/// `m(x) = m_0 + m_1·x + … + m_{k-1}·x^{k-1}`. Its codeword consists of the evaluations of
+/// `m(x)` on the evaluation domain `offset·⟨ω⟩`, where ω is the primitive root of unity of
+/// order `n`. The `i`th codeword symbol is `m(offset·ω^i)`.
+///
+/// Any `k` symbols of a codeword determine the message, which allows
+/// [erasure decoding](Self::decode_erasures). Up to `⌊(n-k)/2⌋` erroneous symbols can be
+/// [corrected](Self::decode).
+///
+/// # Example
+///
+/// ```
+/// # use twenty_first::prelude::*;
+/// # use twenty_first::math::reed_solomon::ReedSolomon;
+/// let code = ReedSolomon::new(3, 8, BFieldElement::generator());
+/// let message = bfe_vec![1, 2, 3];
+/// let mut codeword = code.encode(&message);
+///
+/// codeword[1] = bfe!(42);
+/// codeword[6] = bfe!(42);
+/// assert_eq!(Ok(message.clone()), code.decode(&codeword));
+///
+/// let mut received = codeword.into_iter().map(Some).collect::<Vec<_>>();
+/// for i in [1, 2, 4, 6, 7] {
+///     received[i] = None;
+/// }
+/// assert_eq!(Ok(message), code.decode_erasures(&received));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReedSolomon {
+    message_len: usize,
+    code_len: usize,
+    offset: BFieldElement,
+}
+
+impl ReedSolomon {
+    /// # Panics
+    ///
+    /// Panics if
+    /// - the `code_len` is not a power of two,
+    /// - the `code_len` exceeds [`u32::MAX`],
+    /// - the `message_len` is zero or exceeds the `code_len`, or
+    /// - the `offset` is zero.
+    pub fn new(message_len: usize, code_len: usize, offset: BFieldElement) -> Self {
+        assert!(
+            code_len.is_power_of_two(),
+            "code length must be a power of two"
+        );
+        assert!(
+            u32::try_from(code_len).is_ok(),
+            "code length must not exceed u32::MAX"
+        );
+        assert!(message_len > 0, "message length must not be zero");
+        assert!(
+            message_len <= code_len,
+            "message length must not exceed code length"
+        );
+        assert!(!offset.is_zero(), "offset must not be zero");
+
+        Self {
+            message_len,
+            code_len,
+            offset,
+        }
+    }
+
+    pub fn message_len(&self) -> usize {
+        self.message_len
+    }
+
+    pub fn code_len(&self) -> usize {
+        self.code_len
+    }
+
+    pub fn offset(&self) -> BFieldElement {
+        self.offset
+    }
+
+    /// The maximum number of erroneous symbols that [decoding](Self::decode) can correct.
+    pub fn max_num_errors(&self) -> usize {
+        (self.code_len - self.message_len) / 2
+    }
+
+    /// The points at which the message polynomial is evaluated, in codeword order.
+    pub fn domain(&self) -> Vec<BFieldElement> {
+        let generator = BFieldElement::primitive_root_of_unity(self.code_len as u64).unwrap();
+        let mut point = self.offset;
+        let mut domain = Vec::with_capacity(self.code_len);
+        for _ in 0..self.code_len {
+            domain.push(point);
+            point *= generator;
+        }
+
+        domain
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the length of the message is not the [message length](Self::message_len).
+    pub fn encode<FF>(&self, message: &[FF]) -> Vec<FF>
+    where
+        FF: FiniteField + MulAssign<BFieldElement> + Mul<BFieldElement, Output = FF> + 'static,
+    {
+        assert_eq!(self.message_len, message.len(), "message length must match");
+        Polynomial::new_borrowed(message).fast_coset_evaluate(self.offset, self.code_len)
+    }
+
+    /// Recover the message from a codeword some of whose symbols were lost, indicated by
+    /// [`None`]. The message is [interpolated](Polynomial::interpolate) from the first
+    /// [message length](Self::message_len) many available symbols; all other symbols are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the length of the received word is not the [code length](Self::code_len), or
+    /// if fewer than [message length](Self::message_len) many symbols are available.
+    pub fn decode_erasures<FF>(&self, received: &[Option<FF>]) -> Result<Vec<FF>>
+    where
+        FF: FiniteField + MulAssign<BFieldElement> + From<BFieldElement> + 'static,
+    {
+        self.check_received_word_length(received.len())?;
+        let (domain, values): (Vec<_>, Vec<_>) = self
+            .domain()
+            .into_iter()
+            .zip_eq(received)
+            .filter_map(|(point, &symbol)| Some((FF::from(point), symbol?)))
+            .take(self.message_len)
+            .unzip();
+
+        if values.len() < self.message_len {
+            return Err(ReedSolomonError::TooManyErasures {
+                available: values.len(),
+                required: self.message_len,
+            });
+        }
+
+        let message_polynomial = Polynomial::interpolate(&domain, &values);
+        Ok(self.message_from_polynomial(message_polynomial))
+    }
+
+    /// Recover the message from a codeword with at most [`max_num_errors`](Self::max_num_errors)
+    /// erroneous symbols, using [Gao's algorithm][gao].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the length of the received word is not the [code length](Self::code_len), or
+    /// if there is no codeword within the unique-decoding radius of the received word.
+    ///
+    /// [gao]: https://www.math.clemson.edu/~sgao/papers/RS.pdf
+    pub fn decode<FF>(&self, received: &[FF]) -> Result<Vec<FF>>
+    where
+        FF: FiniteField
+            + MulAssign<BFieldElement>
+            + Mul<BFieldElement, Output = FF>
+            + From<BFieldElement>
+            + 'static,
+    {
+        self.check_received_word_length(received.len())?;
+
+        // The zerofier of the evaluation domain `offset·⟨ω⟩` is `x^n - offset^n`.
+        let mut zerofier_coefficients = vec![FF::ZERO; self.code_len + 1];
+        zerofier_coefficients[0] = -FF::from(self.offset.mod_pow_u32(self.code_len as u32));
+        zerofier_coefficients[self.code_len] = FF::ONE;
+        let zerofier = Polynomial::new(zerofier_coefficients);
+        let interpolant = Polynomial::fast_coset_interpolate(self.offset, received);
+
+        // Run the extended Euclidean algorithm on the zerofier and the interpolant until the
+        // remainder's degree drops below (n + k)/2, keeping track of the interpolant's Bézout
+        // coefficient only.
+        let mut remainders = [zerofier, interpolant];
+        let mut cofactors: [Polynomial<FF>; 2] = [Polynomial::zero(), Polynomial::one()];
+        while 2 * remainders[1].degree() >= (self.code_len + self.message_len) as isize {
+            let [dividend, divisor] = remainders;
+            let (quotient, remainder) = dividend.divide(&divisor);
+            remainders = [divisor, remainder];
+
+            let [cofactor, next_cofactor] = cofactors;
+            let new_cofactor = cofactor - quotient.multiply(&next_cofactor);
+            cofactors = [next_cofactor, new_cofactor];
+        }
+
+        let [_, error_locator] = cofactors;
+        let (message_polynomial, remainder) = remainders[1].divide(&error_locator);
+        if !remainder.is_zero() || message_polynomial.degree() >= self.message_len as isize {
+            return Err(ReedSolomonError::TooManyErrors);
+        }
+
+        let message = self.message_from_polynomial(message_polynomial);
+        let num_errors = self
+            .encode(&message)
+            .into_iter()
+            .zip_eq(received)
+            .filter(|&(expected, &actual)| expected != actual)
+            .count();
+        if num_errors > self.max_num_errors() {
+            return Err(ReedSolomonError::TooManyErrors);
+        }
+
+        Ok(message)
+    }
+
+    fn check_received_word_length(&self, len: usize) -> Result<()> {
+        if len != self.code_len {
+            return Err(ReedSolomonError::InvalidReceivedWordLength {
+                expected: self.code_len,
+                actual: len,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn message_from_polynomial<FF: FiniteField>(&self, polynomial: Polynomial<FF>) -> Vec<FF> {
+        let mut message = polynomial.into_coefficients();
+        debug_assert!(message.len() <= self.message_len);
+        message.resize(self.message_len, FF::ZERO);
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest::sample::subsequence;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+    use test_strategy::Arbitrary;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, Arbitrary)]
+    struct ReedSolomonParameters {
+        #[strategy(0_u32..7)]
+        log2_code_len: u32,
+
+        #[strategy(1..=1_usize << #log2_code_len)]
+        message_len: usize,
+
+        #[strategy(arb())]
+        #[filter(!#offset.is_zero())]
+        offset: BFieldElement,
+    }
+
+    impl ReedSolomonParameters {
+        fn code(&self) -> ReedSolomon {
+            ReedSolomon::new(self.message_len, 1 << self.log2_code_len, self.offset)
+        }
+    }
+
+    #[proptest]
+    fn codeword_symbols_are_evaluations_on_domain(
+        params: ReedSolomonParameters,
+        #[strategy(vec(arb(), #params.message_len))] message: Vec<XFieldElement>,
+    ) {
+        let code = params.code();
+        let codeword = code.encode(&message);
+        let polynomial = Polynomial::new(message);
+        for (point, symbol) in code.domain().into_iter().zip_eq(codeword) {
+            prop_assert_eq!(polynomial.evaluate::<_, XFieldElement>(point), symbol);
+        }
+    }
+
+    #[proptest]
+    fn decoding_codeword_without_errors_gives_message(
+        params: ReedSolomonParameters,
+        #[strategy(vec(arb(), #params.message_len))] message: Vec<BFieldElement>,
+    ) {
+        let code = params.code();
+        let codeword = code.encode(&message);
+        prop_assert_eq!(Ok(message), code.decode(&codeword));
+    }
+
+    #[proptest]
+    fn erasures_can_be_decoded(
+        params: ReedSolomonParameters,
+        #[strategy(vec(arb(), #params.message_len))] message: Vec<XFieldElement>,
+        #[strategy(subsequence((0..1_usize << #params.log2_code_len).collect::<Vec<_>>(),
+            0..=(1 << #params.log2_code_len) - #params.message_len))]
+        erased_indices: Vec<usize>,
+    ) {
+        let code = params.code();
+        let mut received = code.encode(&message).into_iter().map(Some).collect_vec();
+        for i in erased_indices {
+            received[i] = None;
+        }
+        prop_assert_eq!(Ok(message), code.decode_erasures(&received));
+    }
+
+    #[proptest]
+    fn too_many_erasures_cannot_be_decoded(
+        params: ReedSolomonParameters,
+        #[strategy(vec(arb(), #params.message_len))] message: Vec<BFieldElement>,
+        #[strategy(subsequence((0..1_usize << #params.log2_code_len).collect::<Vec<_>>(),
+            (1 << #params.log2_code_len) - #params.message_len + 1..=1 << #params.log2_code_len))]
+        erased_indices: Vec<usize>,
+    ) {
+        let code = params.code();
+        let mut received = code.encode(&message).into_iter().map(Some).collect_vec();
+        let num_available = code.code_len() - erased_indices.len();
+        for i in erased_indices {
+            received[i] = None;
+        }
+
+        let err = code.decode_erasures(&received).unwrap_err();
+        let expected_err = ReedSolomonError::TooManyErasures {
+            available: num_available,
+            required: code.message_len(),
+        };
+        prop_assert_eq!(expected_err, err);
+    }
+
+    #[proptest]
+    fn errors_up_to_unique_decoding_radius_can_be_corrected(
+        params: ReedSolomonParameters,
+        #[strategy(vec(arb(), #params.message_len))] message: Vec<XFieldElement>,
+        #[strategy(subsequence((0..1_usize << #params.log2_code_len).collect::<Vec<_>>(),
+            0..=((1 << #params.log2_code_len) - #params.message_len) / 2))]
+        error_indices: Vec<usize>,
+        #[strategy(vec(arb(), #error_indices.len()))]
+        #[filter(#error_offsets.iter().all(|e: &XFieldElement| !e.is_zero()))]
+        error_offsets: Vec<XFieldElement>,
+    ) {
+        let code = params.code();
+        let mut received = code.encode(&message);
+        for (i, error) in error_indices.into_iter().zip_eq(error_offsets) {
+            received[i] += error;
+        }
+        prop_assert_eq!(Ok(message), code.decode(&received));
+    }
+
+    #[proptest]
+    fn decoding_never_returns_message_far_from_received_word(
+        params: ReedSolomonParameters,
+        #[strategy(vec(arb(), 1 << #params.log2_code_len))] received: Vec<BFieldElement>,
+    ) {
+        let code = params.code();
+        let Ok(message) = code.decode(&received) else {
+            return Ok(());
+        };
+
+        let num_errors = code
+            .encode(&message)
+            .into_iter()
+            .zip_eq(received)
+            .filter(|(expected, actual)| expected != actual)
+            .count();
+        prop_assert!(num_errors <= code.max_num_errors());
+    }
+
+    #[test]
+    fn too_many_errors_cannot_be_corrected() {
+        let code = ReedSolomon::new(4, 16, BFieldElement::generator());
+        let mut received = code.encode(&bfe_array![1, 2, 3, 4]);
+        for symbol in received.iter_mut().take(code.max_num_errors() + 1) {
+            *symbol += bfe!(1);
+        }
+
+        assert_eq!(Err(ReedSolomonError::TooManyErrors), code.decode(&received));
+    }
+
+    #[test]
+    fn decoding_received_word_of_wrong_length_fails() {
+        let code = ReedSolomon::new(2, 4, bfe!(1));
+        let expected_err = ReedSolomonError::InvalidReceivedWordLength {
+            expected: 4,
+            actual: 3,
+        };
+        assert_eq!(Err(expected_err), code.decode(&bfe_array![1, 2, 3]));
+        assert_eq!(Err(expected_err), code.decode_erasures(&[Some(bfe!(1)); 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "message length must match")]
+    fn encoding_message_of_wrong_length_panics() {
+        let code = ReedSolomon::new(2, 4, bfe!(1));
+        let _ = code.encode(&bfe_array![1, 2, 3]);
+    }
+}
@@ -0,0 +1,756 @@
+//! [Shamir secret sharing][shamir] over the [base field](BFieldElement) and the
+//! [extension field](crate::math::x_field_element::XFieldElement), and
+//! [Feldman's verifiable secret sharing][feldman] over the [scalars](Scalar) of the group
+//! [ecGFp5](crate::math::ecgfp5).
+//!
+//! Feldman's scheme checks a share `(i, f(i))` against commitments `C_j = a_j·G` to the
+//! coefficients `a_j` of the sharing polynomial `f` by testing `f(i)·G == Σ_j i^j·C_j`.
+//! This requires a commitment that is additively homomorphic in the secret, which rules
+//! out hash-based commitments like [`Tip5`](crate::prelude::Tip5): the hash of `f(i)`
+//! cannot be computed from the hashes of the `a_j`. It further requires a group of prime
+//! order `n` in which discrete logarithms are hard, because `a·G` only depends on `a`
+//! modulo `n`, so that the polynomial must be defined over the field with `n` elements.
+//! No such group of order `p`, the characteristic of [`BFieldElement`], is available, and
+//! the additive groups of the base and extension fields have easy discrete logarithms.
+//! Consequently, verifiable secrets are [`Scalar`]s of ecGFp5, a group of prime order.
+//!
+//! [shamir]: https://en.wikipedia.org/wiki/Shamir%27s_secret_sharing
+//! [feldman]: https://en.wikipedia.org/wiki/Verifiable_secret_sharing#Feldman's_scheme
+
+use std::collections::HashSet;
+use std::ops::MulAssign;
+
+use itertools::Itertools;
+use rand::distributions::Distribution;
+use rand::distributions::Standard;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::digest::Digest;
+use crate::math::ecgfp5::Point;
+use crate::math::ecgfp5::Scalar;
+use crate::math::polynomial::Polynomial;
+use crate::math::traits::FiniteField;
+
+type Result<T> = std::result::Result<T, SecretSharingError>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum SecretSharingError {
+    #[error("reconstruction requires {required} shares, but only {available} are available")]
+    NotEnoughShares { available: usize, required: usize },
+
+    #[error("share index {0} is out of range")]
+    InvalidShareIndex(u32),
+
+    #[error("share index {0} appears more than once")]
+    DuplicateShareIndex(u32),
+
+    #[error("shares hold secrets of different lengths")]
+    SecretLengthMismatch,
+
+    #[error("shares do not lie on a polynomial of degree less than the threshold")]
+    InconsistentShares,
+
+    #[error("share {0} does not match the commitment")]
+    InvalidShare(u32),
+
+    #[error("commitment does not fix polynomials of degree less than the threshold")]
+    InvalidCommitment,
+
+    #[error("reconstructed secret cannot be decoded")]
+    UndecodableSecret,
+}
+
+/// One share of a secret. A secret consists of one or more field elements, each of
+/// which is shared independently; the share holds one value per secret element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Share<FF: FiniteField> {
+    /// The share's position, starting at 1. The share's values are the evaluations of
+    /// the sharing polynomials in this point.
+    pub index: u32,
+    pub values: Vec<FF>,
+}
+
+/// [Feldman's][feldman] commitment to the sharing polynomials of a
+/// [verifiable sharing](SecretSharing::split_verifiable).
+///
+/// For every element of the secret, the commitment holds the multiples `a_j·G` of the
+/// [generator](Point::GENERATOR) for all coefficients `a_j` of the element's sharing
+/// polynomial, starting with the constant term. In particular, it reveals `s·G` for every
+/// element `s` of the secret.
+///
+/// [feldman]: https://en.wikipedia.org/wiki/Verifiable_secret_sharing#Feldman's_scheme
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FeldmanCommitment {
+    pub coefficients: Vec<Vec<Point>>,
+}
+
+/// One share of a secret consisting of [`Scalar`]s, which can be checked against the dealer's
+/// [`FeldmanCommitment`]. See [`SecretSharing::split_verifiable`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VerifiableShare {
+    /// The share's position, starting at 1.
+    pub index: u32,
+    pub values: Vec<Scalar>,
+}
+
+impl VerifiableShare {
+    /// Verify that the share lies on the committed polynomials.
+    ///
+    /// For every sharing polynomial `f` with coefficient commitments `C_j`, this checks
+    /// `f(i)·G = Σ_j i^j·C_j`, where `i` is the share's index.
+    pub fn verify(&self, commitment: &FeldmanCommitment) -> bool {
+        if self.values.len() != commitment.coefficients.len() {
+            return false;
+        }
+
+        let index = Scalar::from(u64::from(self.index));
+        self.values
+            .iter()
+            .zip(&commitment.coefficients)
+            .all(|(&value, coefficient_commitments)| {
+                let committed_value = coefficient_commitments
+                    .iter()
+                    .rev()
+                    .fold(Point::NEUTRAL, |acc, &c| acc * index + c);
+                Point::GENERATOR * value == committed_value
+            })
+    }
+}
+
+/// A `t`-out-of-`n` threshold secret sharing scheme: a secret is split into `n` shares,
+/// any `t` of which suffice to reconstruct the secret. Fewer than `t` shares reveal
+/// nothing about the secret.
+///
+/// Every element `s` of the secret is the constant term of a random polynomial `f` of
+/// degree less than `t`. The `i`th share is `f(i)`.
+///
+/// All randomness is derived from the supplied `randomness`, which must be uniformly
+/// random and must never be reused.
+///
+/// # Example
+///
+/// ```
+/// # use twenty_first::prelude::*;
+/// # use twenty_first::math::secret_sharing::SecretSharing;
+/// let sharing = SecretSharing::new(3, 5);
+/// let secret = xfe_vec![1, 2];
+/// let shares = sharing.split(&secret, rand::random());
+/// assert_eq!(5, shares.len());
+///
+/// let reconstructed = sharing.reconstruct(&shares[1..4]).unwrap();
+/// assert_eq!(secret, reconstructed);
+/// assert!(sharing.reconstruct(&shares[..2]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SecretSharing {
+    threshold: usize,
+    num_shares: usize,
+}
+
+impl SecretSharing {
+    /// # Panics
+    ///
+    /// Panics if the `threshold` is zero or exceeds the `num_shares`, or if the
+    /// `num_shares` exceeds [`u32::MAX`].
+    pub fn new(threshold: usize, num_shares: usize) -> Self {
+        assert!(threshold > 0, "threshold must not be zero");
+        assert!(
+            threshold <= num_shares,
+            "threshold must not exceed number of shares"
+        );
+        assert!(
+            u32::try_from(num_shares).is_ok(),
+            "number of shares must not exceed u32::MAX"
+        );
+
+        Self {
+            threshold,
+            num_shares,
+        }
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn num_shares(&self) -> usize {
+        self.num_shares
+    }
+
+    /// Split the secret into [`num_shares`](Self::num_shares) many shares.
+    pub fn split<FF>(&self, secret: &[FF], randomness: [u8; 32]) -> Vec<Share<FF>>
+    where
+        FF: FiniteField + MulAssign<BFieldElement> + From<BFieldElement> + 'static,
+        Standard: Distribution<FF>,
+    {
+        self.split_with_rng(secret, &mut StdRng::from_seed(randomness))
+    }
+
+    fn split_with_rng<FF>(&self, secret: &[FF], rng: &mut StdRng) -> Vec<Share<FF>>
+    where
+        FF: FiniteField + MulAssign<BFieldElement> + From<BFieldElement> + 'static,
+        Standard: Distribution<FF>,
+    {
+        let domain = (1..=self.num_shares as u64)
+            .map(|i| FF::from(BFieldElement::new(i)))
+            .collect_vec();
+
+        let share_values_per_secret_element = secret
+            .iter()
+            .map(|&secret_element| {
+                let mut coefficients = vec![secret_element];
+                coefficients.extend((1..self.threshold).map(|_| rng.gen::<FF>()));
+                Polynomial::new(coefficients).batch_evaluate(&domain)
+            })
+            .collect_vec();
+
+        (0..self.num_shares)
+            .map(|i| Share {
+                index: i as u32 + 1,
+                values: share_values_per_secret_element
+                    .iter()
+                    .map(|values| values[i])
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Split the [encoding](BFieldCodec::encode) of the value into
+    /// [`num_shares`](Self::num_shares) many shares.
+    pub fn split_value<T: BFieldCodec>(
+        &self,
+        value: &T,
+        randomness: [u8; 32],
+    ) -> Vec<Share<BFieldElement>> {
+        self.split(&value.encode(), randomness)
+    }
+
+    /// Split the secret into [`num_shares`](Self::num_shares) many shares, and commit to the
+    /// sharing polynomials using [Feldman's scheme](FeldmanCommitment).
+    ///
+    /// Every shareholder can [verify](VerifiableShare::verify) its share against the
+    /// commitment. Since the commitment fixes polynomials of degree less than the
+    /// [`threshold`](Self::threshold), a dealer cannot hand out inconsistent shares without
+    /// being detected, and [reconstruction](Self::reconstruct_verifiable) rejects tampered
+    /// shares.
+    ///
+    /// The commitments live in the group [ecGFp5](crate::math::ecgfp5), whose order differs
+    /// from the field's characteristic. Therefore, the secret consists of [`Scalar`]s. Since
+    /// the commitment reveals `s·G` for every element `s` of the secret, the secret stays
+    /// hidden only if it is hard to guess.
+    pub fn split_verifiable(
+        &self,
+        secret: &[Scalar],
+        randomness: [u8; 32],
+    ) -> (FeldmanCommitment, Vec<VerifiableShare>) {
+        let mut rng = StdRng::from_seed(randomness);
+        let polynomials = secret
+            .iter()
+            .map(|&secret_element| {
+                let mut coefficients = vec![secret_element];
+                coefficients
+                    .extend((1..self.threshold).map(|_| Scalar::from_digest(rng.gen::<Digest>())));
+                coefficients
+            })
+            .collect_vec();
+
+        let coefficient_commitments = polynomials
+            .iter()
+            .map(|coefficients| {
+                coefficients
+                    .iter()
+                    .map(|&coefficient| Point::GENERATOR * coefficient)
+                    .collect()
+            })
+            .collect();
+        let commitment = FeldmanCommitment {
+            coefficients: coefficient_commitments,
+        };
+
+        let shares = (1..=self.num_shares as u32)
+            .map(|index| {
+                let x = Scalar::from(u64::from(index));
+                let values = polynomials
+                    .iter()
+                    .map(|coefficients| {
+                        coefficients
+                            .iter()
+                            .rev()
+                            .fold(Scalar::ZERO, |acc, &coefficient| acc * x + coefficient)
+                    })
+                    .collect();
+                VerifiableShare { index, values }
+            })
+            .collect();
+
+        (commitment, shares)
+    }
+
+    /// Reconstruct the secret from at least [`threshold`](Self::threshold) many shares.
+    ///
+    /// # Errors
+    ///
+    /// Errors if
+    /// - there are fewer than [`threshold`](Self::threshold) many shares,
+    /// - any share index is out of range or appears more than once,
+    /// - the shares hold secrets of different lengths, or
+    /// - the shares do not lie on a polynomial of degree less than the threshold, which
+    ///   can only be detected if there are more than [`threshold`](Self::threshold) many
+    ///   shares.
+    pub fn reconstruct<FF>(&self, shares: &[Share<FF>]) -> Result<Vec<FF>>
+    where
+        FF: FiniteField + MulAssign<BFieldElement> + From<BFieldElement> + 'static,
+    {
+        self.check_shares(shares)?;
+
+        let domain = shares
+            .iter()
+            .map(|share| FF::from(BFieldElement::from(share.index)))
+            .collect_vec();
+        let secret_len = shares[0].values.len();
+
+        (0..secret_len)
+            .map(|i| {
+                let values = shares.iter().map(|share| share.values[i]).collect_vec();
+                let polynomial = Polynomial::interpolate(&domain, &values);
+                if polynomial.degree() >= self.threshold as isize {
+                    return Err(SecretSharingError::InconsistentShares);
+                }
+                Ok(polynomial
+                    .coefficients()
+                    .first()
+                    .copied()
+                    .unwrap_or(FF::ZERO))
+            })
+            .collect()
+    }
+
+    /// Like [`reconstruct`](Self::reconstruct), but additionally
+    /// [decode](BFieldCodec::decode) the secret.
+    ///
+    /// # Errors
+    ///
+    /// Errors if [reconstruction](Self::reconstruct) or decoding fail.
+    pub fn reconstruct_value<T: BFieldCodec>(
+        &self,
+        shares: &[Share<BFieldElement>],
+    ) -> Result<Box<T>> {
+        let encoding = self.reconstruct(shares)?;
+        T::decode(&encoding).map_err(|_| SecretSharingError::UndecodableSecret)
+    }
+
+    /// Reconstruct the secret from at least [`threshold`](Self::threshold) many shares of a
+    /// [verifiable sharing](Self::split_verifiable).
+    ///
+    /// # Errors
+    ///
+    /// Errors if
+    /// - the commitment does not commit to [`threshold`](Self::threshold) many coefficients
+    ///   per secret element,
+    /// - there are fewer than [`threshold`](Self::threshold) many shares,
+    /// - any share index is out of range or appears more than once, or
+    /// - any share does not [verify](VerifiableShare::verify) against the commitment.
+    pub fn reconstruct_verifiable(
+        &self,
+        shares: &[VerifiableShare],
+        commitment: &FeldmanCommitment,
+    ) -> Result<Vec<Scalar>> {
+        let degree_is_valid = |c: &Vec<_>| c.len() == self.threshold;
+        if !commitment.coefficients.iter().all(degree_is_valid) {
+            return Err(SecretSharingError::InvalidCommitment);
+        }
+        if shares.len() < self.threshold {
+            return Err(SecretSharingError::NotEnoughShares {
+                available: shares.len(),
+                required: self.threshold,
+            });
+        }
+        self.check_share_indices(shares.iter().map(|share| share.index))?;
+        if let Some(share) = shares.iter().find(|share| !share.verify(commitment)) {
+            return Err(SecretSharingError::InvalidShare(share.index));
+        }
+
+        // all shares lie on the committed polynomials, so any `threshold` many of them suffice
+        let shares = &shares[..self.threshold];
+        let domain = shares
+            .iter()
+            .map(|share| Scalar::from(u64::from(share.index)))
+            .collect_vec();
+        let lagrange_coefficients_at_zero = domain
+            .iter()
+            .enumerate()
+            .map(|(i, &x_i)| {
+                domain
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .fold(Scalar::ONE, |acc, (_, &x_j)| {
+                        acc * x_j * (x_j - x_i).inverse()
+                    })
+            })
+            .collect_vec();
+
+        let secret = (0..commitment.coefficients.len())
+            .map(|k| {
+                shares.iter().zip(&lagrange_coefficients_at_zero).fold(
+                    Scalar::ZERO,
+                    |acc, (share, &lagrange_coefficient)| {
+                        acc + lagrange_coefficient * share.values[k]
+                    },
+                )
+            })
+            .collect();
+
+        Ok(secret)
+    }
+
+    /// Re-randomize the shares without changing the secret, making shares obtained
+    /// before the refresh useless in combination with shares obtained afterwards.
+    ///
+    /// Refreshing adds a fresh [sharing](Self::split) of the all-zero secret to the
+    /// shares. In a setting without a trusted dealer, every shareholder can split the
+    /// all-zero secret, distribute the resulting shares, and add all received shares'
+    /// values to its own share's values.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any share index is out of range or appears more than once, or if the
+    /// shares hold secrets of different lengths.
+    pub fn refresh<FF>(&self, shares: &mut [Share<FF>], randomness: [u8; 32]) -> Result<()>
+    where
+        FF: FiniteField + MulAssign<BFieldElement> + From<BFieldElement> + 'static,
+        Standard: Distribution<FF>,
+    {
+        self.check_share_indices_and_lengths(shares)?;
+        let Some(secret_len) = shares.first().map(|share| share.values.len()) else {
+            return Ok(());
+        };
+
+        let zero_shares = self.split(&vec![FF::ZERO; secret_len], randomness);
+        for share in shares {
+            let zero_share = &zero_shares[share.index as usize - 1];
+            for (value, &zero_value) in share.values.iter_mut().zip_eq(&zero_share.values) {
+                *value += zero_value;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_shares<FF: FiniteField>(&self, shares: &[Share<FF>]) -> Result<()> {
+        if shares.len() < self.threshold {
+            return Err(SecretSharingError::NotEnoughShares {
+                available: shares.len(),
+                required: self.threshold,
+            });
+        }
+
+        self.check_share_indices_and_lengths(shares)
+    }
+
+    fn check_share_indices_and_lengths<FF: FiniteField>(&self, shares: &[Share<FF>]) -> Result<()> {
+        self.check_share_indices(shares.iter().map(|share| share.index))?;
+        if !shares.iter().map(|share| share.values.len()).all_equal() {
+            return Err(SecretSharingError::SecretLengthMismatch);
+        }
+
+        Ok(())
+    }
+
+    fn check_share_indices(&self, indices: impl IntoIterator<Item = u32>) -> Result<()> {
+        let mut seen_indices = HashSet::new();
+        for index in indices {
+            if index == 0 || index as usize > self.num_shares {
+                return Err(SecretSharingError::InvalidShareIndex(index));
+            }
+            if !seen_indices.insert(index) {
+                return Err(SecretSharingError::DuplicateShareIndex(index));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::Zero;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest::sample::subsequence;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+    use test_strategy::Arbitrary;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, Arbitrary)]
+    struct SecretSharingParameters {
+        #[strategy(1_usize..20)]
+        num_shares: usize,
+
+        #[strategy(1..=#num_shares)]
+        threshold: usize,
+    }
+
+    impl SecretSharingParameters {
+        fn sharing(&self) -> SecretSharing {
+            SecretSharing::new(self.threshold, self.num_shares)
+        }
+
+        fn share_indices(&self) -> Vec<usize> {
+            (0..self.num_shares).collect()
+        }
+    }
+
+    #[proptest]
+    fn any_threshold_many_shares_reconstruct_secret(
+        params: SecretSharingParameters,
+        #[strategy(vec(arb(), 0..5))] secret: Vec<XFieldElement>,
+        randomness: [u8; 32],
+        #[strategy(subsequence(#params.share_indices(), #params.threshold..=#params.num_shares))]
+        share_indices: Vec<usize>,
+    ) {
+        let sharing = params.sharing();
+        let shares = sharing.split(&secret, randomness);
+        prop_assert_eq!(params.num_shares, shares.len());
+
+        let chosen_shares = share_indices
+            .into_iter()
+            .map(|i| shares[i].clone())
+            .collect_vec();
+        prop_assert_eq!(secret, sharing.reconstruct(&chosen_shares)?);
+    }
+
+    #[proptest]
+    fn fewer_than_threshold_many_shares_cannot_reconstruct_secret(
+        params: SecretSharingParameters,
+        #[strategy(vec(arb(), 0..5))] secret: Vec<BFieldElement>,
+        randomness: [u8; 32],
+        #[strategy(subsequence(#params.share_indices(), 0..#params.threshold))] share_indices: Vec<
+            usize,
+        >,
+    ) {
+        let sharing = params.sharing();
+        let shares = sharing.split(&secret, randomness);
+        let chosen_shares = share_indices
+            .into_iter()
+            .map(|i| shares[i].clone())
+            .collect_vec();
+        let err = sharing.reconstruct(&chosen_shares).unwrap_err();
+        let is_expected_err = matches!(err, SecretSharingError::NotEnoughShares { .. });
+        prop_assert!(is_expected_err);
+    }
+
+    #[proptest]
+    fn inconsistent_shares_are_detected(
+        #[strategy(vec(arb(), 1..5))] secret: Vec<BFieldElement>,
+        randomness: [u8; 32],
+        #[strategy(0_usize..5)] tampered_share: usize,
+        #[strategy(0..#secret.len())] tampered_element: usize,
+        #[filter(!#offset.is_zero())] offset: BFieldElement,
+    ) {
+        let sharing = SecretSharing::new(3, 5);
+        let mut shares = sharing.split(&secret, randomness);
+        shares[tampered_share].values[tampered_element] += offset;
+        let err = sharing.reconstruct(&shares).unwrap_err();
+        prop_assert!(matches!(err, SecretSharingError::InconsistentShares));
+    }
+
+    #[proptest]
+    fn encodable_value_can_be_shared(#[strategy(arb())] value: Vec<Digest>, randomness: [u8; 32]) {
+        let sharing = SecretSharing::new(2, 3);
+        let shares = sharing.split_value(&value, randomness);
+        let reconstructed = sharing.reconstruct_value::<Vec<Digest>>(&shares[1..])?;
+        prop_assert_eq!(value, *reconstructed);
+    }
+
+    #[test]
+    fn reconstructing_undecodable_value_fails() {
+        let sharing = SecretSharing::new(2, 3);
+        let shares = sharing.split_value(&bfe_vec![1, 2, 3], rand::random());
+        let err = sharing.reconstruct_value::<Digest>(&shares).unwrap_err();
+        assert_eq!(SecretSharingError::UndecodableSecret, err);
+    }
+
+    #[proptest(cases = 10)]
+    fn verifiable_shares_verify_and_reconstruct_secret(
+        #[strategy(vec(arb(), 0..3))] secret: Vec<Scalar>,
+        randomness: [u8; 32],
+        #[strategy(subsequence((0..5).collect_vec(), 3..=5))] share_indices: Vec<usize>,
+    ) {
+        let sharing = SecretSharing::new(3, 5);
+        let (commitment, shares) = sharing.split_verifiable(&secret, randomness);
+        prop_assert_eq!(5, shares.len());
+        prop_assert!(shares.iter().all(|share| share.verify(&commitment)));
+
+        let chosen_shares = share_indices
+            .into_iter()
+            .map(|i| shares[i].clone())
+            .collect_vec();
+        let reconstructed = sharing.reconstruct_verifiable(&chosen_shares, &commitment)?;
+        prop_assert_eq!(secret, reconstructed);
+    }
+
+    #[proptest(cases = 10)]
+    fn commitment_to_constant_term_is_multiple_of_secret(
+        #[strategy(arb())] secret: Scalar,
+        randomness: [u8; 32],
+    ) {
+        let sharing = SecretSharing::new(2, 3);
+        let (commitment, _) = sharing.split_verifiable(&[secret], randomness);
+        prop_assert_eq!(Point::GENERATOR * secret, commitment.coefficients[0][0]);
+    }
+
+    #[proptest(cases = 10)]
+    fn tampered_verifiable_share_is_rejected(
+        #[strategy(vec(arb(), 1..3))] secret: Vec<Scalar>,
+        randomness: [u8; 32],
+        #[strategy(0_usize..5)] tampered_share: usize,
+        #[strategy(0..#secret.len())] tampered_element: usize,
+        #[strategy(arb())]
+        #[filter(!#offset.is_zero())]
+        offset: Scalar,
+    ) {
+        let sharing = SecretSharing::new(3, 5);
+        let (commitment, mut shares) = sharing.split_verifiable(&secret, randomness);
+        let tampered_value = &mut shares[tampered_share].values[tampered_element];
+        *tampered_value = *tampered_value + offset;
+        prop_assert!(!shares[tampered_share].verify(&commitment));
+
+        let err = sharing
+            .reconstruct_verifiable(&shares, &commitment)
+            .unwrap_err();
+        let tampered_index = shares[tampered_share].index;
+        prop_assert_eq!(SecretSharingError::InvalidShare(tampered_index), err);
+    }
+
+    #[proptest(cases = 10)]
+    fn verifiable_share_with_wrong_index_does_not_verify(
+        randomness: [u8; 32],
+        #[strategy(0_usize..5)] share: usize,
+        #[strategy(0_u32..8)]
+        #[filter(#share as u32 + 1 != #new_index)]
+        new_index: u32,
+    ) {
+        let sharing = SecretSharing::new(3, 5);
+        let (commitment, mut shares) = sharing.split_verifiable(&[Scalar::from(42)], randomness);
+        shares[share].index = new_index;
+        prop_assert!(!shares[share].verify(&commitment));
+    }
+
+    #[test]
+    fn shares_not_on_committed_polynomial_do_not_verify() {
+        // a dealer commits to a polynomial of lower degree than that of the shares
+        let sharing = SecretSharing::new(2, 3);
+        let (mut commitment, shares) =
+            sharing.split_verifiable(&[Scalar::from(42)], rand::random());
+        commitment.coefficients[0].pop();
+        assert!(shares.iter().all(|share| !share.verify(&commitment)));
+
+        let err = sharing
+            .reconstruct_verifiable(&shares, &commitment)
+            .unwrap_err();
+        assert_eq!(SecretSharingError::InvalidCommitment, err);
+    }
+
+    #[test]
+    fn reconstructing_from_too_few_verifiable_shares_fails() {
+        let sharing = SecretSharing::new(2, 3);
+        let (commitment, shares) = sharing.split_verifiable(&[Scalar::from(42)], rand::random());
+        let err = sharing
+            .reconstruct_verifiable(&shares[..1], &commitment)
+            .unwrap_err();
+        let expected = SecretSharingError::NotEnoughShares {
+            available: 1,
+            required: 2,
+        };
+        assert_eq!(expected, err);
+    }
+
+    #[proptest]
+    fn refreshing_shares_preserves_secret_but_changes_shares(
+        params: SecretSharingParameters,
+        #[strategy(vec(arb(), 1..5))] secret: Vec<XFieldElement>,
+        randomness: [u8; 32],
+        refresh_randomness: [u8; 32],
+        #[strategy(subsequence(#params.share_indices(), #params.threshold..=#params.num_shares))]
+        share_indices: Vec<usize>,
+    ) {
+        let sharing = params.sharing();
+        let shares = sharing.split(&secret, randomness);
+        let mut refreshed_shares = share_indices
+            .into_iter()
+            .map(|i| shares[i].clone())
+            .collect_vec();
+        sharing.refresh(&mut refreshed_shares, refresh_randomness)?;
+        prop_assert_eq!(&secret, &sharing.reconstruct(&refreshed_shares)?);
+
+        if params.threshold > 1 {
+            for refreshed_share in refreshed_shares {
+                prop_assert_ne!(
+                    &shares[refreshed_share.index as usize - 1],
+                    &refreshed_share
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn refreshing_shares_of_different_generations_destroys_secret() {
+        let sharing = SecretSharing::new(2, 3);
+        let secret = bfe_vec![42];
+        let old_shares = sharing.split(&secret, rand::random());
+        let mut new_shares = old_shares.clone();
+        sharing.refresh(&mut new_shares, rand::random()).unwrap();
+
+        let mixed_shares = [old_shares[0].clone(), new_shares[1].clone()];
+        assert_ne!(secret, sharing.reconstruct(&mixed_shares).unwrap());
+    }
+
+    #[test]
+    fn invalid_share_indices_are_rejected() {
+        let sharing = SecretSharing::new(2, 3);
+        let mut shares = sharing.split(&bfe_array![42], rand::random());
+
+        shares[0].index = 0;
+        let zero_index_err = sharing.reconstruct(&shares).unwrap_err();
+        assert!(matches!(
+            zero_index_err,
+            SecretSharingError::InvalidShareIndex(0)
+        ));
+
+        shares[0].index = 4;
+        let large_index_err = sharing.reconstruct(&shares).unwrap_err();
+        assert!(matches!(
+            large_index_err,
+            SecretSharingError::InvalidShareIndex(4)
+        ));
+
+        shares[0].index = 2;
+        let duplicate_index_err = sharing.reconstruct(&shares).unwrap_err();
+        assert!(matches!(
+            duplicate_index_err,
+            SecretSharingError::DuplicateShareIndex(2)
+        ));
+    }
+
+    #[test]
+    fn shares_of_secrets_of_different_lengths_are_rejected() {
+        let sharing = SecretSharing::new(2, 3);
+        let mut shares = sharing.split(&bfe_array![4, 2], rand::random());
+        shares[1].values.pop();
+        let err = sharing.reconstruct(&shares).unwrap_err();
+        assert!(matches!(err, SecretSharingError::SecretLengthMismatch));
+    }
+}
@@ -56,6 +56,23 @@ pub trait ModPowU32 {
     fn mod_pow_u32(&self, exp: u32) -> Self;
 }
 
+/// Square roots and quadratic residuosity.
+pub trait SquareRoot
+where
+    Self: Sized,
+{
+    /// A square root of `self`, or `None` if `self` is not a square.
+    fn sqrt(&self) -> Option<Self>;
+
+    /// 1 if `self` is a non-zero square, -1 if `self` is not a square, and 0 if `self` is
+    /// zero.
+    fn legendre_symbol(&self) -> i8;
+
+    fn is_square(&self) -> bool {
+        self.legendre_symbol() >= 0
+    }
+}
+
 pub trait FiniteField:
     Copy
     + Debug
@@ -32,6 +32,7 @@ use crate::math::traits::Inverse;
 use crate::math::traits::ModPowU32;
 use crate::math::traits::ModPowU64;
 use crate::math::traits::PrimitiveRootOfUnity;
+use crate::math::traits::SquareRoot;
 
 pub const EXTENSION_DEGREE: usize = 3;
 
@@ -294,6 +295,19 @@ impl XFieldElement {
     pub fn decrement(&mut self, index: usize) {
         self.coefficients[index].decrement();
     }
+
+    /// The Frobenius endomorphism `x ↦ x^p`.
+    fn frobenius(self) -> Self {
+        self.mod_pow_u64(BFieldElement::P)
+    }
+
+    /// The norm `x^(1 + p + p^2)`, which is the product of all conjugates and lies in the
+    /// base field.
+    fn norm(self) -> BFieldElement {
+        let conjugate = self.frobenius();
+        let norm = self * conjugate * conjugate.frobenius();
+        norm.unlift().unwrap()
+    }
 }
 
 impl Inverse for XFieldElement {
@@ -309,6 +323,29 @@ impl Inverse for XFieldElement {
     }
 }
 
+// Because the extension degree is odd, an element is a square if and only if its norm is a
+// square in the base field. With `s = 1 + p + p^2`, which is odd, and `n^2 = x^s`, the element
+// `x^((s + 1) / 2) / n` squares to `x`. Since `(s + 1) / 2 = p · (p + 1) / 2 + 1`, the
+// large exponentiation reduces to two exponentiations with 64-bit exponents.
+impl SquareRoot for XFieldElement {
+    fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(Self::ZERO);
+        }
+
+        let norm_sqrt = self.norm().sqrt()?;
+        let power = self.mod_pow_u64(BFieldElement::P.div_ceil(2)).frobenius() * *self;
+        Some(power * norm_sqrt.inverse())
+    }
+
+    fn legendre_symbol(&self) -> i8 {
+        if self.is_zero() {
+            return 0;
+        }
+        self.norm().legendre_symbol()
+    }
+}
+
 impl PrimitiveRootOfUnity for XFieldElement {
     fn primitive_root_of_unity(n: u64) -> Option<XFieldElement> {
         let b_root = BFieldElement::primitive_root_of_unity(n);
@@ -1219,4 +1256,70 @@ mod tests {
         let xfe = XFieldElement::new_const(scalar);
         prop_assert_eq!(xfe, xfe!(scalar));
     }
+
+    #[proptest]
+    fn square_root_squares_to_original_element(xfe: XFieldElement) {
+        match xfe.sqrt() {
+            Some(root) => prop_assert_eq!(xfe, root.square()),
+            None => prop_assert_eq!(-1, xfe.legendre_symbol()),
+        }
+    }
+
+    #[proptest]
+    fn square_root_of_square_is_one_of_the_two_roots(xfe: XFieldElement) {
+        let root = xfe.square().sqrt().unwrap();
+        prop_assert!(root == xfe || root == -xfe);
+    }
+
+    #[proptest]
+    fn elements_without_square_root_are_not_squares(xfe: XFieldElement) {
+        prop_assert_eq!(xfe.sqrt().is_some(), xfe.is_square());
+    }
+
+    #[proptest]
+    fn legendre_symbol_is_multiplicative(a: XFieldElement, b: XFieldElement) {
+        prop_assert_eq!(
+            a.legendre_symbol() * b.legendre_symbol(),
+            (a * b).legendre_symbol()
+        );
+    }
+
+    #[proptest]
+    fn legendre_symbol_agrees_with_euler_criterion(xfe: XFieldElement) {
+        // (p^3 - 1) / 2 = (p - 1) / 2 · (1 + p + p^2)
+        let power = xfe
+            .mod_pow_u64((BFieldElement::P - 1) / 2)
+            .mod_pow_u64(BFieldElement::P)
+            .mod_pow_u64(BFieldElement::P)
+            * xfe
+                .mod_pow_u64((BFieldElement::P - 1) / 2)
+                .mod_pow_u64(BFieldElement::P)
+            * xfe.mod_pow_u64((BFieldElement::P - 1) / 2);
+        let expected_power = match xfe.legendre_symbol() {
+            0 => XFieldElement::ZERO,
+            1 => XFieldElement::ONE,
+            _ => -XFieldElement::ONE,
+        };
+        prop_assert_eq!(expected_power, power);
+    }
+
+    #[proptest]
+    fn base_field_elements_are_squares_in_extension_if_they_are_squares_in_base_field(
+        bfe: BFieldElement,
+    ) {
+        let xfe = XFieldElement::new_const(bfe);
+        prop_assert_eq!(bfe.legendre_symbol(), xfe.legendre_symbol());
+    }
+
+    #[proptest]
+    fn norm_of_base_field_element_is_its_cube(bfe: BFieldElement) {
+        let xfe = XFieldElement::new_const(bfe);
+        prop_assert_eq!(bfe * bfe * bfe, xfe.norm());
+    }
+
+    #[test]
+    fn square_root_of_zero_is_zero() {
+        assert_eq!(Some(XFieldElement::ZERO), XFieldElement::ZERO.sqrt());
+        assert_eq!(0, XFieldElement::ZERO.legendre_symbol());
+    }
 }
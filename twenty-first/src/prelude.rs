@@ -5,14 +5,17 @@ pub use crate::bfe_vec;
 pub use crate::math::b_field_element;
 pub use crate::math::b_field_element::BFieldElement;
 pub use crate::math::bfield_codec::BFieldCodec;
+pub use crate::math::mpolynomial::MPolynomial;
 pub use crate::math::polynomial::Polynomial;
 pub use crate::math::tip5;
 pub use crate::math::tip5::Digest;
 pub use crate::math::tip5::Tip5;
 pub use crate::math::traits::Inverse;
 pub use crate::math::traits::ModPowU32;
+pub use crate::math::traits::SquareRoot;
 pub use crate::math::x_field_element;
 pub use crate::math::x_field_element::XFieldElement;
+pub use crate::util_types::algebraic_hasher::AlgebraicHasher;
 pub use crate::util_types::merkle_tree::CpuParallel;
 pub use crate::util_types::merkle_tree::MerkleTree;
 pub use crate::util_types::merkle_tree::MerkleTreeInclusionProof;
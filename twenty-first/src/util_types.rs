@@ -1,4 +1,7 @@
+pub mod algebraic_hasher;
 pub mod merkle_tree;
 pub mod mmr;
+pub mod proof_stream;
 pub mod shared;
+pub mod sparse_merkle_tree;
 pub mod sponge;
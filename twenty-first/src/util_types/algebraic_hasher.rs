@@ -0,0 +1,99 @@
+use std::fmt::Debug;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::digest::Digest;
+use crate::math::tip5::Tip5;
+
+/// A hash function producing [`Digest`]s, as used by [Merkle trees][merkle_tree] and
+/// [Merkle Mountain Ranges][mmr].
+///
+/// The canonical implementor is [`Tip5`], which is also the default hash function of all data
+/// structures that are generic over an `AlgebraicHasher`.
+///
+/// [merkle_tree]: crate::util_types::merkle_tree::MerkleTree
+/// [mmr]: crate::util_types::mmr::mmr_accumulator::MmrAccumulator
+pub trait AlgebraicHasher: Debug + Clone + Default + PartialEq + Eq + Send + Sync {
+    /// Hash two digests into one. Used to compute the parent of two nodes in a Merkle tree.
+    fn hash_pair(left: Digest, right: Digest) -> Digest;
+
+    /// Hash a variable-length sequence of [`BFieldElement`]s.
+    fn hash_varlen(input: &[BFieldElement]) -> Digest;
+
+    /// Hash anything [encodable](BFieldCodec).
+    fn hash<T: BFieldCodec>(value: &T) -> Digest {
+        Self::hash_varlen(&value.encode())
+    }
+}
+
+impl AlgebraicHasher for Tip5 {
+    fn hash_pair(left: Digest, right: Digest) -> Digest {
+        Tip5::hash_pair(left, right)
+    }
+
+    fn hash_varlen(input: &[BFieldElement]) -> Digest {
+        Tip5::hash_varlen(input)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+
+    /// A cheap, insecure hash function. Only suitable for testing the data structures generic
+    /// over an [`AlgebraicHasher`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub(crate) struct TestHasher;
+
+    impl AlgebraicHasher for TestHasher {
+        fn hash_pair(left: Digest, right: Digest) -> Digest {
+            let mut values = left.values();
+            for (i, (value, r)) in values.iter_mut().zip(right.values()).enumerate() {
+                *value += r * r + BFieldElement::new(i as u64 + 1);
+            }
+            Digest::new(values)
+        }
+
+        fn hash_varlen(input: &[BFieldElement]) -> Digest {
+            let mut digest = Digest::default();
+            for &element in input {
+                digest = Self::hash_pair(digest, Digest::new([element; Digest::LEN]));
+            }
+            digest
+        }
+    }
+
+    #[proptest]
+    fn tip5_implements_algebraic_hasher_consistently(
+        #[strategy(arb())] left: Digest,
+        #[strategy(arb())] right: Digest,
+        #[strategy(arb())] input: Vec<BFieldElement>,
+    ) {
+        prop_assert_eq!(
+            Tip5::hash_pair(left, right),
+            <Tip5 as AlgebraicHasher>::hash_pair(left, right)
+        );
+        prop_assert_eq!(
+            Tip5::hash_varlen(&input),
+            <Tip5 as AlgebraicHasher>::hash_varlen(&input)
+        );
+        prop_assert_eq!(Tip5::hash(&input), <Tip5 as AlgebraicHasher>::hash(&input));
+    }
+
+    #[proptest]
+    fn test_hasher_is_not_commutative(
+        #[strategy(arb())] left: Digest,
+        #[strategy(arb())]
+        #[filter(#left != #right)]
+        right: Digest,
+    ) {
+        prop_assert_ne!(
+            TestHasher::hash_pair(left, right),
+            TestHasher::hash_pair(right, left)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::hash_map::Entry::*;
 use std::collections::*;
 use std::fmt::Debug;
+use std::marker::PhantomData;
 use std::result;
 
 use arbitrary::*;
@@ -11,6 +12,7 @@ use thiserror::Error;
 
 use crate::math::digest::Digest;
 use crate::prelude::Tip5;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
 
 const DEFAULT_PARALLELIZATION_CUTOFF: usize = 256;
 lazy_static! {
@@ -45,12 +47,13 @@ type Result<T> = result::Result<T, MerkleTreeError>;
 /// used to efficiently prove the inclusion of items in a set. Set inclusion can
 /// be verified through an [inclusion proof](MerkleTreeInclusionProof).
 ///
-/// The used hash function is [`Tip5`].
+/// The used hash function is generic and defaults to [`Tip5`].
 ///
 /// [merkle_tree]: https://en.wikipedia.org/wiki/Merkle_tree
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct MerkleTree {
+pub struct MerkleTree<H: AlgebraicHasher = Tip5> {
     nodes: Vec<Digest>,
+    _hasher: PhantomData<H>,
 }
 
 /// A trait for creating a [`MerkleTree`] from a list of [`Digest`]s.
@@ -93,6 +96,14 @@ pub struct MerkleTree {
 /// - The number of digests is not a power of two.
 pub trait MerkleTreeMaker {
     fn from_digests(digests: &[Digest]) -> Result<MerkleTree>;
+
+    /// Like [`from_digests`](Self::from_digests), but for any [`AlgebraicHasher`], not just
+    /// [`Tip5`].
+    ///
+    /// The default implementation builds the tree like [`CpuParallel`] does.
+    fn from_digests_with_hasher<H: AlgebraicHasher>(digests: &[Digest]) -> Result<MerkleTree<H>> {
+        CpuParallel::build_tree(digests)
+    }
 }
 
 /// A full inclusion proof for the leafs at the supplied indices, including the
@@ -147,7 +158,21 @@ pub(crate) struct PartialMerkleTree {
 
 impl MerkleTree {
     pub fn new<Maker: MerkleTreeMaker>(leafs: &[Digest]) -> Result<Self> {
-        Maker::from_digests(leafs)
+        Self::new_with_hasher::<Maker>(leafs)
+    }
+}
+
+impl<H: AlgebraicHasher> MerkleTree<H> {
+    /// Like [`new`](MerkleTree::new), but for any [`AlgebraicHasher`], not just [`Tip5`].
+    ///
+    /// ```
+    /// # use twenty_first::prelude::*;
+    /// let leafs = [bfe!(42), bfe!(43)].map(|leaf| Tip5::hash(&leaf));
+    /// let tree = MerkleTree::<Tip5>::new_with_hasher::<CpuParallel>(&leafs).unwrap();
+    /// assert_eq!(MerkleTree::new::<CpuParallel>(&leafs).unwrap(), tree);
+    /// ```
+    pub fn new_with_hasher<Maker: MerkleTreeMaker>(leafs: &[Digest]) -> Result<Self> {
+        Maker::from_digests_with_hasher(leafs)
     }
 
     /// Given a list of leaf indices, return the indices of exactly those nodes that
@@ -294,14 +319,14 @@ impl MerkleTree {
     }
 }
 
-impl<'a> Arbitrary<'a> for MerkleTree {
+impl<'a, H: AlgebraicHasher> Arbitrary<'a> for MerkleTree<H> {
     fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
         let height = u.int_in_range(0..=13)?;
         let num_leafs = 1 << height;
         let leaf_digests: arbitrary::Result<Vec<_>> =
             (0..num_leafs).map(|_| u.arbitrary()).collect();
 
-        let tree = Self::new::<CpuParallel>(&leaf_digests?).unwrap();
+        let tree = Self::new_with_hasher::<CpuParallel>(&leaf_digests?).unwrap();
         Ok(tree)
     }
 }
@@ -318,10 +343,16 @@ impl MerkleTreeInclusionProof {
     /// Verify that the given root digest is the root of a Merkle tree that contains
     /// the indicated leafs.
     pub fn verify(self, expected_root: Digest) -> bool {
+        self.verify_with_hasher::<Tip5>(expected_root)
+    }
+
+    /// Like [`verify`](Self::verify), but for a Merkle tree built with any
+    /// [`AlgebraicHasher`], not just [`Tip5`].
+    pub fn verify_with_hasher<H: AlgebraicHasher>(self, expected_root: Digest) -> bool {
         if self.is_trivial() {
             return true;
         }
-        let Ok(partial_tree) = PartialMerkleTree::try_from(self) else {
+        let Ok(partial_tree) = PartialMerkleTree::from_inclusion_proof::<H>(self) else {
             return false;
         };
         let Ok(computed_root) = partial_tree.root() else {
@@ -357,7 +388,15 @@ impl MerkleTreeInclusionProof {
     ///
     /// [auth_structure]: MerkleTree::authentication_structure
     pub fn into_authentication_paths(self) -> Result<Vec<Vec<Digest>>> {
-        let partial_tree = PartialMerkleTree::try_from(self)?;
+        self.into_authentication_paths_with_hasher::<Tip5>()
+    }
+
+    /// Like [`into_authentication_paths`](Self::into_authentication_paths), but for a Merkle
+    /// tree built with any [`AlgebraicHasher`], not just [`Tip5`].
+    pub fn into_authentication_paths_with_hasher<H: AlgebraicHasher>(
+        self,
+    ) -> Result<Vec<Vec<Digest>>> {
+        let partial_tree = PartialMerkleTree::from_inclusion_proof::<H>(self)?;
         partial_tree.into_authentication_paths()
     }
 }
@@ -390,12 +429,18 @@ impl PartialMerkleTree {
     ///   the root, or
     /// - not minimal, _i.e._, if it contains nodes that can be computed from other
     ///   nodes.
+    #[cfg(test)]
     pub fn fill(&mut self) -> Result<()> {
+        self.fill_with_hasher::<Tip5>()
+    }
+
+    /// Like [`fill`](Self::fill), but for any [`AlgebraicHasher`], not just [`Tip5`].
+    pub fn fill_with_hasher<H: AlgebraicHasher>(&mut self) -> Result<()> {
         let mut parent_node_indices = self.first_layer_parent_node_indices()?;
 
         for _ in 0..self.tree_height {
             for &parent_node_index in &parent_node_indices {
-                self.insert_digest_for_index(parent_node_index)?;
+                self.insert_digest_for_index::<H>(parent_node_index)?;
             }
             parent_node_indices = Self::move_indices_one_layer_up(parent_node_indices);
         }
@@ -416,9 +461,9 @@ impl PartialMerkleTree {
         Ok(parent_node_indices)
     }
 
-    fn insert_digest_for_index(&mut self, parent_index: usize) -> Result<()> {
+    fn insert_digest_for_index<H: AlgebraicHasher>(&mut self, parent_index: usize) -> Result<()> {
         let (left_child, right_child) = self.children_of_node(parent_index)?;
-        let parent_digest = Tip5::hash_pair(left_child, right_child);
+        let parent_digest = H::hash_pair(left_child, right_child);
 
         match self.nodes.insert(parent_index, parent_digest) {
             Some(_) => Err(MerkleTreeError::SpuriousNodeIndex(parent_index)),
@@ -473,6 +518,12 @@ impl TryFrom<MerkleTreeInclusionProof> for PartialMerkleTree {
     type Error = MerkleTreeError;
 
     fn try_from(proof: MerkleTreeInclusionProof) -> Result<Self> {
+        Self::from_inclusion_proof::<Tip5>(proof)
+    }
+}
+
+impl PartialMerkleTree {
+    fn from_inclusion_proof<H: AlgebraicHasher>(proof: MerkleTreeInclusionProof) -> Result<Self> {
         let leaf_indices = proof.leaf_indices().copied().collect();
         let mut partial_tree = PartialMerkleTree {
             tree_height: proof.tree_height,
@@ -485,7 +536,7 @@ impl TryFrom<MerkleTreeInclusionProof> for PartialMerkleTree {
             return Err(MerkleTreeError::LeafIndexInvalid { num_leafs });
         }
 
-        let node_indices = MerkleTree::authentication_structure_node_indices(
+        let node_indices = MerkleTree::<H>::authentication_structure_node_indices(
             num_leafs,
             &partial_tree.leaf_indices,
         )?;
@@ -507,7 +558,7 @@ impl TryFrom<MerkleTreeInclusionProof> for PartialMerkleTree {
         }
 
         partial_tree.nodes = nodes;
-        partial_tree.fill()?;
+        partial_tree.fill_with_hasher::<H>()?;
         Ok(partial_tree)
     }
 }
@@ -524,6 +575,12 @@ impl MerkleTreeMaker for CpuParallel {
     /// - If the number of digests is 0.
     /// - If the number of digests is not a power of two.
     fn from_digests(digests: &[Digest]) -> Result<MerkleTree> {
+        Self::build_tree(digests)
+    }
+}
+
+impl CpuParallel {
+    fn build_tree<H: AlgebraicHasher>(digests: &[Digest]) -> Result<MerkleTree<H>> {
         if digests.is_empty() {
             return Err(MerkleTreeError::TooFewLeafs);
         }
@@ -549,7 +606,7 @@ impl MerkleTreeMaker for CpuParallel {
                     let j = node_count_on_this_level + i;
                     let left_child = nodes[j * 2];
                     let right_child = nodes[j * 2 + 1];
-                    Tip5::hash_pair(left_child, right_child)
+                    H::hash_pair(left_child, right_child)
                 })
                 .collect_into_vec(&mut local_digests);
             nodes[node_count_on_this_level..(node_count_on_this_level + node_count_on_this_level)]
@@ -560,10 +617,14 @@ impl MerkleTreeMaker for CpuParallel {
 
         // Sequential digest calculations
         for i in (ROOT_INDEX..(digests.len() - count_acc)).rev() {
-            nodes[i] = Tip5::hash_pair(nodes[i * 2], nodes[i * 2 + 1]);
+            nodes[i] = H::hash_pair(nodes[i * 2], nodes[i * 2 + 1]);
         }
 
-        Ok(MerkleTree { nodes })
+        let tree = MerkleTree {
+            nodes,
+            _hasher: PhantomData,
+        };
+        Ok(tree)
     }
 }
 
@@ -609,6 +670,7 @@ pub mod merkle_tree_test {
     use crate::math::b_field_element::BFieldElement;
     use crate::math::digest::digest_tests::DigestCorruptor;
     use crate::math::tip5::Tip5;
+    use crate::util_types::algebraic_hasher::tests::TestHasher;
 
     impl MerkleTree {
         fn test_tree_of_height(tree_height: usize) -> Self {
@@ -1036,4 +1098,25 @@ pub mod merkle_tree_test {
 
         assert_eq!(expected_paths, auth_paths);
     }
+
+    #[proptest(cases = 30)]
+    fn merkle_tree_with_custom_hasher_uses_that_hasher(
+        #[strategy(0_usize..5)] _tree_height: usize,
+        #[strategy(vec(arb(), 1 << #_tree_height))] leafs: Vec<Digest>,
+        #[strategy(vec(0..#leafs.len(), 0..4))] leaf_indices: Vec<usize>,
+    ) {
+        let tree = MerkleTree::<TestHasher>::new_with_hasher::<CpuParallel>(&leafs)?;
+        for node_index in 1..tree.nodes.len() / 2 {
+            let left_child = tree.nodes[2 * node_index];
+            let right_child = tree.nodes[2 * node_index + 1];
+            let expected = TestHasher::hash_pair(left_child, right_child);
+            prop_assert_eq!(expected, tree.nodes[node_index]);
+        }
+
+        let proof = tree.inclusion_proof_for_leaf_indices(&leaf_indices)?;
+        prop_assert!(proof.clone().verify_with_hasher::<TestHasher>(tree.root()));
+        prop_assert!(proof
+            .into_authentication_paths_with_hasher::<TestHasher>()
+            .is_ok());
+    }
 }
@@ -1,5 +1,7 @@
+pub mod archival_mmr;
 pub mod mmr_accumulator;
 pub mod mmr_membership_proof;
+pub mod mmr_storage;
 pub mod mmr_successor_proof;
 pub mod mmr_trait;
 pub mod shared_advanced;
@@ -0,0 +1,565 @@
+use std::io;
+use std::marker::PhantomData;
+
+use itertools::Itertools;
+use thiserror::Error;
+
+use super::mmr_accumulator::MmrAccumulator;
+use super::mmr_membership_proof::MmrMembershipProof;
+use super::mmr_storage::MmrStorage;
+use super::mmr_trait::LeafMutation;
+use super::mmr_trait::Mmr;
+use super::shared_advanced;
+use crate::math::digest::Digest;
+use crate::prelude::Tip5;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
+use crate::util_types::shared::bag_peaks_with_hasher;
+
+/// A Merkle Mountain Range that stores all nodes of all its trees, as opposed to the
+/// [`MmrAccumulator`], which only stores the trees' roots. Consequently, the archival MMR can
+/// [prove membership](Self::prove_membership) of any leaf and can [remove](Self::remove_last_leaf)
+/// leafs.
+///
+/// The nodes are persisted in a [storage backend](MmrStorage), for example, an in-memory
+/// [`Vec`] or a [file](super::mmr_storage::FileStorage). The hash function is generic and
+/// defaults to [`Tip5`].
+///
+/// The methods of this type return the storage's I/O errors. The [`Mmr`] trait is
+/// infallible, so its methods panic if the storage fails; use the `try_` methods, like
+/// [`try_append`](Self::try_append), to handle such errors.
+///
+/// ```
+/// # use twenty_first::prelude::*;
+/// # use twenty_first::util_types::mmr::archival_mmr::ArchivalMmr;
+/// let mut mmr = ArchivalMmr::new(vec![]).unwrap();
+/// let leaf = Tip5::hash(&bfe!(42));
+/// mmr.append(leaf);
+/// mmr.append(Tip5::hash(&bfe!(43)));
+///
+/// let membership_proof = mmr.prove_membership(0).unwrap();
+/// assert!(membership_proof.verify(0, leaf, &mmr.peaks(), mmr.num_leafs()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArchivalMmr<S: MmrStorage, H: AlgebraicHasher = Tip5> {
+    /// The node with (1-indexed) node index `i` is stored at storage index `i - 1`.
+    storage: S,
+    _hasher: PhantomData<H>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ArchivalMmrError {
+    /// The storage does not contain the nodes of an MMR. This happens, for example, if an
+    /// [append](Mmr::append) to a [file](super::mmr_storage::FileStorage) was interrupted.
+    #[error("storage must contain the nodes of an MMR, but no MMR has {0} nodes")]
+    InvalidNumNodes(u64),
+}
+
+impl<S: MmrStorage> ArchivalMmr<S> {
+    /// An archival MMR using the given storage. The storage must either be empty or contain the
+    /// nodes of a previously built archival MMR.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the number of nodes in the storage is not the number of nodes of any MMR.
+    pub fn new(storage: S) -> Result<Self, ArchivalMmrError> {
+        Self::new_with_hasher(storage)
+    }
+}
+
+impl<S: MmrStorage, H: AlgebraicHasher> ArchivalMmr<S, H> {
+    /// Like [`ArchivalMmr::new`], but for any [`AlgebraicHasher`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the number of nodes in the storage is not the number of nodes of any MMR.
+    pub fn new_with_hasher(storage: S) -> Result<Self, ArchivalMmrError> {
+        let num_nodes = storage.len();
+        if num_nodes_to_num_leafs(num_nodes).is_none() {
+            return Err(ArchivalMmrError::InvalidNumNodes(num_nodes));
+        }
+
+        Ok(Self {
+            storage,
+            _hasher: PhantomData,
+        })
+    }
+
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the leaf index is out of bounds.
+    pub fn get_leaf(&self, leaf_index: u64) -> io::Result<Digest> {
+        self.assert_leaf_index_is_in_bounds(leaf_index);
+        self.node(shared_advanced::leaf_index_to_node_index(leaf_index))
+    }
+
+    /// Return the MMR membership proof for the leaf with the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the leaf index is out of bounds.
+    pub fn prove_membership(&self, leaf_index: u64) -> io::Result<MmrMembershipProof> {
+        self.assert_leaf_index_is_in_bounds(leaf_index);
+
+        let mut node_index = shared_advanced::leaf_index_to_node_index(leaf_index);
+        let mut authentication_path = vec![];
+        loop {
+            let (right_ancestor_count, height) =
+                shared_advanced::right_lineage_length_and_own_height(node_index);
+            let (sibling_index, parent_index) = match right_ancestor_count {
+                0 => (
+                    shared_advanced::right_sibling(node_index, height),
+                    node_index + (1 << (height + 1)),
+                ),
+                _ => (
+                    shared_advanced::left_sibling(node_index, height),
+                    node_index + 1,
+                ),
+            };
+            if parent_index > self.num_nodes() {
+                break;
+            }
+
+            authentication_path.push(self.node(sibling_index)?);
+            node_index = parent_index;
+        }
+
+        Ok(MmrMembershipProof::new(authentication_path))
+    }
+
+    /// Remove the last leaf from the MMR, returning it. Returns `None` if the MMR is empty.
+    pub fn remove_last_leaf(&mut self) -> io::Result<Option<Digest>> {
+        let last_node_index = self.num_nodes();
+        if last_node_index == 0 {
+            return Ok(None);
+        }
+
+        // The last node is the peak created by the most recent append. Its height is the number
+        // of parent nodes created in addition to the leaf.
+        let (_, height) = shared_advanced::right_lineage_length_and_own_height(last_node_index);
+        for _ in 0..height {
+            self.storage.pop()?;
+        }
+
+        self.storage.pop()
+    }
+
+    /// Like [`Mmr::peaks`], but returns storage errors instead of panicking.
+    pub fn try_peaks(&self) -> io::Result<Vec<Digest>> {
+        let (_, peak_node_indices) =
+            shared_advanced::get_peak_heights_and_peak_node_indices(self.num_leafs());
+        peak_node_indices
+            .into_iter()
+            .map(|node_index| self.node(node_index))
+            .collect()
+    }
+
+    /// Like [`Mmr::append`], but returns storage errors instead of panicking.
+    pub fn try_append(&mut self, new_leaf: Digest) -> io::Result<MmrMembershipProof> {
+        let leaf_index = self.num_leafs();
+        let mut node_index = self.num_nodes() + 1;
+        let mut acc_hash = new_leaf;
+        self.storage.push(acc_hash)?;
+
+        let (mut right_ancestor_count, mut height) =
+            shared_advanced::right_lineage_length_and_own_height(node_index);
+        while right_ancestor_count != 0 {
+            let left_sibling = self.node(shared_advanced::left_sibling(node_index, height))?;
+            acc_hash = H::hash_pair(left_sibling, acc_hash);
+            self.storage.push(acc_hash)?;
+
+            node_index += 1;
+            right_ancestor_count -= 1;
+            height += 1;
+        }
+
+        self.prove_membership(leaf_index)
+    }
+
+    /// Set the leaf with the given index and recompute all its ancestors. Like
+    /// [`Mmr::mutate_leaf`], but does not require a membership proof and returns storage
+    /// errors instead of panicking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the leaf index is out of bounds.
+    pub fn try_mutate_leaf(&mut self, leaf_index: u64, new_leaf: Digest) -> io::Result<()> {
+        self.assert_leaf_index_is_in_bounds(leaf_index);
+
+        let mut node_index = shared_advanced::leaf_index_to_node_index(leaf_index);
+        let mut acc_hash = new_leaf;
+        self.storage.set(node_index - 1, acc_hash)?;
+
+        loop {
+            let (right_ancestor_count, height) =
+                shared_advanced::right_lineage_length_and_own_height(node_index);
+            let parent_index = match right_ancestor_count {
+                0 => node_index + (1 << (height + 1)),
+                _ => node_index + 1,
+            };
+            if parent_index > self.num_nodes() {
+                break;
+            }
+
+            acc_hash = match right_ancestor_count {
+                0 => H::hash_pair(
+                    acc_hash,
+                    self.node(shared_advanced::right_sibling(node_index, height))?,
+                ),
+                _ => H::hash_pair(
+                    self.node(shared_advanced::left_sibling(node_index, height))?,
+                    acc_hash,
+                ),
+            };
+            self.storage.set(parent_index - 1, acc_hash)?;
+            node_index = parent_index;
+        }
+
+        Ok(())
+    }
+
+    fn assert_leaf_index_is_in_bounds(&self, leaf_index: u64) {
+        let num_leafs = self.num_leafs();
+        assert!(
+            leaf_index < num_leafs,
+            "leaf index {leaf_index} out of bounds for MMR with {num_leafs} leafs"
+        );
+    }
+
+    fn num_nodes(&self) -> u64 {
+        self.storage.len()
+    }
+
+    fn node(&self, node_index: u64) -> io::Result<Digest> {
+        self.storage.get(node_index - 1)
+    }
+}
+
+/// Unwrap the result of a storage access in the infallible [`Mmr`] interface.
+fn expect_storage_access<T>(result: io::Result<T>) -> T {
+    result.unwrap_or_else(|e| panic!("archival MMR storage failed: {e}"))
+}
+
+/// # Panics
+///
+/// All methods panic if the storage fails.
+impl<S: MmrStorage, H: AlgebraicHasher> Mmr<H> for ArchivalMmr<S, H> {
+    fn bag_peaks(&self) -> Digest {
+        bag_peaks_with_hasher::<H>(&self.peaks())
+    }
+
+    fn peaks(&self) -> Vec<Digest> {
+        expect_storage_access(self.try_peaks())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    fn num_leafs(&self) -> u64 {
+        num_nodes_to_num_leafs(self.num_nodes()).unwrap()
+    }
+
+    fn append(&mut self, new_leaf: Digest) -> MmrMembershipProof {
+        expect_storage_access(self.try_append(new_leaf))
+    }
+
+    /// Mutate an existing leaf. Since the archival MMR knows all nodes, the supplied membership
+    /// proof is not used.
+    fn mutate_leaf(&mut self, leaf_mutation: LeafMutation) {
+        let result = self.try_mutate_leaf(leaf_mutation.leaf_index, leaf_mutation.new_leaf);
+        expect_storage_access(result)
+    }
+
+    fn batch_mutate_leaf_and_update_mps(
+        &mut self,
+        membership_proofs: &mut [&mut MmrMembershipProof],
+        membership_proof_leaf_indices: &[u64],
+        leaf_mutations: Vec<LeafMutation>,
+    ) -> Vec<usize> {
+        assert!(
+            leaf_mutations
+                .iter()
+                .map(|leaf_mutation| leaf_mutation.leaf_index)
+                .all_unique(),
+            "Duplicated leafs are not allowed in membership proof updater"
+        );
+
+        for leaf_mutation in leaf_mutations {
+            self.mutate_leaf(leaf_mutation);
+        }
+
+        let mut modified_membership_proofs = vec![];
+        for (i, (membership_proof, &leaf_index)) in membership_proofs
+            .iter_mut()
+            .zip_eq(membership_proof_leaf_indices)
+            .enumerate()
+        {
+            let new_membership_proof = expect_storage_access(self.prove_membership(leaf_index));
+            if new_membership_proof != **membership_proof {
+                modified_membership_proofs.push(i);
+            }
+            **membership_proof = new_membership_proof;
+        }
+
+        modified_membership_proofs
+    }
+
+    fn verify_batch_update(
+        &self,
+        new_peaks: &[Digest],
+        appended_leafs: &[Digest],
+        leaf_mutations: Vec<LeafMutation>,
+    ) -> bool {
+        self.to_accumulator()
+            .verify_batch_update(new_peaks, appended_leafs, leaf_mutations)
+    }
+
+    fn to_accumulator(&self) -> MmrAccumulator<H> {
+        MmrAccumulator::init_with_hasher(self.peaks(), self.num_leafs())
+    }
+}
+
+impl<S: MmrStorage + Default> Default for ArchivalMmr<S> {
+    fn default() -> Self {
+        Self::new(S::default()).expect("default storage should contain the nodes of an MMR")
+    }
+}
+
+/// The number of leafs of the MMR with the given number of nodes, or `None` if no MMR has that
+/// number of nodes.
+fn num_nodes_to_num_leafs(mut num_nodes: u64) -> Option<u64> {
+    // The trees of an MMR have strictly decreasing heights. A perfect binary tree of height `h`
+    // has `2^(h+1) - 1` nodes, which is more than all smaller trees combined.
+    let mut num_leafs = 0;
+    for height in (0..u64::BITS - 1).rev() {
+        let num_nodes_in_tree = (1 << (height + 1)) - 1;
+        if num_nodes >= num_nodes_in_tree {
+            num_nodes -= num_nodes_in_tree;
+            num_leafs += 1 << height;
+        }
+    }
+
+    (num_nodes == 0).then_some(num_leafs)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::util_types::algebraic_hasher::tests::TestHasher;
+    use crate::util_types::mmr::mmr_storage::tests::TempFilePath;
+    use crate::util_types::mmr::mmr_storage::FileStorage;
+    use crate::util_types::mmr::shared_advanced::num_leafs_to_num_nodes;
+
+    fn archival_mmr_from_leafs(leafs: &[Digest]) -> ArchivalMmr<Vec<Digest>> {
+        let mut archival_mmr = ArchivalMmr::new(vec![]).unwrap();
+        for &leaf in leafs {
+            archival_mmr.append(leaf);
+        }
+        archival_mmr
+    }
+
+    #[proptest]
+    fn number_of_leafs_can_be_recovered_from_number_of_nodes(
+        #[strategy(0_u64..1 << 62)] num_leafs: u64,
+    ) {
+        let num_nodes = num_leafs_to_num_nodes(num_leafs);
+        prop_assert_eq!(Some(num_leafs), num_nodes_to_num_leafs(num_nodes));
+    }
+
+    #[test]
+    fn invalid_numbers_of_nodes_are_rejected() {
+        for num_nodes in [2, 5, 6, 9] {
+            assert_eq!(None, num_nodes_to_num_leafs(num_nodes));
+        }
+    }
+
+    #[test]
+    fn storage_with_invalid_number_of_nodes_is_rejected() {
+        let err = ArchivalMmr::new(vec![Digest::default(); 2]).unwrap_err();
+        assert_eq!(ArchivalMmrError::InvalidNumNodes(2), err);
+    }
+
+    #[test]
+    fn reopening_file_storage_after_interrupted_append_fails() {
+        let path = TempFilePath::new();
+        let mut file_storage = FileStorage::open(&path.0).unwrap();
+        for _ in 0..num_leafs_to_num_nodes(4) {
+            file_storage.push(Digest::default()).unwrap();
+        }
+
+        // the last node is the root of the tree with 4 leafs, which is pushed last
+        file_storage.pop().unwrap();
+        drop(file_storage);
+
+        let err = ArchivalMmr::new(FileStorage::open(&path.0).unwrap()).unwrap_err();
+        assert_eq!(ArchivalMmrError::InvalidNumNodes(6), err);
+    }
+
+    #[proptest(cases = 30)]
+    fn archival_mmr_agrees_with_accumulator_on_appends(
+        #[strategy(vec(arb(), 0..100))] leafs: Vec<Digest>,
+    ) {
+        let mut archival_mmr = ArchivalMmr::new(vec![]).unwrap();
+        let mut accumulator = MmrAccumulator::new_from_leafs(vec![]);
+        for (leaf_index, &leaf) in leafs.iter().enumerate() {
+            let archival_proof = archival_mmr.append(leaf);
+            let accumulator_proof = accumulator.append(leaf);
+            prop_assert_eq!(&archival_proof, &accumulator_proof);
+            prop_assert_eq!(archival_mmr.peaks(), accumulator.peaks());
+            prop_assert_eq!(archival_mmr.num_leafs(), accumulator.num_leafs());
+            prop_assert_eq!(leaf, archival_mmr.get_leaf(leaf_index as u64)?);
+        }
+
+        prop_assert_eq!(archival_mmr.bag_peaks(), accumulator.bag_peaks());
+        prop_assert_eq!(accumulator, archival_mmr.to_accumulator());
+    }
+
+    #[proptest(cases = 30)]
+    fn membership_proofs_for_all_leafs_can_be_verified(
+        #[strategy(vec(arb(), 1..100))] leafs: Vec<Digest>,
+    ) {
+        let archival_mmr = archival_mmr_from_leafs(&leafs);
+        let accumulator = MmrAccumulator::new_from_leafs(leafs.clone());
+        for (leaf_index, leaf) in leafs.into_iter().enumerate() {
+            let leaf_index = leaf_index as u64;
+            let proof = archival_mmr.prove_membership(leaf_index)?;
+            let num_leafs = accumulator.num_leafs();
+            prop_assert!(proof.verify(leaf_index, leaf, &accumulator.peaks(), num_leafs));
+        }
+    }
+
+    #[proptest(cases = 30)]
+    fn archival_mmr_agrees_with_accumulator_on_leaf_mutations(
+        #[strategy(vec(arb(), 1..100))] leafs: Vec<Digest>,
+        #[strategy(vec((0..#leafs.len() as u64, arb()), 1..10))] mutations: Vec<(u64, Digest)>,
+    ) {
+        let mut archival_mmr = archival_mmr_from_leafs(&leafs);
+        let mut accumulator = MmrAccumulator::new_from_leafs(leafs);
+        for (leaf_index, new_leaf) in mutations {
+            let proof = archival_mmr.prove_membership(leaf_index)?;
+            let leaf_mutation = LeafMutation::new(leaf_index, new_leaf, proof);
+            accumulator.mutate_leaf(leaf_mutation.clone());
+            archival_mmr.mutate_leaf(leaf_mutation);
+
+            prop_assert_eq!(new_leaf, archival_mmr.get_leaf(leaf_index)?);
+            prop_assert_eq!(archival_mmr.peaks(), accumulator.peaks());
+        }
+    }
+
+    #[proptest(cases = 30)]
+    fn batch_leaf_mutation_updates_membership_proofs(
+        #[strategy(vec(arb(), 1..100))] leafs: Vec<Digest>,
+        #[strategy(vec((0..#leafs.len() as u64, arb()), 1..10))] mutations: Vec<(u64, Digest)>,
+        #[strategy(vec(0..#leafs.len() as u64, 1..10))] proof_leaf_indices: Vec<u64>,
+    ) {
+        let mutations = mutations.into_iter().unique_by(|&(i, _)| i).collect_vec();
+        let mut archival_mmr = archival_mmr_from_leafs(&leafs);
+        let leaf_mutations = mutations
+            .iter()
+            .map(|&(i, leaf)| {
+                Ok(LeafMutation::new(
+                    i,
+                    leaf,
+                    archival_mmr.prove_membership(i)?,
+                ))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let mut proofs = proof_leaf_indices
+            .iter()
+            .map(|&i| archival_mmr.prove_membership(i))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let old_accumulator = archival_mmr.to_accumulator();
+        archival_mmr.batch_mutate_leaf_and_update_mps(
+            &mut proofs.iter_mut().collect_vec(),
+            &proof_leaf_indices,
+            leaf_mutations.clone(),
+        );
+        prop_assert!(old_accumulator.verify_batch_update(
+            &archival_mmr.peaks(),
+            &[],
+            leaf_mutations
+        ));
+
+        for (proof, leaf_index) in proofs.into_iter().zip_eq(proof_leaf_indices) {
+            let leaf = archival_mmr.get_leaf(leaf_index)?;
+            let peaks = archival_mmr.peaks();
+            prop_assert!(proof.verify(leaf_index, leaf, &peaks, archival_mmr.num_leafs()));
+        }
+    }
+
+    #[proptest(cases = 30)]
+    fn removing_last_leaf_undoes_append(
+        #[strategy(vec(arb(), 0..100))] leafs: Vec<Digest>,
+        #[strategy(arb())] new_leaf: Digest,
+    ) {
+        let mut archival_mmr = archival_mmr_from_leafs(&leafs);
+        let accumulator = archival_mmr.to_accumulator();
+        archival_mmr.append(new_leaf);
+
+        prop_assert_eq!(Some(new_leaf), archival_mmr.remove_last_leaf()?);
+        prop_assert_eq!(accumulator, archival_mmr.to_accumulator());
+        let expected_storage = archival_mmr_from_leafs(&leafs).into_storage();
+        prop_assert_eq!(expected_storage, archival_mmr.into_storage());
+    }
+
+    #[test]
+    fn removing_leaf_from_empty_mmr_gives_none() {
+        let mut archival_mmr = ArchivalMmr::new(vec![]).unwrap();
+        assert!(archival_mmr.is_empty());
+        assert_eq!(None, archival_mmr.remove_last_leaf().unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn getting_leaf_out_of_bounds_panics() {
+        let archival_mmr = archival_mmr_from_leafs(&[Digest::default(); 3]);
+        let _ = archival_mmr.get_leaf(3);
+    }
+
+    #[proptest(cases = 10)]
+    fn file_backed_archival_mmr_agrees_with_in_memory_archival_mmr(
+        #[strategy(vec(arb(), 0..50))] leafs: Vec<Digest>,
+        #[strategy(arb())] new_leaf: Digest,
+    ) {
+        let path = TempFilePath::new();
+        let mut original_file_mmr = ArchivalMmr::new(FileStorage::open(&path.0)?)?;
+        for &leaf in &leafs {
+            original_file_mmr.append(leaf);
+        }
+        drop(original_file_mmr);
+
+        let mut file_mmr = ArchivalMmr::new(FileStorage::open(&path.0)?)?;
+        let mut vec_mmr = archival_mmr_from_leafs(&leafs);
+        prop_assert_eq!(vec_mmr.peaks(), file_mmr.peaks());
+        prop_assert_eq!(vec_mmr.append(new_leaf), file_mmr.append(new_leaf));
+        prop_assert_eq!(vec_mmr.remove_last_leaf()?, file_mmr.remove_last_leaf()?);
+        prop_assert_eq!(vec_mmr.to_accumulator(), file_mmr.to_accumulator());
+    }
+
+    #[proptest(cases = 10)]
+    fn archival_mmr_with_custom_hasher_agrees_with_accumulator(
+        #[strategy(vec(arb(), 0..50))] leafs: Vec<Digest>,
+    ) {
+        let mut archival_mmr = ArchivalMmr::<_, TestHasher>::new_with_hasher(vec![])?;
+        for &leaf in &leafs {
+            archival_mmr.append(leaf);
+        }
+        let accumulator = MmrAccumulator::<TestHasher>::new_from_leafs_with_hasher(leafs);
+        prop_assert_eq!(accumulator, archival_mmr.to_accumulator());
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::marker::PhantomData;
 
 use arbitrary::Arbitrary;
 use get_size2::GetSize;
@@ -14,25 +15,45 @@ use super::shared_basic;
 use crate::math::bfield_codec::BFieldCodec;
 use crate::math::digest::Digest;
 use crate::prelude::Tip5;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
 use crate::util_types::mmr::shared_advanced;
-use crate::util_types::shared::bag_peaks;
+use crate::util_types::shared::bag_peaks_with_hasher;
 
+/// The hash function is generic and defaults to [`Tip5`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, GetSize, BFieldCodec)]
-pub struct MmrAccumulator {
+#[get_size(ignore(H))]
+pub struct MmrAccumulator<H: AlgebraicHasher = Tip5> {
     leaf_count: u64,
     peaks: Vec<Digest>,
+
+    #[serde(skip)]
+    #[bfield_codec(ignore)]
+    _hasher: PhantomData<H>,
 }
 
 impl MmrAccumulator {
     pub fn init(peaks: Vec<Digest>, leaf_count: u64) -> Self {
-        Self { leaf_count, peaks }
+        Self::init_with_hasher(peaks, leaf_count)
     }
 
     pub fn new_from_leafs(digests: Vec<Digest>) -> Self {
-        let mut mmra = MmrAccumulator {
-            leaf_count: 0,
-            peaks: vec![],
-        };
+        Self::new_from_leafs_with_hasher(digests)
+    }
+}
+
+impl<H: AlgebraicHasher> MmrAccumulator<H> {
+    /// Like [`MmrAccumulator::init`], but for any [`AlgebraicHasher`].
+    pub fn init_with_hasher(peaks: Vec<Digest>, leaf_count: u64) -> Self {
+        Self {
+            leaf_count,
+            peaks,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Like [`MmrAccumulator::new_from_leafs`], but for any [`AlgebraicHasher`].
+    pub fn new_from_leafs_with_hasher(digests: Vec<Digest>) -> Self {
+        let mut mmra = Self::init_with_hasher(vec![], 0);
         for digest in digests {
             mmra.append(digest);
         }
@@ -41,10 +62,10 @@ impl MmrAccumulator {
     }
 }
 
-impl Mmr for MmrAccumulator {
+impl<H: AlgebraicHasher> Mmr<H> for MmrAccumulator<H> {
     /// Calculate a commitment to the entire MMR.
     fn bag_peaks(&self) -> Digest {
-        bag_peaks(&self.peaks)
+        bag_peaks_with_hasher::<H>(&self.peaks)
     }
 
     /// Return the Merkle tree roots of the Merkle trees that this MMR consists
@@ -66,11 +87,12 @@ impl Mmr for MmrAccumulator {
     /// Add a leaf to the MMR. Returns the membership proof of the newly added
     /// leaf.
     fn append(&mut self, new_leaf: Digest) -> MmrMembershipProof {
-        let (new_peaks, membership_proof) = shared_basic::calculate_new_peaks_from_append(
-            self.leaf_count,
-            self.peaks.clone(),
-            new_leaf,
-        );
+        let (new_peaks, membership_proof) =
+            shared_basic::calculate_new_peaks_from_append_with_hasher::<H>(
+                self.leaf_count,
+                self.peaks.clone(),
+                new_leaf,
+            );
         self.peaks = new_peaks;
         self.leaf_count += 1;
 
@@ -81,7 +103,7 @@ impl Mmr for MmrAccumulator {
     /// membership proof is valid. If the membership proof is wrong, the MMR
     /// will end up in a broken state.
     fn mutate_leaf(&mut self, leaf_mutation: LeafMutation) {
-        self.peaks = shared_basic::calculate_new_peaks_from_leaf_mutation(
+        self.peaks = shared_basic::calculate_new_peaks_from_leaf_mutation_with_hasher::<H>(
             &self.peaks,
             self.leaf_count,
             leaf_mutation.new_leaf,
@@ -137,7 +159,7 @@ impl Mmr for MmrAccumulator {
             // TODO: Should we verify the membership proof here?
 
             // Calculate the new peaks after mutating a leaf
-            running_peaks = shared_basic::calculate_new_peaks_from_leaf_mutation(
+            running_peaks = shared_basic::calculate_new_peaks_from_leaf_mutation_with_hasher::<H>(
                 &running_peaks,
                 self.leaf_count,
                 new_leaf_value,
@@ -149,7 +171,7 @@ impl Mmr for MmrAccumulator {
             // Update all remaining membership proofs with this leaf mutation
             let leaf_mutation =
                 LeafMutation::new(leaf_index_mutated_leaf, new_leaf_value, membership_proof);
-            MmrMembershipProof::batch_update_from_leaf_mutation(
+            MmrMembershipProof::batch_update_from_leaf_mutation_with_hasher::<H>(
                 &mut updated_membership_proofs,
                 &leaf_mutation_indices,
                 leaf_mutation,
@@ -167,7 +189,7 @@ impl Mmr for MmrAccumulator {
         let mut running_leaf_count = self.leaf_count;
         while let Some(new_leaf_for_append) = new_leafs_cloned.pop() {
             let (calculated_new_peaks, _new_membership_proof) =
-                shared_basic::calculate_new_peaks_from_append(
+                shared_basic::calculate_new_peaks_from_append_with_hasher::<H>(
                     running_leaf_count,
                     running_peaks,
                     new_leaf_for_append,
@@ -242,7 +264,7 @@ impl Mmr for MmrAccumulator {
                         .get(&left_sibling_index)
                         .copied()
                         .unwrap_or(hash);
-                    acc_hash = Tip5::hash_pair(sibling_hash, acc_hash);
+                    acc_hash = H::hash_pair(sibling_hash, acc_hash);
 
                     // Find parent node index
                     node_index += 1;
@@ -252,7 +274,7 @@ impl Mmr for MmrAccumulator {
                         .get(&right_sibling_index)
                         .copied()
                         .unwrap_or(hash);
-                    acc_hash = Tip5::hash_pair(acc_hash, sibling_hash);
+                    acc_hash = H::hash_pair(acc_hash, sibling_hash);
 
                     // Find parent node index
                     node_index += 1 << (height + 1);
@@ -308,7 +330,7 @@ impl Mmr for MmrAccumulator {
         modified_membership_proof_indices
     }
 
-    fn to_accumulator(&self) -> MmrAccumulator {
+    fn to_accumulator(&self) -> MmrAccumulator<H> {
         self.to_owned()
     }
 }
@@ -456,14 +478,14 @@ pub mod util {
     }
 }
 
-impl<'a> Arbitrary<'a> for MmrAccumulator {
+impl<'a, H: AlgebraicHasher> Arbitrary<'a> for MmrAccumulator<H> {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         let num_leafs = u.arbitrary::<u64>()? >> 1; // num_leafs can be at most 63 bits
         let peaks = (0..num_leafs.count_ones())
             .map(|_| Digest::arbitrary(u))
             .try_collect()?;
 
-        Ok(MmrAccumulator::init(peaks, num_leafs))
+        Ok(MmrAccumulator::init_with_hasher(peaks, num_leafs))
     }
 }
 
@@ -475,6 +497,7 @@ mod accumulator_mmr_tests {
     use itertools::Itertools;
     use num_traits::ConstZero;
     use proptest::collection::vec;
+    use proptest::prop_assert;
     use proptest::prop_assert_eq;
     use proptest_arbitrary_interop::arb;
     use rand::distributions::Uniform;
@@ -490,22 +513,17 @@ mod accumulator_mmr_tests {
     use crate::math::tip5::Tip5;
     use crate::mock::mmr::get_mock_ammr_from_digests;
     use crate::mock::mmr::MockMmr;
+    use crate::util_types::algebraic_hasher::tests::TestHasher;
 
     impl From<MockMmr> for MmrAccumulator {
         fn from(ammr: MockMmr) -> Self {
-            MmrAccumulator {
-                leaf_count: ammr.num_leafs(),
-                peaks: ammr.peaks(),
-            }
+            MmrAccumulator::init(ammr.peaks(), ammr.num_leafs())
         }
     }
 
     impl From<&MockMmr> for MmrAccumulator {
         fn from(ammr: &MockMmr) -> Self {
-            MmrAccumulator {
-                leaf_count: ammr.num_leafs(),
-                peaks: ammr.peaks(),
-            }
+            MmrAccumulator::init(ammr.peaks(), ammr.num_leafs())
         }
     }
 
@@ -967,4 +985,24 @@ mod accumulator_mmr_tests {
             mmr_mp.verify(*mmr_leaf_index, *leaf, &mmra.peaks(), mmra.num_leafs());
         }
     }
+
+    #[proptest(cases = 20)]
+    fn mmra_with_custom_hasher_produces_valid_membership_proofs(
+        #[strategy(vec(arb(), 0..40))] leafs: Vec<Digest>,
+        #[strategy(arb())] new_leaf: Digest,
+    ) {
+        let mut mmra = MmrAccumulator::<TestHasher>::new_from_leafs_with_hasher(leafs);
+        let leaf_index = mmra.num_leafs();
+        let membership_proof = mmra.append(new_leaf);
+        prop_assert!(membership_proof.verify_with_hasher::<TestHasher>(
+            leaf_index,
+            new_leaf,
+            &mmra.peaks(),
+            mmra.num_leafs()
+        ));
+        prop_assert_eq!(
+            bag_peaks_with_hasher::<TestHasher>(&mmra.peaks()),
+            mmra.bag_peaks()
+        );
+    }
 }
@@ -17,6 +17,7 @@ use super::shared_basic;
 use crate::math::bfield_codec::BFieldCodec;
 use crate::math::digest::Digest;
 use crate::prelude::Tip5;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
 
 #[derive(Debug, Clone, Serialize, Deserialize, GetSize, BFieldCodec, Arbitrary)]
 pub struct MmrMembershipProof {
@@ -46,6 +47,18 @@ impl MmrMembershipProof {
         leaf_hash: Digest,
         peaks: &[Digest],
         leaf_count: u64,
+    ) -> bool {
+        self.verify_with_hasher::<Tip5>(leaf_index, leaf_hash, peaks, leaf_count)
+    }
+
+    /// Like [`verify`](Self::verify), but for any [`AlgebraicHasher`], not just
+    /// [`Tip5`].
+    pub fn verify_with_hasher<H: AlgebraicHasher>(
+        &self,
+        leaf_index: u64,
+        leaf_hash: Digest,
+        peaks: &[Digest],
+        leaf_count: u64,
     ) -> bool {
         // Return false if leaf index is out-of-bounds.
         if leaf_index >= leaf_count {
@@ -73,10 +86,10 @@ impl MmrMembershipProof {
             let ap_element = self.authentication_path[i];
             if mt_index % 2 == 0 {
                 // node of `acc_hash` is left child
-                acc_hash = Tip5::hash_pair(acc_hash, ap_element);
+                acc_hash = H::hash_pair(acc_hash, ap_element);
             } else {
                 // node of `acc_hash` is right child
-                acc_hash = Tip5::hash_pair(ap_element, acc_hash);
+                acc_hash = H::hash_pair(ap_element, acc_hash);
             }
 
             i += 1;
@@ -142,6 +155,23 @@ impl MmrMembershipProof {
         old_mmr_leaf_count: u64,
         new_mmr_leaf: Digest,
         old_mmr_peaks: &[Digest],
+    ) -> bool {
+        self.update_from_append_with_hasher::<Tip5>(
+            membership_proof_leaf_index,
+            old_mmr_leaf_count,
+            new_mmr_leaf,
+            old_mmr_peaks,
+        )
+    }
+
+    /// Like [`update_from_append`](Self::update_from_append), but for any
+    /// [`AlgebraicHasher`], not just [`Tip5`].
+    pub fn update_from_append_with_hasher<H: AlgebraicHasher>(
+        &mut self,
+        membership_proof_leaf_index: u64,
+        old_mmr_leaf_count: u64,
+        new_mmr_leaf: Digest,
+        old_mmr_peaks: &[Digest],
     ) -> bool {
         // 1. Get index of authentication paths's peak
         // 2. Get node indices for nodes added by the append
@@ -204,7 +234,7 @@ impl MmrMembershipProof {
             known_digests.insert(*node_index, acc_hash.to_owned());
 
             // peaks are always left children, so we don't have to check for that
-            acc_hash = Tip5::hash_pair(old_peak_digest, acc_hash);
+            acc_hash = H::hash_pair(old_peak_digest, acc_hash);
 
             // once we encouter the first of the needed accumulator indices,
             // we can break. Just like we could in the update for the leaf update
@@ -238,6 +268,24 @@ impl MmrMembershipProof {
         old_leaf_count: u64,
         new_leaf: Digest,
         old_peaks: &[Digest],
+    ) -> Vec<usize> {
+        Self::batch_update_from_append_with_hasher::<Tip5>(
+            membership_proofs,
+            membership_proof_leaf_indices,
+            old_leaf_count,
+            new_leaf,
+            old_peaks,
+        )
+    }
+
+    /// Like [`batch_update_from_append`](Self::batch_update_from_append), but for
+    /// any [`AlgebraicHasher`], not just [`Tip5`].
+    pub fn batch_update_from_append_with_hasher<H: AlgebraicHasher>(
+        membership_proofs: &mut [&mut Self],
+        membership_proof_leaf_indices: &[u64],
+        old_leaf_count: u64,
+        new_leaf: Digest,
+        old_peaks: &[Digest],
     ) -> Vec<usize> {
         assert_eq!(
             membership_proofs.len(),
@@ -292,7 +340,7 @@ impl MmrMembershipProof {
             }
 
             // peaks are always left children, so we don't have to check for that
-            acc_hash = Tip5::hash_pair(old_peak_digest, acc_hash);
+            acc_hash = H::hash_pair(old_peak_digest, acc_hash);
         }
 
         // Loop over all membership proofs and insert missing hashes for each
@@ -348,6 +396,16 @@ impl MmrMembershipProof {
         &mut self,
         own_mp_leaf_index: u64,
         leaf_mutation: &LeafMutation,
+    ) -> bool {
+        self.update_from_leaf_mutation_with_hasher::<Tip5>(own_mp_leaf_index, leaf_mutation)
+    }
+
+    /// Like [`update_from_leaf_mutation`](Self::update_from_leaf_mutation), but for
+    /// any [`AlgebraicHasher`], not just [`Tip5`].
+    pub fn update_from_leaf_mutation_with_hasher<H: AlgebraicHasher>(
+        &mut self,
+        own_mp_leaf_index: u64,
+        leaf_mutation: &LeafMutation,
     ) -> bool {
         let affected_node_indices: HashSet<u64> =
             leaf_mutation.affected_node_indices().into_iter().collect();
@@ -388,12 +446,12 @@ impl MmrMembershipProof {
             let (acc_right_ancestor_count, acc_height) =
                 shared_advanced::right_lineage_length_and_own_height(node_index);
             if acc_right_ancestor_count != 0 {
-                acc_hash = Tip5::hash_pair(hash, acc_hash);
+                acc_hash = H::hash_pair(hash, acc_hash);
 
                 // parent of right child is +1
                 node_index += 1;
             } else {
-                acc_hash = Tip5::hash_pair(acc_hash, hash);
+                acc_hash = H::hash_pair(acc_hash, hash);
 
                 // parent of left child:
                 node_index += 1 << (acc_height + 1);
@@ -430,6 +488,21 @@ impl MmrMembershipProof {
         membership_proofs: &mut [Self],
         membership_proof_leaf_indices: &[u64],
         leaf_mutation: LeafMutation,
+    ) -> Vec<u64> {
+        Self::batch_update_from_leaf_mutation_with_hasher::<Tip5>(
+            membership_proofs,
+            membership_proof_leaf_indices,
+            leaf_mutation,
+        )
+    }
+
+    /// Like
+    /// [`batch_update_from_leaf_mutation`](Self::batch_update_from_leaf_mutation),
+    /// but for any [`AlgebraicHasher`], not just [`Tip5`].
+    pub fn batch_update_from_leaf_mutation_with_hasher<H: AlgebraicHasher>(
+        membership_proofs: &mut [Self],
+        membership_proof_leaf_indices: &[u64],
+        leaf_mutation: LeafMutation,
     ) -> Vec<u64> {
         assert_eq!(
             membership_proofs.len(),
@@ -465,13 +538,13 @@ impl MmrMembershipProof {
                 shared_advanced::right_lineage_length_and_own_height(node_index);
             if right_ancestor_count != 0 {
                 // node is right child
-                acc_hash = Tip5::hash_pair(hash, acc_hash);
+                acc_hash = H::hash_pair(hash, acc_hash);
 
                 // parent of right child is +1
                 node_index += 1;
             } else {
                 // node is left child
-                acc_hash = Tip5::hash_pair(acc_hash, hash);
+                acc_hash = H::hash_pair(acc_hash, hash);
 
                 // parent of left child:
                 node_index += 1 << (acc_height + 1);
@@ -528,6 +601,21 @@ impl MmrMembershipProof {
     ///
     /// Returns those indices into the slice of membership proofs that were updated.
     pub fn batch_update_from_batch_leaf_mutation(
+        membership_proofs: &mut [&mut Self],
+        membership_proof_leaf_indices: &[u64],
+        leaf_mutations: Vec<LeafMutation>,
+    ) -> Vec<usize> {
+        Self::batch_update_from_batch_leaf_mutation_with_hasher::<Tip5>(
+            membership_proofs,
+            membership_proof_leaf_indices,
+            leaf_mutations,
+        )
+    }
+
+    /// Like
+    /// [`batch_update_from_batch_leaf_mutation`](Self::batch_update_from_batch_leaf_mutation),
+    /// but for any [`AlgebraicHasher`], not just [`Tip5`].
+    pub fn batch_update_from_batch_leaf_mutation_with_hasher<H: AlgebraicHasher>(
         membership_proofs: &mut [&mut Self],
         membership_proof_leaf_indices: &[u64],
         mut leaf_mutations: Vec<LeafMutation>,
@@ -579,7 +667,7 @@ impl MmrMembershipProof {
                         .get(&left_sibling_index)
                         .copied()
                         .unwrap_or(hash);
-                    acc_hash = Tip5::hash_pair(sibling_hash, acc_hash);
+                    acc_hash = H::hash_pair(sibling_hash, acc_hash);
 
                     // Find parent node index
                     node_index += 1;
@@ -589,7 +677,7 @@ impl MmrMembershipProof {
                         .get(&right_sibling_index)
                         .copied()
                         .unwrap_or(hash);
-                    acc_hash = Tip5::hash_pair(acc_hash, sibling_hash);
+                    acc_hash = H::hash_pair(acc_hash, sibling_hash);
 
                     // Find parent node index
                     node_index += 1 << (height + 1);
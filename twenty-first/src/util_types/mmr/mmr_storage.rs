@@ -0,0 +1,438 @@
+//! Storage backends for the [`ArchivalMmr`](super::archival_mmr::ArchivalMmr).
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+
+use crate::math::digest::Digest;
+
+/// A list of [`Digest`]s, persisting the nodes of an [`ArchivalMmr`].
+///
+/// The archival MMR only ever appends nodes to the end, overwrites existing nodes, or removes
+/// nodes from the end.
+///
+/// All methods that access the stored digests return the I/O errors of the backend. The
+/// in-memory [`Vec`] backend never fails.
+///
+/// [`ArchivalMmr`]: super::archival_mmr::ArchivalMmr
+pub trait MmrStorage {
+    /// The number of stored digests.
+    fn len(&self) -> u64;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    fn get(&self, index: u64) -> io::Result<Digest>;
+
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    fn set(&mut self, index: u64, digest: Digest) -> io::Result<()>;
+
+    fn push(&mut self, digest: Digest) -> io::Result<()>;
+
+    fn pop(&mut self) -> io::Result<Option<Digest>>;
+}
+
+impl MmrStorage for Vec<Digest> {
+    fn len(&self) -> u64 {
+        Vec::len(self) as u64
+    }
+
+    fn get(&self, index: u64) -> io::Result<Digest> {
+        Ok(self[index as usize])
+    }
+
+    fn set(&mut self, index: u64, digest: Digest) -> io::Result<()> {
+        self[index as usize] = digest;
+        Ok(())
+    }
+
+    fn push(&mut self, digest: Digest) -> io::Result<()> {
+        Vec::push(self, digest);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> io::Result<Option<Digest>> {
+        Ok(Vec::pop(self))
+    }
+}
+
+/// Stores [`Digest`]s in an append-only log file.
+///
+/// Every mutation appends one record to the end of the file; existing records are never
+/// modified, and the file is never truncated. A record consists of a one-byte tag, the
+/// 8-byte little-endian index of the digest it refers to, and [`Digest::BYTES`] bytes of
+/// digest:
+///  - a push record appends a digest,
+///  - a set record overwrites a digest,
+///  - a pop record removes the last digest. Its digest bytes are zero.
+///
+/// Reading a digest returns the digest of the latest push or set record for its index. To
+/// find that record without scanning the file, the storage keeps the offset of the latest
+/// record for every stored digest in memory. [Opening](Self::open) the file replays all
+/// records to rebuild these offsets.
+///
+/// The file grows with every mutation, including removals.
+#[derive(Debug)]
+pub struct FileStorage {
+    file: File,
+
+    /// For every stored digest, the offset of the latest record setting it.
+    record_offsets: Vec<u64>,
+}
+
+/// The tags of the records in a [`FileStorage`]'s log.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum RecordTag {
+    Push = 0,
+    Set = 1,
+    Pop = 2,
+}
+
+impl RecordTag {
+    fn from_byte(byte: u8) -> Option<Self> {
+        [Self::Push, Self::Set, Self::Pop]
+            .into_iter()
+            .find(|&tag| tag as u8 == byte)
+    }
+}
+
+impl FileStorage {
+    /// The number of bytes of every record in the log.
+    const RECORD_BYTES: usize = 1 + 8 + Digest::BYTES;
+
+    /// The offset of the digest within a record.
+    const DIGEST_OFFSET: u64 = 1 + 8;
+
+    /// Open the log file at the given path, creating it if it does not exist. An existing
+    /// file's records are replayed.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the file cannot be opened, created, or read, or if it does not consist of
+    /// valid records. The latter happens, for example, if an append to the file was
+    /// interrupted.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut record_offsets = vec![];
+        let mut reader = BufReader::new(&file);
+        let mut record = [0; Self::RECORD_BYTES];
+        let mut offset = 0;
+        while Self::read_record(&mut reader, &mut record)? {
+            Self::replay_record(&mut record_offsets, offset, &record)?;
+            offset += Self::RECORD_BYTES as u64;
+        }
+
+        Ok(Self {
+            file,
+            record_offsets,
+        })
+    }
+
+    /// Flush all data to disk. See [`File::sync_all`].
+    pub fn sync_all(&self) -> io::Result<()> {
+        self.file.sync_all()
+    }
+
+    /// Read the next record into the buffer. Returns `false` if there are no more records.
+    fn read_record(reader: &mut impl Read, record: &mut [u8]) -> io::Result<bool> {
+        let mut num_read_bytes = 0;
+        while num_read_bytes < record.len() {
+            match reader.read(&mut record[num_read_bytes..]) {
+                Ok(0) => break,
+                Ok(n) => num_read_bytes += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        match num_read_bytes {
+            0 => Ok(false),
+            n if n == record.len() => Ok(true),
+            _ => Err(invalid_data("incomplete record at the end of the file")),
+        }
+    }
+
+    fn replay_record(record_offsets: &mut Vec<u64>, offset: u64, record: &[u8]) -> io::Result<()> {
+        let (&tag, rest) = record.split_first().unwrap();
+        let (index, digest) = rest.split_at(8);
+        let index = u64::from_le_bytes(index.try_into().unwrap());
+        let len = record_offsets.len() as u64;
+
+        match RecordTag::from_byte(tag) {
+            Some(RecordTag::Push) if index == len => {
+                Self::digest_from_bytes(digest)?;
+                record_offsets.push(offset);
+            }
+            Some(RecordTag::Set) if index < len => {
+                Self::digest_from_bytes(digest)?;
+                record_offsets[index as usize] = offset;
+            }
+            Some(RecordTag::Pop) if index.checked_add(1) == Some(len) => {
+                record_offsets.pop();
+            }
+            _ => {
+                let msg =
+                    format!("invalid record with tag {tag} for index {index} at offset {offset}");
+                return Err(invalid_data(msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn digest_from_bytes(bytes: &[u8]) -> io::Result<Digest> {
+        let bytes = <[u8; Digest::BYTES]>::try_from(bytes).unwrap();
+        Digest::try_from(bytes).map_err(invalid_data)
+    }
+
+    /// Read the digest of the latest record for the given index.
+    fn read_digest(&self, index: u64) -> io::Result<Digest> {
+        let mut file = &self.file;
+        let record_offset = self.record_offsets[index as usize];
+        file.seek(SeekFrom::Start(record_offset + Self::DIGEST_OFFSET))?;
+        let mut bytes = [0; Digest::BYTES];
+        file.read_exact(&mut bytes)?;
+        Self::digest_from_bytes(&bytes)
+    }
+
+    /// Append a record to the log, returning its offset.
+    fn append_record(&self, tag: RecordTag, index: u64, digest: Digest) -> io::Result<u64> {
+        let mut file = &self.file;
+
+        // A previous append might have failed halfway. Appending after an incomplete record
+        // would corrupt the log.
+        let offset = file.seek(SeekFrom::End(0))?;
+        if offset % Self::RECORD_BYTES as u64 != 0 {
+            return Err(invalid_data("incomplete record at the end of the file"));
+        }
+
+        let mut record = [0; Self::RECORD_BYTES];
+        record[0] = tag as u8;
+        record[1..9].copy_from_slice(&index.to_le_bytes());
+        if tag != RecordTag::Pop {
+            record[9..].copy_from_slice(&<[u8; Digest::BYTES]>::from(digest));
+        }
+        file.write_all(&record)?;
+
+        Ok(offset)
+    }
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+impl MmrStorage for FileStorage {
+    fn len(&self) -> u64 {
+        self.record_offsets.len() as u64
+    }
+
+    fn get(&self, index: u64) -> io::Result<Digest> {
+        assert!(
+            index < self.len(),
+            "index {index} out of bounds {}",
+            self.len()
+        );
+        self.read_digest(index)
+    }
+
+    fn set(&mut self, index: u64, digest: Digest) -> io::Result<()> {
+        assert!(
+            index < self.len(),
+            "index {index} out of bounds {}",
+            self.len()
+        );
+        let offset = self.append_record(RecordTag::Set, index, digest)?;
+        self.record_offsets[index as usize] = offset;
+        Ok(())
+    }
+
+    fn push(&mut self, digest: Digest) -> io::Result<()> {
+        let offset = self.append_record(RecordTag::Push, self.len(), digest)?;
+        self.record_offsets.push(offset);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> io::Result<Option<Digest>> {
+        let Some(index) = self.len().checked_sub(1) else {
+            return Ok(None);
+        };
+        let digest = self.read_digest(index)?;
+        self.append_record(RecordTag::Pop, index, Digest::default())?;
+        self.record_offsets.pop();
+
+        Ok(Some(digest))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::path::PathBuf;
+
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use rand::random;
+    use test_strategy::proptest;
+
+    use super::*;
+
+    /// A path to a file in the system's temporary directory, which is deleted on drop.
+    pub(crate) struct TempFilePath(pub PathBuf);
+
+    impl TempFilePath {
+        pub(crate) fn new() -> Self {
+            let file_name = format!("twenty-first-mmr-storage-{:x}", random::<u64>());
+            Self(std::env::temp_dir().join(file_name))
+        }
+    }
+
+    impl Drop for TempFilePath {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn file_storage_behaves_like_vec_storage(
+        #[strategy(vec(arb(), 1..20))] digests: Vec<Digest>,
+        #[strategy(arb())] new_digest: Digest,
+        #[strategy(0..#digests.len() as u64)] index: u64,
+    ) {
+        let path = TempFilePath::new();
+        let mut file_storage = FileStorage::open(&path.0)?;
+        let mut vec_storage = vec![];
+        for &digest in &digests {
+            file_storage.push(digest)?;
+            MmrStorage::push(&mut vec_storage, digest)?;
+        }
+        file_storage.set(index, new_digest)?;
+        MmrStorage::set(&mut vec_storage, index, new_digest)?;
+        prop_assert_eq!(MmrStorage::pop(&mut vec_storage)?, file_storage.pop()?);
+
+        prop_assert_eq!(MmrStorage::len(&vec_storage), file_storage.len());
+        for i in 0..file_storage.len() {
+            prop_assert_eq!(MmrStorage::get(&vec_storage, i)?, file_storage.get(i)?);
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn file_storage_persists_digests(
+        #[strategy(vec(arb(), 1..20))] digests: Vec<Digest>,
+        #[strategy(arb())] new_digest: Digest,
+        #[strategy(0..#digests.len() as u64)] index: u64,
+    ) {
+        let path = TempFilePath::new();
+        let mut original_storage = FileStorage::open(&path.0)?;
+        let mut vec_storage = vec![];
+        for &digest in &digests {
+            original_storage.push(digest)?;
+            MmrStorage::push(&mut vec_storage, digest)?;
+        }
+        original_storage.pop()?;
+        MmrStorage::pop(&mut vec_storage)?;
+        if index < original_storage.len() {
+            original_storage.set(index, new_digest)?;
+            MmrStorage::set(&mut vec_storage, index, new_digest)?;
+        }
+        drop(original_storage);
+
+        let reopened_storage = FileStorage::open(&path.0)?;
+        prop_assert_eq!(MmrStorage::len(&vec_storage), reopened_storage.len());
+        for (i, digest) in vec_storage.into_iter().enumerate() {
+            prop_assert_eq!(digest, reopened_storage.get(i as u64)?);
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn file_storage_only_appends_to_file(
+        #[strategy(vec(arb(), 1..20))] digests: Vec<Digest>,
+        #[strategy(arb())] new_digest: Digest,
+        #[strategy(0..#digests.len() as u64)] index: u64,
+    ) {
+        let path = TempFilePath::new();
+        let mut file_storage = FileStorage::open(&path.0)?;
+        for &digest in &digests {
+            file_storage.push(digest)?;
+        }
+
+        let mut file_before = std::fs::read(&path.0)?;
+        for step in 0..3 {
+            match step {
+                0 => file_storage.set(index, new_digest)?,
+                1 => _ = file_storage.pop()?,
+                _ => file_storage.push(new_digest)?,
+            }
+            let file_after = std::fs::read(&path.0)?;
+            prop_assert_eq!(
+                file_before.len() + FileStorage::RECORD_BYTES,
+                file_after.len()
+            );
+            prop_assert_eq!(&file_before[..], &file_after[..file_before.len()]);
+            file_before = file_after;
+        }
+    }
+
+    #[test]
+    fn opening_file_with_incomplete_record_fails() {
+        let path = TempFilePath::new();
+        let mut file_storage = FileStorage::open(&path.0).unwrap();
+        file_storage.push(Digest::default()).unwrap();
+        drop(file_storage);
+
+        let mut file = OpenOptions::new().append(true).open(&path.0).unwrap();
+        file.write_all(&[RecordTag::Push as u8]).unwrap();
+        drop(file);
+
+        let err = FileStorage::open(&path.0).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    }
+
+    #[test]
+    fn opening_file_with_invalid_records_fails() {
+        let push_at_wrong_index = [RecordTag::Push as u8, 1];
+        let set_out_of_bounds = [RecordTag::Set as u8, 0];
+        let pop_from_empty = [RecordTag::Pop as u8, 0];
+        let unknown_tag = [42, 0];
+        for [tag, index] in [
+            push_at_wrong_index,
+            set_out_of_bounds,
+            pop_from_empty,
+            unknown_tag,
+        ] {
+            let path = TempFilePath::new();
+            let mut record = [0; FileStorage::RECORD_BYTES];
+            record[0] = tag;
+            record[1] = index;
+            std::fs::write(&path.0, record).unwrap();
+
+            let err = FileStorage::open(&path.0).unwrap_err();
+            assert_eq!(io::ErrorKind::InvalidData, err.kind(), "tag: {tag}");
+        }
+    }
+
+    #[test]
+    fn popping_from_empty_file_storage_gives_none() {
+        let path = TempFilePath::new();
+        let mut file_storage = FileStorage::open(&path.0).unwrap();
+        assert!(file_storage.is_empty());
+        assert_eq!(None, file_storage.pop().unwrap());
+    }
+}
@@ -6,11 +6,11 @@ use super::mmr_accumulator::MmrAccumulator;
 use super::shared_advanced::get_peak_heights_and_peak_node_indices;
 use super::shared_advanced::parent;
 use super::shared_advanced::right_sibling;
-use super::shared_basic::calculate_new_peaks_from_append;
+use super::shared_basic::calculate_new_peaks_from_append_with_hasher;
 use super::shared_basic::leaf_index_to_mt_index_and_peak_index;
 use crate::prelude::Digest;
 use crate::prelude::Mmr;
-use crate::prelude::Tip5;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
 use crate::util_types::mmr::shared_advanced::left_sibling;
 use crate::util_types::mmr::shared_advanced::node_indices_added_by_append;
 
@@ -30,7 +30,10 @@ impl MmrSuccessorProof {
     /// # Panics
     ///
     ///  - if the number of leafs in the MMRA is greater than or equal to 2^63
-    pub fn new_from_batch_append(mmra: &MmrAccumulator, new_leafs: &[Digest]) -> Self {
+    pub fn new_from_batch_append<H: AlgebraicHasher>(
+        mmra: &MmrAccumulator<H>,
+        new_leafs: &[Digest],
+    ) -> Self {
         let (heights_of_old_peaks, indices_of_old_peaks) =
             get_peak_heights_and_peak_node_indices(mmra.num_leafs());
         let (_heights_of_new_peaks, indices_of_new_peaks) =
@@ -70,7 +73,7 @@ impl MmrSuccessorProof {
         for &new_leaf in new_leafs {
             let new_node_indices = node_indices_added_by_append(current_leaf_count);
 
-            let (new_peaks, membership_proof) = calculate_new_peaks_from_append(
+            let (new_peaks, membership_proof) = calculate_new_peaks_from_append_with_hasher::<H>(
                 current_leaf_count,
                 current_peaks.clone(),
                 new_leaf,
@@ -83,7 +86,7 @@ impl MmrSuccessorProof {
                 .into_iter()
                 .scan(new_leaf, |runner, path_node| {
                     let yld = *runner;
-                    *runner = Tip5::hash_pair(path_node, *runner);
+                    *runner = H::hash_pair(path_node, *runner);
                     Some(yld)
                 })
                 .collect_vec();
@@ -116,7 +119,11 @@ impl MmrSuccessorProof {
     }
 
     /// Verify that `old_mmra` is a predecessor of `new_mmra`.
-    pub fn verify(&self, old_mmra: &MmrAccumulator, new_mmra: &MmrAccumulator) -> bool {
+    pub fn verify<H: AlgebraicHasher>(
+        &self,
+        old_mmra: &MmrAccumulator<H>,
+        new_mmra: &MmrAccumulator<H>,
+    ) -> bool {
         if old_mmra.num_leafs() > new_mmra.num_leafs() {
             return false;
         }
@@ -149,9 +156,9 @@ impl MmrSuccessorProof {
                     .unwrap_or(Digest::default());
                 let is_left_sibling = current_merkle_tree_index & 1 == 0;
                 current_node = if is_left_sibling {
-                    Tip5::hash_pair(current_node, sibling)
+                    H::hash_pair(current_node, sibling)
                 } else {
-                    Tip5::hash_pair(sibling, current_node)
+                    H::hash_pair(sibling, current_node)
                 };
                 current_merkle_tree_index >>= 1;
                 ap_index += 1;
@@ -1,6 +1,8 @@
 use super::mmr_accumulator::MmrAccumulator;
 use super::mmr_membership_proof::MmrMembershipProof;
 use crate::math::digest::Digest;
+use crate::prelude::Tip5;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
 
 /// A wrapper for the data needed to change the value of a leaf in an MMR when
 /// only the MMR-accumulator is known, i.e., only the peaks and the leaf-count
@@ -39,7 +41,8 @@ impl LeafMutation {
     }
 }
 
-pub trait Mmr {
+/// The hash function is generic and defaults to [`Tip5`].
+pub trait Mmr<H: AlgebraicHasher = Tip5> {
     // constructors cannot be part of the interface since the archival version requires a
     // database which we want the caller to create, and the accumulator does not need a
     // constructor.
@@ -84,5 +87,5 @@ pub trait Mmr {
     ) -> bool;
 
     /// Return an MMR accumulator containing only peaks and leaf count
-    fn to_accumulator(&self) -> MmrAccumulator;
+    fn to_accumulator(&self) -> MmrAccumulator<H>;
 }
@@ -1,6 +1,7 @@
 use super::mmr_membership_proof::MmrMembershipProof;
 use crate::math::digest::Digest;
 use crate::prelude::Tip5;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
 
 #[inline]
 pub fn left_child(node_index: u64, height: u32) -> u64 {
@@ -80,6 +81,15 @@ pub fn calculate_new_peaks_from_append(
     old_leaf_count: u64,
     old_peaks: Vec<Digest>,
     new_leaf: Digest,
+) -> (Vec<Digest>, MmrMembershipProof) {
+    calculate_new_peaks_from_append_with_hasher::<Tip5>(old_leaf_count, old_peaks, new_leaf)
+}
+
+/// Like [`calculate_new_peaks_from_append`], but for any [`AlgebraicHasher`], not just [`Tip5`].
+pub fn calculate_new_peaks_from_append_with_hasher<H: AlgebraicHasher>(
+    old_leaf_count: u64,
+    old_peaks: Vec<Digest>,
+    new_leaf: Digest,
 ) -> (Vec<Digest>, MmrMembershipProof) {
     let mut peaks = old_peaks;
     peaks.push(new_leaf);
@@ -89,7 +99,7 @@ pub fn calculate_new_peaks_from_append(
         let new_hash = peaks.pop().unwrap();
         let previous_peak = peaks.pop().unwrap();
         membership_proof.authentication_path.push(previous_peak);
-        peaks.push(Tip5::hash_pair(previous_peak, new_hash));
+        peaks.push(H::hash_pair(previous_peak, new_hash));
         right_lineage_count -= 1;
     }
 
@@ -105,6 +115,24 @@ pub fn calculate_new_peaks_from_leaf_mutation(
     new_leaf: Digest,
     leaf_index: u64,
     membership_proof: &MmrMembershipProof,
+) -> Vec<Digest> {
+    calculate_new_peaks_from_leaf_mutation_with_hasher::<Tip5>(
+        old_peaks,
+        leaf_count,
+        new_leaf,
+        leaf_index,
+        membership_proof,
+    )
+}
+
+/// Like [`calculate_new_peaks_from_leaf_mutation`], but for any [`AlgebraicHasher`], not just
+/// [`Tip5`].
+pub fn calculate_new_peaks_from_leaf_mutation_with_hasher<H: AlgebraicHasher>(
+    old_peaks: &[Digest],
+    leaf_count: u64,
+    new_leaf: Digest,
+    leaf_index: u64,
+    membership_proof: &MmrMembershipProof,
 ) -> Vec<Digest> {
     let (mut acc_mt_index, peak_index) =
         leaf_index_to_mt_index_and_peak_index(leaf_index, leaf_count);
@@ -114,10 +142,10 @@ pub fn calculate_new_peaks_from_leaf_mutation(
         let ap_element = membership_proof.authentication_path[i];
         if acc_mt_index % 2 == 1 {
             // Node with `acc_hash` is a right child
-            acc_hash = Tip5::hash_pair(ap_element, acc_hash);
+            acc_hash = H::hash_pair(ap_element, acc_hash);
         } else {
             // Node with `acc_hash` is a left child
-            acc_hash = Tip5::hash_pair(acc_hash, ap_element);
+            acc_hash = H::hash_pair(acc_hash, ap_element);
         }
 
         acc_mt_index /= 2;
@@ -0,0 +1,310 @@
+//! A [Fiat-Shamir][fs] transcript of the messages a prover sends to a verifier.
+//!
+//! The prover [enqueues](ProofStream::enqueue) [`ProofItem`]s, each of which is absorbed into the
+//! transcript's [`Sponge`]. Verifier challenges are derived by squeezing that same sponge.
+//! The verifier turns the received items back into a [`ProofStream`], [dequeues](ProofStream::dequeue)
+//! the items in the same order, and thereby derives the same challenges. Dequeuing an item of an
+//! unexpected type results in an error.
+//!
+//! [fs]: https://en.wikipedia.org/wiki/Fiat%E2%80%93Shamir_heuristic
+
+use std::result;
+
+use arbitrary::Arbitrary;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::digest::Digest;
+use crate::math::x_field_element::XFieldElement;
+use crate::util_types::sponge::Sponge;
+
+type Result<T> = result::Result<T, ProofStreamError>;
+
+/// A single message from the prover to the verifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BFieldCodec, Arbitrary)]
+pub enum ProofItem {
+    MerkleRoot(Digest),
+    AuthenticationStructure(Vec<Digest>),
+    XFieldElements(Vec<XFieldElement>),
+    BFieldElements(Vec<BFieldElement>),
+}
+
+impl ProofItem {
+    /// The name of the item's variant, for example, `"MerkleRoot"`.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::MerkleRoot(_) => "MerkleRoot",
+            Self::AuthenticationStructure(_) => "AuthenticationStructure",
+            Self::XFieldElements(_) => "XFieldElements",
+            Self::BFieldElements(_) => "BFieldElements",
+        }
+    }
+
+    pub fn try_into_merkle_root(self) -> Result<Digest> {
+        match self {
+            Self::MerkleRoot(root) => Ok(root),
+            other => Err(other.unexpected("MerkleRoot")),
+        }
+    }
+
+    pub fn try_into_authentication_structure(self) -> Result<Vec<Digest>> {
+        match self {
+            Self::AuthenticationStructure(auth_structure) => Ok(auth_structure),
+            other => Err(other.unexpected("AuthenticationStructure")),
+        }
+    }
+
+    pub fn try_into_xfield_elements(self) -> Result<Vec<XFieldElement>> {
+        match self {
+            Self::XFieldElements(elements) => Ok(elements),
+            other => Err(other.unexpected("XFieldElements")),
+        }
+    }
+
+    pub fn try_into_bfield_elements(self) -> Result<Vec<BFieldElement>> {
+        match self {
+            Self::BFieldElements(elements) => Ok(elements),
+            other => Err(other.unexpected("BFieldElements")),
+        }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ProofStreamError {
+        ProofStreamError::UnexpectedItem {
+            expected,
+            actual: self.variant_name(),
+        }
+    }
+}
+
+/// A Fiat-Shamir transcript. See the [module-level documentation](self) for details.
+///
+/// Both the prover and the verifier must start with a sponge in the same state, for example, one
+/// obtained through [`Sponge::init`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofStream<H: Sponge> {
+    items: Vec<ProofItem>,
+    items_index: usize,
+    sponge: H,
+}
+
+impl<H: Sponge> ProofStream<H> {
+    /// A new, empty transcript, to be populated by the prover.
+    pub fn new() -> Self {
+        Self::from_items_and_sponge(vec![], H::init())
+    }
+
+    /// A transcript to be [dequeued](Self::dequeue) by the verifier.
+    pub fn from_items(items: Vec<ProofItem>) -> Self {
+        Self::from_items_and_sponge(items, H::init())
+    }
+
+    /// A transcript with a custom initial sponge state, for example, after absorbing a claim.
+    pub fn from_items_and_sponge(items: Vec<ProofItem>, sponge: H) -> Self {
+        Self {
+            items,
+            items_index: 0,
+            sponge,
+        }
+    }
+
+    /// All items in the transcript, including the ones that were already dequeued.
+    pub fn items(&self) -> &[ProofItem] {
+        &self.items
+    }
+
+    /// Consume the transcript, returning all its items.
+    pub fn into_items(self) -> Vec<ProofItem> {
+        self.items
+    }
+
+    /// The current state of the underlying sponge.
+    pub fn sponge(&self) -> &H {
+        &self.sponge
+    }
+
+    /// The number of items not yet dequeued.
+    pub fn num_remaining_items(&self) -> usize {
+        self.items.len() - self.items_index
+    }
+
+    /// Absorb the encoding of some (public) value without adding it to the transcript.
+    pub fn alter_fiat_shamir_state_with(&mut self, value: &impl BFieldCodec) {
+        self.sponge.pad_and_absorb_all(&value.encode());
+    }
+
+    /// Send an item from the prover to the verifier. The item is absorbed into the sponge.
+    pub fn enqueue(&mut self, item: ProofItem) {
+        self.alter_fiat_shamir_state_with(&item);
+        self.items.push(item);
+    }
+
+    /// Receive the next item. The item is absorbed into the sponge, just like the prover did when
+    /// [enqueuing](Self::enqueue) it.
+    pub fn dequeue(&mut self) -> Result<ProofItem> {
+        let Some(item) = self.items.get(self.items_index).cloned() else {
+            return Err(ProofStreamError::EmptyQueue);
+        };
+        self.alter_fiat_shamir_state_with(&item);
+        self.items_index += 1;
+
+        Ok(item)
+    }
+
+    pub fn dequeue_merkle_root(&mut self) -> Result<Digest> {
+        self.dequeue()?.try_into_merkle_root()
+    }
+
+    pub fn dequeue_authentication_structure(&mut self) -> Result<Vec<Digest>> {
+        self.dequeue()?.try_into_authentication_structure()
+    }
+
+    pub fn dequeue_xfield_elements(&mut self) -> Result<Vec<XFieldElement>> {
+        self.dequeue()?.try_into_xfield_elements()
+    }
+
+    pub fn dequeue_bfield_elements(&mut self) -> Result<Vec<BFieldElement>> {
+        self.dequeue()?.try_into_bfield_elements()
+    }
+
+    /// Derive `num_scalars` challenges from the current transcript.
+    ///
+    /// See [`Sponge::sample_scalars`].
+    pub fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement> {
+        self.sponge.sample_scalars(num_scalars)
+    }
+
+    /// Derive `num_indices` indices in the range `[0, upper_bound)` from the current transcript.
+    ///
+    /// See [`Sponge::sample_indices`].
+    pub fn sample_indices(&mut self, upper_bound: u32, num_indices: usize) -> Vec<u32> {
+        self.sponge.sample_indices(upper_bound, num_indices)
+    }
+}
+
+impl<H: Sponge> Default for ProofStream<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Sponge> From<Vec<ProofItem>> for ProofStream<H> {
+    fn from(items: Vec<ProofItem>) -> Self {
+        Self::from_items(items)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ProofStreamError {
+    #[error("cannot dequeue from an empty proof stream")]
+    EmptyQueue,
+
+    #[error("expected proof item of type {expected} but got {actual}")]
+    UnexpectedItem {
+        expected: &'static str,
+        actual: &'static str,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::math::tip5::Tip5;
+
+    #[proptest]
+    fn verifier_derives_same_challenges_as_prover(
+        #[strategy(vec(arb::<ProofItem>(), 0..10))] items: Vec<ProofItem>,
+    ) {
+        let mut prover_stream = ProofStream::<Tip5>::new();
+        let mut prover_challenges = vec![];
+        for item in items.clone() {
+            prover_stream.enqueue(item);
+            prover_challenges.push(prover_stream.sample_scalars(2));
+        }
+
+        let mut verifier_stream = ProofStream::<Tip5>::from(prover_stream.clone().into_items());
+        let mut verifier_challenges = vec![];
+        for item in items {
+            prop_assert_eq!(item, verifier_stream.dequeue()?);
+            verifier_challenges.push(verifier_stream.sample_scalars(2));
+        }
+
+        prop_assert_eq!(prover_challenges, verifier_challenges);
+        prop_assert_eq!(prover_stream.sponge(), verifier_stream.sponge());
+        prop_assert_eq!(0, verifier_stream.num_remaining_items());
+    }
+
+    #[proptest]
+    fn different_items_lead_to_different_challenges(
+        #[strategy(arb())] item: ProofItem,
+        #[strategy(arb())]
+        #[filter(#item != #other_item)]
+        other_item: ProofItem,
+    ) {
+        let mut proof_stream = ProofStream::<Tip5>::new();
+        proof_stream.enqueue(item);
+
+        let mut other_proof_stream = ProofStream::<Tip5>::new();
+        other_proof_stream.enqueue(other_item);
+
+        prop_assert_ne!(
+            proof_stream.sample_scalars(1),
+            other_proof_stream.sample_scalars(1)
+        );
+    }
+
+    #[proptest]
+    fn typed_dequeuing_returns_enqueued_values(
+        #[strategy(arb())] root: Digest,
+        #[strategy(arb())] auth_structure: Vec<Digest>,
+        #[strategy(arb())] xfes: Vec<XFieldElement>,
+        #[strategy(arb())] bfes: Vec<BFieldElement>,
+    ) {
+        let mut proof_stream = ProofStream::<Tip5>::new();
+        proof_stream.enqueue(ProofItem::MerkleRoot(root));
+        proof_stream.enqueue(ProofItem::AuthenticationStructure(auth_structure.clone()));
+        proof_stream.enqueue(ProofItem::XFieldElements(xfes.clone()));
+        proof_stream.enqueue(ProofItem::BFieldElements(bfes.clone()));
+
+        let mut proof_stream = ProofStream::<Tip5>::from(proof_stream.into_items());
+        prop_assert_eq!(root, proof_stream.dequeue_merkle_root()?);
+        prop_assert_eq!(
+            auth_structure,
+            proof_stream.dequeue_authentication_structure()?
+        );
+        prop_assert_eq!(xfes, proof_stream.dequeue_xfield_elements()?);
+        prop_assert_eq!(bfes, proof_stream.dequeue_bfield_elements()?);
+    }
+
+    #[proptest]
+    fn dequeuing_item_of_wrong_type_fails(#[strategy(arb())] root: Digest) {
+        let mut proof_stream = ProofStream::<Tip5>::from(vec![ProofItem::MerkleRoot(root)]);
+        let err = proof_stream.dequeue_xfield_elements().unwrap_err();
+        let expected_err = ProofStreamError::UnexpectedItem {
+            expected: "XFieldElements",
+            actual: "MerkleRoot",
+        };
+        prop_assert_eq!(expected_err, err);
+    }
+
+    #[test]
+    fn dequeuing_from_empty_stream_fails() {
+        let mut proof_stream = ProofStream::<Tip5>::new();
+        let err = proof_stream.dequeue().unwrap_err();
+        assert_eq!(ProofStreamError::EmptyQueue, err);
+    }
+
+    #[proptest]
+    fn proof_item_can_be_encoded_and_decoded(#[strategy(arb())] item: ProofItem) {
+        let decoded = *ProofItem::decode(&item.encode())?;
+        prop_assert_eq!(item, decoded);
+    }
+}
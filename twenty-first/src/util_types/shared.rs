@@ -1,5 +1,6 @@
 use crate::math::digest::Digest;
 use crate::prelude::Tip5;
+use crate::util_types::algebraic_hasher::AlgebraicHasher;
 
 /// Get a root commitment to the entire MMR/list of Merkle trees
 // Follows the description on
@@ -8,12 +9,17 @@ use crate::prelude::Tip5;
 // that the node count described on that website is not used here, as we don't need
 // the extra bits of security that that would provide.
 pub fn bag_peaks(peaks: &[Digest]) -> Digest {
+    bag_peaks_with_hasher::<Tip5>(peaks)
+}
+
+/// Like [`bag_peaks`], but for any [`AlgebraicHasher`], not just [`Tip5`].
+pub fn bag_peaks_with_hasher<H: AlgebraicHasher>(peaks: &[Digest]) -> Digest {
     let mut peaks = peaks.iter().rev();
     let Some(&last_peak) = peaks.next() else {
-        return Tip5::hash(&0u128);
+        return H::hash(&0u128);
     };
 
-    peaks.fold(last_peak, |acc, &peak| Tip5::hash_pair(peak, acc))
+    peaks.fold(last_peak, |acc, &peak| H::hash_pair(peak, acc))
 }
 
 #[cfg(test)]
@@ -0,0 +1,754 @@
+//! A [sparse Merkle tree][smt] mapping [`Digest`] keys to [`Digest`] values.
+//!
+//! Unlike the dense [`MerkleTree`](crate::util_types::merkle_tree::MerkleTree), a
+//! [`SparseMerkleTree`] has a fixed height of up to [`MAX_SPARSE_MERKLE_TREE_HEIGHT`] and
+//! therefore up to 2^256 leafs, almost all of which are empty. Subtrees without any non-empty
+//! leaf are represented by precomputed default nodes and are never stored.
+//!
+//! The position of a key's leaf is determined by the key itself: the path from the root to the
+//! leaf is given by the first `height` bits of the key's first four [`BFieldElement`]s, least
+//! significant bit first, where a 0-bit means “go left.” A leaf slot can hold at most one key.
+//!
+//! [Proofs](SparseMerkleTreeProof) can attest membership as well as non-membership of any number
+//! of keys. Siblings that are default nodes are not included in the proof, and neither are
+//! siblings that can be computed from other parts of the proof.
+//!
+//! [smt]: https://eprint.iacr.org/2016/683
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::result;
+
+use arbitrary::Arbitrary;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::math::b_field_element::BFieldElement;
+use crate::math::bfield_codec::BFieldCodec;
+use crate::math::digest::Digest;
+use crate::math::tip5::Tip5;
+
+/// The maximum height of a [`SparseMerkleTree`]. The leaf's path is derived from the first four
+/// elements of a key, each of which contributes 64 bits.
+pub const MAX_SPARSE_MERKLE_TREE_HEIGHT: usize = 256;
+
+/// The number of sibling flags packed into one element of
+/// [`SparseMerkleTreeProof::default_siblings`].
+const FLAGS_PER_WORD: usize = u32::BITS as usize;
+
+/// Prepended to the key and value when hashing a [leaf](SparseMerkleTreeLeaf). Separates the
+/// digests of leafs from those of internal nodes, which prevents passing off an internal node as
+/// a leaf.
+const LEAF_DOMAIN_SEPARATOR: BFieldElement = BFieldElement::new(1);
+
+type Result<T> = result::Result<T, SparseMerkleTreeError>;
+
+lazy_static! {
+    /// The root of a subtree without any non-empty leaf, indexed by the subtree's height. The
+    /// default node of height 0 is the empty leaf.
+    static ref DEFAULT_NODES: Vec<Digest> = {
+        let mut default_nodes = vec![Digest::default()];
+        for height in 0..MAX_SPARSE_MERKLE_TREE_HEIGHT {
+            let node = default_nodes[height];
+            default_nodes.push(Tip5::hash_pair(node, node));
+        }
+        default_nodes
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMerkleTree {
+    height: usize,
+    leafs: HashMap<NodePath, SparseMerkleTreeLeaf>,
+
+    /// All nodes, including leafs, that are not default nodes, keyed by their depth and path.
+    nodes: HashMap<(usize, NodePath), Digest>,
+}
+
+/// A non-empty leaf of a [`SparseMerkleTree`].
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BFieldCodec, Arbitrary,
+)]
+pub struct SparseMerkleTreeLeaf {
+    pub key: Digest,
+    pub value: Digest,
+}
+
+/// A proof of (non-)membership of one or more keys in a [`SparseMerkleTree`].
+///
+/// For every key, the proof contains the leaf occupying the key's slot, if any. If that leaf's
+/// key is the queried key, the proof attests membership, otherwise non-membership.
+///
+/// The proof does not state the height of the tree it is relative to. The verifier must know the
+/// height, just like the root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BFieldCodec, Arbitrary)]
+pub struct SparseMerkleTreeProof {
+    /// The keys the proof is about.
+    pub keys: Vec<Digest>,
+
+    /// For every key, the leaf occupying the key's slot, or `None` if the slot is empty.
+    pub leafs: Vec<Option<SparseMerkleTreeLeaf>>,
+
+    /// A bitmap with one flag per sibling needed to compute the root, in the order the siblings
+    /// are needed. A set flag means that the sibling is a default node and is not included in
+    /// [`siblings`](Self::siblings).
+    pub default_siblings: Vec<u32>,
+
+    /// The needed siblings that are not default nodes.
+    pub siblings: Vec<Digest>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum SparseMerkleTreeError {
+    #[error("tree height must be in range 1..={MAX_SPARSE_MERKLE_TREE_HEIGHT}, but was {0}")]
+    InvalidHeight(usize),
+
+    #[error("key {key} collides with key {occupant}, which occupies the same leaf")]
+    KeyPathCollision { key: Digest, occupant: Digest },
+}
+
+/// The first `depth` bits of a key's path, identifying a node at that depth. The root has depth 0.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct NodePath([u64; 4]);
+
+impl NodePath {
+    fn from_key(key: Digest, depth: usize) -> Self {
+        let mut limbs = [0; 4];
+        for (i, (limb, element)) in limbs.iter_mut().zip(key.values()).enumerate() {
+            let num_bits = depth.saturating_sub(i * 64).min(64);
+            *limb = match num_bits {
+                64 => element.value(),
+                _ => element.value() & ((1 << num_bits) - 1),
+            };
+        }
+        Self(limbs)
+    }
+
+    fn bit(self, index: usize) -> bool {
+        (self.0[index / 64] >> (index % 64)) & 1 == 1
+    }
+
+    fn toggle_bit(mut self, index: usize) -> Self {
+        self.0[index / 64] ^= 1 << (index % 64);
+        self
+    }
+
+    fn clear_bit(mut self, index: usize) -> Self {
+        self.0[index / 64] &= !(1 << (index % 64));
+        self
+    }
+}
+
+impl SparseMerkleTreeLeaf {
+    pub fn digest(&self) -> Digest {
+        let preimage = [
+            &[LEAF_DOMAIN_SEPARATOR][..],
+            &self.key.values(),
+            &self.value.values(),
+        ]
+        .concat();
+        Tip5::hash_varlen(&preimage)
+    }
+}
+
+impl SparseMerkleTree {
+    /// An empty sparse Merkle tree of the given height.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the height is 0 or exceeds [`MAX_SPARSE_MERKLE_TREE_HEIGHT`].
+    pub fn new(height: usize) -> Result<Self> {
+        if !(1..=MAX_SPARSE_MERKLE_TREE_HEIGHT).contains(&height) {
+            return Err(SparseMerkleTreeError::InvalidHeight(height));
+        }
+
+        Ok(Self {
+            height,
+            leafs: HashMap::new(),
+            nodes: HashMap::new(),
+        })
+    }
+
+    /// The root of an empty sparse Merkle tree of the given height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the height exceeds [`MAX_SPARSE_MERKLE_TREE_HEIGHT`].
+    pub fn empty_root(height: usize) -> Digest {
+        DEFAULT_NODES[height]
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn root(&self) -> Digest {
+        self.node(0, NodePath::default())
+    }
+
+    /// The number of non-empty leafs.
+    pub fn len(&self) -> usize {
+        self.leafs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leafs.is_empty()
+    }
+
+    pub fn get(&self, key: Digest) -> Option<Digest> {
+        self.leafs
+            .get(&NodePath::from_key(key, self.height))
+            .filter(|leaf| leaf.key == key)
+            .map(|leaf| leaf.value)
+    }
+
+    pub fn contains_key(&self, key: Digest) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Insert a key-value pair or update the value of an existing key. Returns the previous value
+    /// of the key, if any.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the key's leaf is already occupied by a different key.
+    pub fn insert(&mut self, key: Digest, value: Digest) -> Result<Option<Digest>> {
+        let path = NodePath::from_key(key, self.height);
+        let previous_value = match self.leafs.get(&path) {
+            Some(occupant) if occupant.key != key => {
+                let occupant = occupant.key;
+                return Err(SparseMerkleTreeError::KeyPathCollision { key, occupant });
+            }
+            occupant => occupant.map(|leaf| leaf.value),
+        };
+
+        let leaf = SparseMerkleTreeLeaf { key, value };
+        self.update_path(path, leaf.digest());
+        self.leafs.insert(path, leaf);
+
+        Ok(previous_value)
+    }
+
+    /// Remove a key from the tree, emptying its leaf. Returns the key's value, if any.
+    pub fn remove(&mut self, key: Digest) -> Option<Digest> {
+        let path = NodePath::from_key(key, self.height);
+        if self.leafs.get(&path)?.key != key {
+            return None;
+        }
+
+        let leaf = self.leafs.remove(&path)?;
+        self.update_path(path, DEFAULT_NODES[0]);
+
+        Some(leaf.value)
+    }
+
+    /// A proof of (non-)membership of a single key.
+    pub fn prove(&self, key: Digest) -> SparseMerkleTreeProof {
+        self.prove_batch(&[key])
+    }
+
+    /// A proof of (non-)membership of all the given keys. Siblings shared by the keys' paths are
+    /// included only once.
+    pub fn prove_batch(&self, keys: &[Digest]) -> SparseMerkleTreeProof {
+        let mut leaf_nodes = BTreeMap::new();
+        let mut leafs = Vec::with_capacity(keys.len());
+        for &key in keys {
+            let path = NodePath::from_key(key, self.height);
+            leaf_nodes.insert(path, self.node(self.height, path));
+            leafs.push(self.leafs.get(&path).copied());
+        }
+
+        let mut default_sibling_flags = vec![];
+        let mut siblings = vec![];
+        let _ = compute_root(self.height, leaf_nodes, |depth, path| {
+            let sibling = self.node(depth, path);
+            let is_default = sibling == DEFAULT_NODES[self.height - depth];
+            default_sibling_flags.push(is_default);
+            if !is_default {
+                siblings.push(sibling);
+            }
+            Some(sibling)
+        });
+
+        let default_siblings = default_sibling_flags
+            .chunks(FLAGS_PER_WORD)
+            .map(|flags| {
+                flags
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &flag)| u32::from(flag) << i)
+                    .sum()
+            })
+            .collect();
+
+        SparseMerkleTreeProof {
+            keys: keys.to_vec(),
+            leafs,
+            default_siblings,
+            siblings,
+        }
+    }
+
+    fn node(&self, depth: usize, path: NodePath) -> Digest {
+        self.nodes
+            .get(&(depth, path))
+            .copied()
+            .unwrap_or(DEFAULT_NODES[self.height - depth])
+    }
+
+    fn set_node(&mut self, depth: usize, path: NodePath, digest: Digest) {
+        if digest == DEFAULT_NODES[self.height - depth] {
+            self.nodes.remove(&(depth, path));
+        } else {
+            self.nodes.insert((depth, path), digest);
+        }
+    }
+
+    /// Set the leaf at the given path and recompute all nodes on its path to the root.
+    fn update_path(&mut self, mut path: NodePath, leaf_digest: Digest) {
+        let mut digest = leaf_digest;
+        for depth in (1..=self.height).rev() {
+            self.set_node(depth, path, digest);
+            let bit_index = depth - 1;
+            let sibling = self.node(depth, path.toggle_bit(bit_index));
+            digest = match path.bit(bit_index) {
+                true => Tip5::hash_pair(sibling, digest),
+                false => Tip5::hash_pair(digest, sibling),
+            };
+            path = path.clear_bit(bit_index);
+        }
+        self.set_node(0, path, digest);
+    }
+}
+
+impl SparseMerkleTreeProof {
+    /// Verify that all [leafs](Self::leafs) are in the stated slots of the tree with the given
+    /// root and height. Also rejects proofs that are not minimal, _i.e._, contain superfluous
+    /// data.
+    ///
+    /// To check the (non-)membership of a specific key, see
+    /// [`verify_membership`](Self::verify_membership) and
+    /// [`verify_non_membership`](Self::verify_non_membership).
+    pub fn verify(&self, expected_root: Digest, height: usize) -> bool {
+        if !(1..=MAX_SPARSE_MERKLE_TREE_HEIGHT).contains(&height)
+            || self.keys.is_empty()
+            || self.keys.len() != self.leafs.len()
+        {
+            return false;
+        }
+
+        let mut leaf_nodes = BTreeMap::new();
+        for (&key, leaf) in self.keys.iter().zip(&self.leafs) {
+            let path = NodePath::from_key(key, height);
+            let leaf_digest = match leaf {
+                Some(leaf) if NodePath::from_key(leaf.key, height) != path => return false,
+                Some(leaf) => leaf.digest(),
+                None => DEFAULT_NODES[0],
+            };
+            if leaf_nodes
+                .insert(path, leaf_digest)
+                .is_some_and(|d| d != leaf_digest)
+            {
+                return false;
+            }
+        }
+
+        let mut num_flags = 0;
+        let mut siblings = self.siblings.iter();
+        let root = compute_root(height, leaf_nodes, |depth, _| {
+            let word = self.default_siblings.get(num_flags / FLAGS_PER_WORD)?;
+            let is_default = (word >> (num_flags % FLAGS_PER_WORD)) & 1 == 1;
+            num_flags += 1;
+            match is_default {
+                true => Some(DEFAULT_NODES[height - depth]),
+                false => siblings.next().copied(),
+            }
+        });
+
+        let num_unused_flags = self.default_siblings.len() * FLAGS_PER_WORD - num_flags;
+        let unused_flags_are_unset = num_unused_flags < FLAGS_PER_WORD
+            && self
+                .default_siblings
+                .last()
+                .is_none_or(|word| word.leading_zeros() as usize >= num_unused_flags);
+
+        root == Some(expected_root) && siblings.len() == 0 && unused_flags_are_unset
+    }
+
+    /// Verify that the key maps to the value in the tree with the given root and height.
+    pub fn verify_membership(
+        &self,
+        expected_root: Digest,
+        height: usize,
+        key: Digest,
+        value: Digest,
+    ) -> bool {
+        let leaf = SparseMerkleTreeLeaf { key, value };
+        self.verify(expected_root, height)
+            && self.slot_of(key).is_some_and(|slot| slot == Some(leaf))
+    }
+
+    /// Verify that the key is not in the tree with the given root and height.
+    pub fn verify_non_membership(&self, expected_root: Digest, height: usize, key: Digest) -> bool {
+        let slot_is_not_taken_by_key =
+            |slot: Option<SparseMerkleTreeLeaf>| slot.is_none_or(|leaf| leaf.key != key);
+        self.verify(expected_root, height)
+            && self.slot_of(key).is_some_and(slot_is_not_taken_by_key)
+    }
+
+    /// The content of the key's slot as stated by this proof, or `None` if the proof is not about
+    /// the key.
+    fn slot_of(&self, key: Digest) -> Option<Option<SparseMerkleTreeLeaf>> {
+        let index = self.keys.iter().position(|&k| k == key)?;
+        self.leafs.get(index).copied()
+    }
+}
+
+/// Compute the root from the given nodes at depth `height`. Missing siblings are requested
+/// through `sibling`, which receives the depth and path of the requested node. The order in
+/// which siblings are requested is deterministic. Returns `None` if `sibling` does, or if
+/// there are no nodes to start from.
+fn compute_root(
+    height: usize,
+    mut nodes: BTreeMap<NodePath, Digest>,
+    mut sibling: impl FnMut(usize, NodePath) -> Option<Digest>,
+) -> Option<Digest> {
+    for depth in (1..=height).rev() {
+        let bit_index = depth - 1;
+        let mut parents = BTreeMap::new();
+        for (&path, &digest) in &nodes {
+            let parent_path = path.clear_bit(bit_index);
+            if parents.contains_key(&parent_path) {
+                continue;
+            }
+
+            let sibling_path = path.toggle_bit(bit_index);
+            let sibling_digest = match nodes.get(&sibling_path) {
+                Some(&sibling_digest) => sibling_digest,
+                None => sibling(depth, sibling_path)?,
+            };
+            let parent = match path.bit(bit_index) {
+                true => Tip5::hash_pair(sibling_digest, digest),
+                false => Tip5::hash_pair(digest, sibling_digest),
+            };
+            parents.insert(parent_path, parent);
+        }
+        nodes = parents;
+    }
+
+    nodes.into_values().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use itertools::Itertools;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use super::*;
+    use crate::bfe;
+
+    /// A tree of maximal height, in which key collisions are practically impossible.
+    fn tree_with_entries(entries: &[(Digest, Digest)]) -> SparseMerkleTree {
+        let mut tree = SparseMerkleTree::new(MAX_SPARSE_MERKLE_TREE_HEIGHT).unwrap();
+        for &(key, value) in entries {
+            tree.insert(key, value).unwrap();
+        }
+        tree
+    }
+
+    #[test]
+    fn tree_height_must_be_valid() {
+        for height in [0, MAX_SPARSE_MERKLE_TREE_HEIGHT + 1] {
+            let err = SparseMerkleTree::new(height).unwrap_err();
+            assert_eq!(SparseMerkleTreeError::InvalidHeight(height), err);
+        }
+    }
+
+    #[test]
+    fn root_of_empty_tree_is_default_node() {
+        for height in [1, 2, 10, MAX_SPARSE_MERKLE_TREE_HEIGHT] {
+            let tree = SparseMerkleTree::new(height).unwrap();
+            assert_eq!(SparseMerkleTree::empty_root(height), tree.root());
+            assert!(tree.is_empty());
+        }
+    }
+
+    #[test]
+    fn root_of_tiny_tree_can_be_computed_by_hand() {
+        let key = Digest::new([BFieldElement::new(1); Digest::LEN]);
+        let value = Digest::new([BFieldElement::new(42); Digest::LEN]);
+        let mut tree = SparseMerkleTree::new(2).unwrap();
+        tree.insert(key, value).unwrap();
+
+        // the key's path is 0b01 (least significant bit first): first right, then left
+        let leaf_preimage = [&[bfe!(1)][..], &key.values(), &value.values()].concat();
+        let leaf = Tip5::hash_varlen(&leaf_preimage);
+        let empty = Digest::default();
+        let right_subtree = Tip5::hash_pair(leaf, empty);
+        let left_subtree = Tip5::hash_pair(empty, empty);
+        let expected_root = Tip5::hash_pair(left_subtree, right_subtree);
+        assert_eq!(expected_root, tree.root());
+    }
+
+    #[proptest(cases = 20)]
+    fn root_is_independent_of_insertion_order(
+        #[strategy(vec((arb(), arb()), 0..10))] entries: Vec<(Digest, Digest)>,
+    ) {
+        let tree = tree_with_entries(&entries);
+        let reversed_entries = entries.into_iter().rev().collect_vec();
+        let other_tree = tree_with_entries(&reversed_entries);
+        prop_assert_eq!(tree.root(), other_tree.root());
+    }
+
+    #[proptest(cases = 20)]
+    fn removing_all_entries_results_in_empty_tree(
+        #[strategy(vec((arb(), arb()), 0..10))] entries: Vec<(Digest, Digest)>,
+    ) {
+        let mut tree = tree_with_entries(&entries);
+        for (key, _) in entries {
+            tree.remove(key);
+        }
+        prop_assert!(tree.is_empty());
+        prop_assert!(tree.nodes.is_empty());
+        prop_assert_eq!(SparseMerkleTree::empty_root(tree.height()), tree.root());
+    }
+
+    #[proptest(cases = 20)]
+    fn insert_get_update_and_remove_behave_like_a_map(
+        #[strategy(arb())] key: Digest,
+        #[strategy(arb())] value: Digest,
+        #[strategy(arb())] new_value: Digest,
+    ) {
+        let mut tree = tree_with_entries(&[]);
+        prop_assert_eq!(None, tree.insert(key, value)?);
+        prop_assert_eq!(Some(value), tree.get(key));
+        prop_assert_eq!(Some(value), tree.insert(key, new_value)?);
+        prop_assert_eq!(Some(new_value), tree.get(key));
+        prop_assert_eq!(1, tree.len());
+        prop_assert_eq!(Some(new_value), tree.remove(key));
+        prop_assert!(!tree.contains_key(key));
+        prop_assert_eq!(None, tree.remove(key));
+    }
+
+    #[test]
+    fn inserting_key_into_occupied_leaf_fails() {
+        let key = Digest::new([BFieldElement::new(0b101); Digest::LEN]);
+        let other_key = Digest::new([BFieldElement::new(0b001); Digest::LEN]);
+        let value = Digest::default();
+
+        let mut tree = SparseMerkleTree::new(2).unwrap();
+        tree.insert(key, value).unwrap();
+        let err = tree.insert(other_key, value).unwrap_err();
+        let expected_err = SparseMerkleTreeError::KeyPathCollision {
+            key: other_key,
+            occupant: key,
+        };
+        assert_eq!(expected_err, err);
+        assert_eq!(None, tree.remove(other_key));
+        assert_eq!(Some(value), tree.get(key));
+    }
+
+    #[proptest(cases = 20)]
+    fn membership_proofs_can_be_verified(
+        #[strategy(vec((arb(), arb()), 1..10))] entries: Vec<(Digest, Digest)>,
+    ) {
+        let tree = tree_with_entries(&entries);
+        for (key, value) in entries {
+            let proof = tree.prove(key);
+            prop_assert!(proof.verify_membership(tree.root(), tree.height(), key, value));
+            prop_assert!(!proof.verify_non_membership(tree.root(), tree.height(), key));
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn non_membership_proofs_can_be_verified(
+        #[strategy(vec((arb(), arb()), 0..10))] entries: Vec<(Digest, Digest)>,
+        #[strategy(arb())] absent_key: Digest,
+        #[strategy(arb())] value: Digest,
+    ) {
+        let tree = tree_with_entries(&entries);
+        prop_assume!(!tree.contains_key(absent_key));
+
+        let proof = tree.prove(absent_key);
+        prop_assert!(proof.verify_non_membership(tree.root(), tree.height(), absent_key));
+        prop_assert!(!proof.verify_membership(tree.root(), tree.height(), absent_key, value));
+    }
+
+    #[test]
+    fn non_membership_of_key_colliding_with_occupant_can_be_verified() {
+        let key = Digest::new([BFieldElement::new(0b101); Digest::LEN]);
+        let other_key = Digest::new([BFieldElement::new(0b001); Digest::LEN]);
+        let value = Digest::default();
+
+        let mut tree = SparseMerkleTree::new(2).unwrap();
+        tree.insert(key, value).unwrap();
+        let proof = tree.prove(other_key);
+        assert!(proof.verify_non_membership(tree.root(), tree.height(), other_key));
+        assert!(!proof.verify_membership(tree.root(), tree.height(), other_key, value));
+    }
+
+    #[proptest(cases = 20)]
+    fn proof_for_wrong_value_or_root_fails_verification(
+        #[strategy(vec((arb(), arb()), 1..10))] entries: Vec<(Digest, Digest)>,
+        #[strategy(arb())] wrong_value: Digest,
+        #[strategy(arb())] wrong_root: Digest,
+    ) {
+        let tree = tree_with_entries(&entries);
+        let (key, value) = entries[0];
+        prop_assume!(value != wrong_value);
+        prop_assume!(tree.root() != wrong_root);
+
+        let proof = tree.prove(key);
+        prop_assert!(!proof.verify_membership(tree.root(), tree.height(), key, wrong_value));
+        prop_assert!(!proof.verify_membership(wrong_root, tree.height(), key, value));
+    }
+
+    #[proptest(cases = 20)]
+    fn batch_proofs_can_be_verified(
+        #[strategy(vec((arb(), arb()), 1..20))] entries: Vec<(Digest, Digest)>,
+        #[strategy(vec(arb(), 0..5))] absent_keys: Vec<Digest>,
+    ) {
+        let tree = tree_with_entries(&entries);
+        let keys = entries
+            .iter()
+            .map(|&(key, _)| key)
+            .chain(absent_keys.clone());
+        let proof = tree.prove_batch(&keys.collect_vec());
+
+        prop_assert!(proof.verify(tree.root(), tree.height()));
+        for (key, value) in entries {
+            prop_assert!(proof.verify_membership(tree.root(), tree.height(), key, value));
+        }
+        for key in absent_keys {
+            prop_assert!(proof.verify_non_membership(tree.root(), tree.height(), key));
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn batch_proof_is_no_larger_than_individual_proofs(
+        #[strategy(vec((arb(), arb()), 1..20))] entries: Vec<(Digest, Digest)>,
+    ) {
+        let tree = tree_with_entries(&entries);
+        let keys = entries.iter().map(|&(key, _)| key).collect_vec();
+        let batch_proof = tree.prove_batch(&keys);
+
+        let num_individual_siblings = keys
+            .iter()
+            .map(|&key| tree.prove(key).siblings.len())
+            .sum::<usize>();
+        prop_assert!(batch_proof.siblings.len() <= num_individual_siblings);
+        prop_assert!(batch_proof.siblings.len() <= tree.nodes.len());
+    }
+
+    #[proptest(cases = 20)]
+    fn proofs_omit_default_siblings(
+        #[strategy(vec((arb(), arb()), 1..10))] entries: Vec<(Digest, Digest)>,
+    ) {
+        let tree = tree_with_entries(&entries);
+        let (key, _) = entries[0];
+        let proof = tree.prove(key);
+
+        // Every non-default sibling is the root of a subtree containing some other entry.
+        let num_other_keys = entries.iter().map(|(k, _)| k).collect::<HashSet<_>>().len() - 1;
+        prop_assert!(proof.siblings.len() <= num_other_keys);
+        let expected_num_words = MAX_SPARSE_MERKLE_TREE_HEIGHT / FLAGS_PER_WORD;
+        prop_assert_eq!(expected_num_words, proof.default_siblings.len());
+    }
+
+    #[proptest(cases = 20)]
+    fn tampering_with_proof_leads_to_verification_failure(
+        #[strategy(vec((arb(), arb()), 2..10))] entries: Vec<(Digest, Digest)>,
+        #[strategy(arb())] digest: Digest,
+        #[strategy(0_usize..3)] tampering: usize,
+    ) {
+        let tree = tree_with_entries(&entries);
+        let (key, _) = entries[0];
+        let mut proof = tree.prove(key);
+        prop_assert!(!proof.verify(tree.root(), tree.height() - 1));
+
+        match tampering {
+            0 => proof.siblings.push(digest),
+            1 => proof.siblings[0] = digest,
+            _ => proof.default_siblings[0] ^= 1,
+        }
+        prop_assert!(!proof.verify(tree.root(), tree.height()));
+    }
+
+    #[test]
+    fn internal_node_cannot_be_passed_off_as_leaf_of_shorter_tree() {
+        // Find a tree of height 2 in which some node at depth 1 has a left child that, read as a
+        // key, has a path through that very node. In a tree of height 1, a leaf with the
+        // children as key and value would be in that node's slot.
+        let (tree, parent_path) = (0..)
+            .find_map(|seed: u64| {
+                let mut tree = SparseMerkleTree::new(2).unwrap();
+                for i in 0..4 {
+                    let key = Digest::new([bfe!(i); Digest::LEN]);
+                    let value = Digest::new([bfe!(seed); Digest::LEN]);
+                    tree.insert(key, value).unwrap();
+                }
+                let parent_path = [0, 1].into_iter().find(|&parent_path| {
+                    let left_child = tree.node(2, NodePath([parent_path, 0, 0, 0]));
+                    NodePath::from_key(left_child, 1) == NodePath([parent_path, 0, 0, 0])
+                })?;
+                Some((tree, parent_path))
+            })
+            .unwrap();
+
+        let left_child = tree.node(2, NodePath([parent_path, 0, 0, 0]));
+        let right_child = tree.node(2, NodePath([parent_path | 0b10, 0, 0, 0]));
+        let sibling = tree.node(1, NodePath([parent_path ^ 1, 0, 0, 0]));
+        let absent_key = Digest::new([bfe!(parent_path + 4); Digest::LEN]);
+        let forged_leaf = SparseMerkleTreeLeaf {
+            key: left_child,
+            value: right_child,
+        };
+        let forged_proof = SparseMerkleTreeProof {
+            keys: vec![absent_key],
+            leafs: vec![Some(forged_leaf)],
+            default_siblings: vec![0],
+            siblings: vec![sibling],
+        };
+
+        for height in [1, 2] {
+            assert!(!forged_proof.verify_non_membership(tree.root(), height, absent_key));
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn proofs_in_small_trees_can_be_verified(
+        #[strategy(1_usize..8)] height: usize,
+        #[strategy(vec((arb(), arb()), 0..20))] entries: Vec<(Digest, Digest)>,
+        #[strategy(vec(arb(), 1..10))] keys: Vec<Digest>,
+    ) {
+        let mut tree = SparseMerkleTree::new(height)?;
+        for (key, value) in entries {
+            let _ = tree.insert(key, value);
+        }
+
+        let proof = tree.prove_batch(&keys);
+        prop_assert!(proof.verify(tree.root(), tree.height()));
+        for key in keys {
+            match tree.get(key) {
+                Some(value) => {
+                    prop_assert!(proof.verify_membership(tree.root(), tree.height(), key, value))
+                }
+                None => prop_assert!(proof.verify_non_membership(tree.root(), tree.height(), key)),
+            }
+        }
+    }
+
+    #[proptest]
+    fn proof_can_be_encoded_and_decoded(#[strategy(arb())] proof: SparseMerkleTreeProof) {
+        let decoded = *SparseMerkleTreeProof::decode(&proof.encode())?;
+        prop_assert_eq!(proof, decoded);
+    }
+}